@@ -0,0 +1,380 @@
+//! Derive macros used by `mjml-builder`, the generated code refers to its private items.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Field, Fields, GenericArgument, LitStr, PathArguments, Type};
+
+/// Generates the rendering, the parsing and the schema of an attributes struct.
+///
+/// Every field is an attribute named after the field in kebab-case, its value is read and written
+/// through `AttributeValue`. `Url` fields are reported as such in the schema.
+///
+/// Field annotations, in `#[mj(...)]`:
+/// - `name = "..."`: attribute name when it is not the field name.
+/// - `default = "..."`: value used by MJML when the attribute is missing, for the schema.
+/// - `required`: the attribute must always be set.
+/// - `list = "..."`: a `Vec` field, `"css-class"` for space separated classes or `"font-family"`
+///   for comma separated font names. `Vec` fields need it.
+/// - `parse = "path"`: function used instead of `AttributeValue::parse_value`, its result is
+///   converted with `Into` so it can fill an `Option`.
+/// - `flag = "..."`: a `bool` field rendered as `name="flag"` when set, `"true"` is accepted too.
+/// - `flatten`: an `Option` of another attributes struct whose attributes are inlined. Combined
+///   with `flag`, the flag attribute is rendered whenever the nested struct is present.
+/// - `skip`: not an attribute, the field is only written in the generated Rust source.
+///
+/// The struct itself takes `#[mj(no_default)]` when it does not implement `Default`, its generated
+/// Rust source then lists every field.
+#[proc_macro_derive(Attributes, attributes(mj))]
+pub fn derive_attributes(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    match attributes(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct Options {
+    name: Option<String>,
+    default: Option<String>,
+    required: bool,
+    flag: Option<String>,
+    list: Option<LitStr>,
+    flatten: bool,
+    skip: bool,
+    parse: Option<syn::Path>,
+}
+
+impl Options {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("mj")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("default") {
+                    options.default = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("flag") {
+                    options.flag = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("list") {
+                    options.list = Some(meta.value()?.parse::<LitStr>()?);
+                } else if meta.path.is_ident("parse") {
+                    options.parse = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("required") {
+                    options.required = true;
+                } else if meta.path.is_ident("flatten") {
+                    options.flatten = true;
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error("unknown mj option"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+enum Kind {
+    Value,
+    CssClassList,
+    FontFamilyList,
+    Flag(String),
+    Flatten(Option<String>),
+}
+
+/// Last identifier of the type, and of its generic argument if any: `Option<Url>` gives
+/// `("Option", Some("Url"))`.
+fn type_names(ty: &Type) -> (String, Option<String>) {
+    let Type::Path(path) = ty else {
+        return (String::new(), None);
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return (String::new(), None);
+    };
+    let argument = match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(GenericArgument::Type(inner)) => Some(type_names(inner).0),
+            _ => None,
+        },
+        _ => None,
+    };
+    (segment.ident.to_string(), argument)
+}
+
+fn attributes(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "expected a struct"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(input.span(), "expected named fields"));
+    };
+
+    let mut no_default = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("mj")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("no_default") {
+                no_default = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown mj option"))
+            }
+        })?;
+    }
+
+    let mut render = vec![];
+    let mut rust_fields = vec![];
+    let mut set = vec![];
+    let mut set_nested = vec![];
+    let mut schema = vec![];
+    let mut nested_schemas = vec![];
+    for field in &fields.named {
+        let options = Options::parse(field)?;
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let name = options
+            .name
+            .clone()
+            .unwrap_or_else(|| ident.to_string().replace('_', "-"));
+        let (outer, inner) = type_names(ty);
+        let field_name = ident.to_string();
+        rust_fields.push(if no_default {
+            quote! { out.field(#field_name, &self.#ident); }
+        } else {
+            quote! {
+                if self.#ident != base.#ident {
+                    out.field(#field_name, &self.#ident);
+                }
+            }
+        });
+        if options.skip {
+            continue;
+        }
+
+        let kind = if options.flatten {
+            Kind::Flatten(options.flag.clone())
+        } else if let Some(flag) = &options.flag {
+            Kind::Flag(flag.clone())
+        } else if let Some(list) = &options.list {
+            match list.value().as_str() {
+                _ if outer != "Vec" => {
+                    return Err(syn::Error::new(ty.span(), "list expects a Vec"));
+                }
+                "css-class" => Kind::CssClassList,
+                "font-family" => Kind::FontFamilyList,
+                _ => {
+                    return Err(syn::Error::new(
+                        list.span(),
+                        "expected \"css-class\" or \"font-family\"",
+                    ))
+                }
+            }
+        } else if outer == "Vec" {
+            return Err(syn::Error::new(
+                ty.span(),
+                "Vec fields need #[mj(list = \"css-class\")] or #[mj(list = \"font-family\")]",
+            ));
+        } else {
+            Kind::Value
+        };
+
+        match &kind {
+            Kind::Value => {
+                render.push(quote! {
+                    crate::model::AttributeValue::render_value(&self.#ident, #name, writer)?;
+                });
+                set.push(match &options.parse {
+                    Some(parse) => quote! { #name => self.#ident = #parse(value)?.into(), },
+                    None => quote! {
+                        #name => self.#ident = crate::model::AttributeValue::parse_value(value)?,
+                    },
+                });
+            }
+            Kind::CssClassList => {
+                render
+                    .push(quote! { crate::model::render_css_class(writer, #name, &self.#ident)?; });
+                set.push(quote! { #name => self.#ident = crate::model::parse_css_class(value), });
+            }
+            Kind::FontFamilyList => {
+                render.push(quote! {
+                    crate::model::render_font_family(writer, #name, &self.#ident)?;
+                });
+                set.push(quote! { #name => self.#ident = crate::model::parse_font_family(value), });
+            }
+            Kind::Flag(flag) => {
+                render.push(quote! {
+                    if self.#ident {
+                        crate::model::AttributeWriter::attribute(writer, #name, &#flag)?;
+                    }
+                });
+                set.push(quote! { #name => self.#ident = value == #flag || value == "true", });
+            }
+            Kind::Flatten(flag) => {
+                let flag_render = flag.as_ref().map(|flag| quote! { crate::model::AttributeWriter::attribute(writer, #flag, &#flag)?; });
+                render.push(quote! {
+                    if let Some(nested) = &self.#ident {
+                        #flag_render
+                        crate::model::MjBaseComponentAttributes::render(nested, writer)?;
+                    }
+                });
+                if let Some(flag) = flag {
+                    set.push(quote! {
+                        #flag => {
+                            self.#ident.get_or_insert_with(Default::default);
+                        }
+                    });
+                }
+                set_nested.push(quote! {
+                    let mut nested = self.#ident.clone().unwrap_or_default();
+                    if crate::model::MjBaseComponentAttributes::set(&mut nested, name, value)? {
+                        self.#ident = Some(nested);
+                        return Ok(true);
+                    }
+                });
+                let nested = inner
+                    .as_ref()
+                    .map(|inner| syn::Ident::new(inner, ty.span()))
+                    .ok_or_else(|| syn::Error::new(ty.span(), "flatten expects an Option"))?;
+                nested_schemas.push(quote! {
+                    <#nested as crate::model::schema::AttributesSchema>::ATTRIBUTES
+                });
+            }
+        }
+
+        if let Kind::Flatten(flag) = &kind {
+            if let Some(flag) = flag {
+                schema.push(quote! {
+                    crate::model::schema::AttributeSchema {
+                        name: #flag,
+                        kind: crate::model::schema::ValueKind::Boolean,
+                        default: None,
+                        required: false,
+                    }
+                });
+            }
+            continue;
+        }
+        let value_kind = match (&kind, outer.as_str(), inner.as_deref()) {
+            (Kind::CssClassList, _, _) => quote! { crate::model::schema::ValueKind::CssClass },
+            (Kind::FontFamilyList, _, _) => quote! { crate::model::schema::ValueKind::FontFamily },
+            (Kind::Flag(_), _, _) => quote! { crate::model::schema::ValueKind::Boolean },
+            (_, "Url", _) | (_, "Option", Some("Url")) => {
+                quote! { crate::model::schema::ValueKind::Url }
+            }
+            _ => quote! { <#ty as crate::model::AttributeValue>::KIND },
+        };
+        let default = match &options.default {
+            Some(default) => quote! { Some(#default) },
+            None => quote! { None },
+        };
+        let required = options.required;
+        schema.push(quote! {
+            crate::model::schema::AttributeSchema {
+                name: #name,
+                kind: #value_kind,
+                default: #default,
+                required: #required,
+            }
+        });
+    }
+
+    let own = quote! { &[#(#schema),*] };
+    let all = nested_schemas.iter().fold(own, |all, nested| {
+        quote! {
+            &crate::model::schema::concat::<{ (#all).len() + (#nested).len() }>(#all, #nested)
+        }
+    });
+
+    let name = &input.ident;
+    let base = (!no_default).then(|| quote! { let base = <Self as Default>::default(); });
+    let rest_default = !no_default;
+    Ok(quote! {
+        impl crate::model::MjBaseComponentAttributes for #name {
+            fn render(
+                &self,
+                writer: &mut dyn crate::model::AttributeWriter,
+            ) -> std::fmt::Result {
+                #(#render)*
+                Ok(())
+            }
+
+            fn set(
+                &mut self,
+                name: &str,
+                value: &str,
+            ) -> Result<bool, crate::model::ParseValueError> {
+                match name {
+                    #(#set)*
+                    _ => {
+                        #(#set_nested)*
+                        return Ok(false);
+                    }
+                }
+                #[allow(unreachable_code)]
+                Ok(true)
+            }
+        }
+
+        impl crate::model::schema::AttributesSchema for #name {
+            const ATTRIBUTES: &'static [crate::model::schema::AttributeSchema] = #all;
+        }
+
+        impl crate::model::codegen::ToRust for #name {
+            fn to_rust(&self, out: &mut crate::model::codegen::RustWriter) {
+                #base
+                out.open_struct::<Self>();
+                #(#rust_fields)*
+                out.close_struct(#rest_default);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::attributes;
+
+    fn error(input: syn::DeriveInput) -> String {
+        attributes(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn lists() {
+        let derived = attributes(&syn::parse_quote! {
+            struct Attributes {
+                #[mj(list = "css-class")]
+                css_class: Vec<crate::model::CssClass>,
+                #[mj(default = "Arial", list = "font-family")]
+                font_family: Vec<String>,
+            }
+        })
+        .unwrap()
+        .to_string();
+        assert!(derived.contains("parse_css_class"));
+        assert!(derived.contains("parse_font_family"));
+
+        assert!(error(syn::parse_quote! {
+            struct Attributes {
+                css_class: Vec<Classes>,
+            }
+        })
+        .contains("Vec fields need"));
+        assert!(error(syn::parse_quote! {
+            struct Attributes {
+                #[mj(list = "colors")]
+                colors: Vec<String>,
+            }
+        })
+        .contains("expected"));
+        assert!(error(syn::parse_quote! {
+            struct Attributes {
+                #[mj(list = "css-class")]
+                css_class: Option<String>,
+            }
+        })
+        .contains("list expects a Vec"));
+    }
+}
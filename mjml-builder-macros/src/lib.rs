@@ -0,0 +1,203 @@
+//! Macros of `mjml-builder` that need the model itself at compile time.
+
+use mjml_builder::model::element::Element;
+use mjml_builder::model::macro_support::{self, PLACEHOLDER_END, PLACEHOLDER_START};
+use mjml_builder::model::mjml::Mjml;
+use mjml_builder::model::schema::{self, ValueKind};
+use mjml_builder::template::CompiledTemplate;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Ident, LitStr, Token, Visibility};
+
+const TEMPLATE_START: &str = "{{";
+
+/// Reads a `.mjml` file when compiling and turns it into a function building its [`Mjml`].
+///
+/// ```ignore
+/// mjml_builder_macros::include_mjml! {
+///     /// Sent right after the sign up.
+///     pub fn welcome("templates/welcome.mjml");
+/// }
+///
+/// let mjml = welcome("Ada", "https://example.com/confirm");
+/// ```
+///
+/// The path is relative to the directory of the crate manifest. The document is checked against
+/// the model, so an unknown tag or attribute, an invalid value or a misplaced component fails the
+/// build, and the function builds the typed components directly.
+///
+/// Each `{{ name }}` placeholder of the content or of the attributes becomes an
+/// `impl Display` parameter, in order of first appearance, with `-` and `.` replaced by `_` in its
+/// name. Placeholders are only allowed in attributes holding free text, such as `href` or
+/// `css-class`, so that the function cannot fail. The values are set on the model as they are,
+/// `{{{ name }}}` is the same as `{{ name }}`, and `\{{` writes a literal `{{`.
+#[proc_macro]
+pub fn include_mjml(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as IncludeMjml);
+    match include(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+struct IncludeMjml {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    path: LitStr,
+}
+
+impl Parse for IncludeMjml {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![fn]>()?;
+        let name = input.parse()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let path = content.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            path,
+        })
+    }
+}
+
+fn include(input: &IncludeMjml) -> syn::Result<TokenStream2> {
+    let error = |message: String| syn::Error::new(input.path.span(), message);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|err| error(err.to_string()))?;
+    let path = std::path::Path::new(&manifest_dir).join(input.path.value());
+    let source = std::fs::read_to_string(&path)
+        .map_err(|err| error(format!("cannot read {}: {}", path.display(), err)))?;
+    let mut element = Element::from_markup(&source).map_err(|err| error(err.to_string()))?;
+    let mut placeholders = Vec::new();
+    collect_placeholders(&element, &mut placeholders).map_err(error)?;
+    let mut params: Vec<Ident> = Vec::new();
+    for placeholder in &placeholders {
+        let param = syn::parse_str::<Ident>(&placeholder.replace(['-', '.'], "_"))
+            .map_err(|_| error(format!("placeholder `{}` is not a valid name", placeholder)))?;
+        if params.contains(&param) {
+            return Err(error(format!(
+                "placeholder `{}` has the same parameter name as another one",
+                placeholder
+            )));
+        }
+        params.push(param);
+    }
+    mark_placeholders(&mut element, &placeholders);
+    let mjml = Mjml::try_from(&element).map_err(|err| error(err.to_string()))?;
+    let names: Vec<String> = params.iter().map(ToString::to_string).collect();
+    let expression: TokenStream2 = macro_support::rust_expression(&mjml, &names)
+        .parse()
+        .map_err(|err: proc_macro2::LexError| error(err.to_string()))?;
+
+    let attrs = &input.attrs;
+    let vis = &input.vis;
+    let name = &input.name;
+    let path = path.display().to_string();
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #name(#(#params: impl ::std::fmt::Display),*) -> ::mjml_builder::model::mjml::Mjml {
+            // rebuilds the function when the template changes
+            const _: &str = ::std::include_str!(#path);
+            #expression
+        }
+    })
+}
+
+/// Names of the placeholders, in order of first appearance.
+fn collect_placeholders(element: &Element, names: &mut Vec<String>) -> Result<(), String> {
+    for (name, value) in &element.attributes {
+        let found = placeholders(value)?;
+        if found.is_empty() {
+            continue;
+        }
+        let kind = schema::component(&element.tag_name)
+            .and_then(|component| component.attribute(name))
+            .map(|attribute| attribute.kind);
+        if let Some(kind) = kind.filter(|kind| !is_text(kind)) {
+            return Err(format!(
+                "attribute `{}` of <{}> takes a {} value, placeholders are only allowed in text attributes",
+                name,
+                element.tag_name,
+                kind_name(&kind)
+            ));
+        }
+        add_new(names, found);
+    }
+    if let Some(content) = &element.content {
+        add_new(names, placeholders(content)?);
+    }
+    for child in &element.children {
+        collect_placeholders(child, names)?;
+    }
+    Ok(())
+}
+
+fn is_text(kind: &ValueKind) -> bool {
+    matches!(
+        kind,
+        ValueKind::String | ValueKind::Url | ValueKind::CssClass | ValueKind::FontFamily
+    )
+}
+
+fn kind_name(kind: &ValueKind) -> String {
+    match kind {
+        ValueKind::Enum { name, .. } => name.to_string(),
+        kind => format!("{:?}", kind),
+    }
+}
+
+fn placeholders(value: &str) -> Result<Vec<String>, String> {
+    if !value.contains(TEMPLATE_START) {
+        return Ok(vec![]);
+    }
+    CompiledTemplate::parse(value.to_string())
+        .map(|template| template.placeholders().to_vec())
+        .map_err(|err| err.to_string())
+}
+
+fn add_new(names: &mut Vec<String>, found: Vec<String>) {
+    for name in found {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+}
+
+/// Replaces the placeholders by the markers of their parameter, which the model keeps as they are
+/// and the generated source turns into `format!` arguments.
+fn mark_placeholders(element: &mut Element, placeholders: &[String]) {
+    for value in element.attributes.values_mut() {
+        *value = mark(value, placeholders);
+    }
+    if let Some(content) = &mut element.content {
+        *content = mark(content, placeholders);
+    }
+    for child in &mut element.children {
+        mark_placeholders(child, placeholders);
+    }
+}
+
+fn mark(value: &str, placeholders: &[String]) -> String {
+    if !value.contains(TEMPLATE_START) {
+        return value.to_string();
+    }
+    let markers = placeholders
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let marker = format!("{}{}{}", PLACEHOLDER_START, index, PLACEHOLDER_END);
+            (name.as_str(), marker)
+        })
+        .collect::<Vec<_>>();
+    CompiledTemplate::parse(value.to_string())
+        .and_then(|template| template.render_to_string(markers.as_slice()))
+        .expect("placeholders are validated before")
+}
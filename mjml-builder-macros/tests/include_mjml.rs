@@ -0,0 +1,54 @@
+use mjml_builder::model::mjml::Mjml;
+use mjml_builder_macros::include_mjml;
+
+include_mjml! {
+    /// Sent right after the sign up.
+    fn welcome("tests/templates/welcome.mjml");
+}
+
+include_mjml! {
+    fn receipt("tests/templates/receipt.mjml");
+}
+
+include_mjml! {
+    fn literal("tests/templates/literal.mjml");
+}
+
+#[test]
+fn placeholders_become_parameters() {
+    let mjml = welcome("Ada", "https://example.com/confirm?id=1");
+
+    let expected: Mjml = include_str!("templates/welcome.mjml")
+        .replace("{{ name }}", "Ada")
+        .replace("{{ confirm-url }}", "https://example.com/confirm?id=1")
+        .parse()
+        .unwrap();
+    assert_eq!(mjml, expected);
+    assert!(mjml
+        .to_string()
+        .contains("Hello Ada, {count} items are waiting."));
+}
+
+#[test]
+fn typed_components() {
+    let mjml = receipt("Arial", "dark", 42);
+
+    let expected: Mjml = include_str!("templates/receipt.mjml")
+        .replace("{{ font }}", "Arial")
+        .replace("{{ theme.class }}", "dark")
+        .replace("{{ total }}", "42")
+        .parse()
+        .unwrap();
+    assert_eq!(mjml, expected);
+    let rendered = mjml.to_string();
+    assert!(rendered.contains("<mj-all font-family=\"Arial\"/>"));
+    assert!(rendered.contains("<mj-image alt=\"logo\"/>"));
+    assert!(rendered.contains("css-class=\"total dark\""));
+    assert!(rendered.contains("Total: 42"));
+}
+
+#[test]
+fn literal_and_raw_placeholders() {
+    let mjml = literal("<b>new</b>");
+    assert!(mjml.to_string().contains("{{ kept }} <b>new</b>"));
+}
@@ -0,0 +1,123 @@
+use crate::model::mjml::{Mjml, DEFAULT_CHUNK_SIZE};
+
+impl Mjml {
+    pub async fn render_tokio<W>(&self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        self.render_tokio_chunked(writer, DEFAULT_CHUNK_SIZE).await
+    }
+
+    /// Writes and flushes the document every `chunk_size` bytes, see [`Mjml::render_chunks`].
+    pub async fn render_tokio_chunked<W>(
+        &self,
+        writer: &mut W,
+        chunk_size: usize,
+    ) -> std::io::Result<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        let mut chunks = self.render_chunks(chunk_size);
+        while let Some(chunk) = chunks.next_chunk().map_err(std::io::Error::other)? {
+            writer.write_all(chunk.as_bytes()).await?;
+            writer.flush().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn render_futures<W>(&self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: futures_util::io::AsyncWrite + Unpin + ?Sized,
+    {
+        self.render_futures_chunked(writer, DEFAULT_CHUNK_SIZE)
+            .await
+    }
+
+    /// Writes and flushes the document every `chunk_size` bytes, see [`Mjml::render_chunks`].
+    pub async fn render_futures_chunked<W>(
+        &self,
+        writer: &mut W,
+        chunk_size: usize,
+    ) -> std::io::Result<()>
+    where
+        W: futures_util::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use futures_util::io::AsyncWriteExt;
+
+        let mut chunks = self.render_chunks(chunk_size);
+        while let Some(chunk) = chunks.next_chunk().map_err(std::io::Error::other)? {
+            writer.write_all(chunk.as_bytes()).await?;
+            writer.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::mj_body::MjBody;
+    use crate::model::mj_column::MjColumn;
+    use crate::model::mj_section::MjSection;
+    use crate::model::mj_text::MjText;
+    use crate::model::mjml::Mjml;
+
+    fn newsletter(sections: usize) -> Mjml {
+        let section = MjSection {
+            attributes: Default::default(),
+            content: vec![MjColumn {
+                content: vec![MjText {
+                    content: "Lorem ipsum dolor sit amet".to_string(),
+                    attributes: Default::default(),
+                }
+                .into()],
+                attributes: Default::default(),
+            }
+            .into()],
+        };
+
+        Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![section.into(); sections],
+                attributes: Default::default(),
+            },
+            mj_head: None,
+            attributes: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn tokio_writer() {
+        let mjml = newsletter(200);
+        let mut out: Vec<u8> = Vec::new();
+        mjml.render_tokio_chunked(&mut out, 1024).await.unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            mjml.render_to_string().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn tokio_writer_size_zero() {
+        let mjml = newsletter(3);
+        let mut out: Vec<u8> = Vec::new();
+        mjml.render_tokio_chunked(&mut out, 0).await.unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            mjml.render_to_string().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn futures_writer() {
+        let mjml = newsletter(3);
+        let mut out = futures_util::io::Cursor::new(Vec::new());
+        mjml.render_futures(&mut out).await.unwrap();
+        assert_eq!(
+            String::from_utf8(out.into_inner()).unwrap(),
+            mjml.render_to_string().unwrap()
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! Command-line tool to check, format and convert MJML templates.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use mjml_builder::model::element::Element;
+use mjml_builder::model::markup::MarkupError;
+use mjml_builder::model::mjml::Mjml;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+mod serve;
+
+const STDIO: &str = "-";
+
+#[derive(Parser)]
+#[command(name = "mjml-builder", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Rewrites `.mjml` files with one tag per line and the attributes sorted by name
+    Fmt {
+        /// Lists the files that would change instead of writing them
+        #[arg(long)]
+        check: bool,
+        /// Removes the comments
+        #[arg(long)]
+        strip_comments: bool,
+        /// Files, or directories searched for `.mjml` files, `-` to format the standard input to
+        /// the standard output
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Reports the templates that do not match the model
+    Validate {
+        /// Files, or directories searched for `.mjml` files
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Converts a template from one format to another
+    Convert {
+        /// Input file, `-` for the standard input
+        input: PathBuf,
+        /// Format of the input, guessed from its extension and content when missing
+        #[arg(long, value_enum)]
+        from: Option<Format>,
+        #[arg(long, value_enum)]
+        to: Format,
+        /// Output file, the standard output when missing
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Prints the plain-text version of a template
+    Text {
+        /// Input file, `-` for the standard input
+        input: PathBuf,
+    },
+    /// Previews the templates of a directory on localhost, reloading when they change
+    Serve {
+        dir: PathBuf,
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// `mjml` command used to show the compiled HTML
+        #[arg(long, default_value = "mjml")]
+        mjml: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// MJML markup
+    Mjml,
+    /// The JSON format of MJML, `{tagName, attributes, children, content}` nodes
+    MjmlJson,
+    /// The model serialized to JSON
+    Json,
+    /// The model serialized to YAML
+    Yaml,
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Fmt {
+            check,
+            strip_comments,
+            paths,
+        } => fmt(&paths, check, strip_comments),
+        Command::Validate { paths } => validate(&paths),
+        Command::Convert {
+            input,
+            from,
+            to,
+            output,
+        } => convert(&input, from, to, output.as_deref()),
+        Command::Text { input } => text(&input),
+        Command::Serve { dir, port, mjml } => serve::serve(&dir, port, &mjml),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fmt(paths: &[PathBuf], check: bool, strip_comments: bool) -> Result<(), String> {
+    let mut failed = 0;
+    for path in mjml_files(paths)? {
+        let result = read(&path).and_then(|source| {
+            let mut element = Element::from_markup(&source).map_err(|err| err.to_string())?;
+            Mjml::try_from(&element).map_err(|err| err.to_string())?;
+            if strip_comments {
+                remove_comments(&mut element);
+            }
+            let formatted = element.to_markup();
+            if check {
+                return match formatted == source {
+                    true => Ok(()),
+                    false => Err("not formatted".to_string()),
+                };
+            }
+            if path == Path::new(STDIO) {
+                return std::io::stdout()
+                    .write_all(formatted.as_bytes())
+                    .map_err(|err| err.to_string());
+            }
+            if formatted == source {
+                return Ok(());
+            }
+            std::fs::write(&path, formatted).map_err(|err| err.to_string())
+        });
+        if let Err(err) = result {
+            eprintln!("{}: {}", path.display(), err);
+            failed += 1;
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} file(s) failed", failed)),
+    }
+}
+
+fn remove_comments(element: &mut Element) {
+    element.comments.clear();
+    element.end_comments.clear();
+    for child in &mut element.children {
+        remove_comments(child);
+    }
+}
+
+fn validate(paths: &[PathBuf]) -> Result<(), String> {
+    let mut failed = 0;
+    for path in mjml_files(paths)? {
+        if let Err(err) = read(&path).and_then(|source| parse(&source, Format::Mjml)) {
+            eprintln!("{}: {}", path.display(), err);
+            failed += 1;
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} file(s) are invalid", failed)),
+    }
+}
+
+fn convert(
+    input: &Path,
+    from: Option<Format>,
+    to: Format,
+    output: Option<&Path>,
+) -> Result<(), String> {
+    let mjml = read(input)
+        .and_then(|source| {
+            parse(
+                &source,
+                from.unwrap_or_else(|| guess_format(input, &source)),
+            )
+        })
+        .map_err(|err| format!("{}: {}", input.display(), err))?;
+    let converted = match to {
+        Format::Mjml => mjml.to_string(),
+        Format::MjmlJson => to_json(&mjml.to_json())?,
+        Format::Json => to_json(&mjml)?,
+        Format::Yaml => serde_yaml::to_string(&mjml).map_err(|err| err.to_string())?,
+    };
+    match output {
+        Some(path) if path != Path::new(STDIO) => {
+            std::fs::write(path, converted).map_err(|err| format!("{}: {}", path.display(), err))
+        }
+        _ => std::io::stdout()
+            .write_all(converted.as_bytes())
+            .map_err(|err| err.to_string()),
+    }
+}
+
+fn text(input: &Path) -> Result<(), String> {
+    let mjml = read(input)
+        .and_then(|source| parse(&source, guess_format(input, &source)))
+        .map_err(|err| format!("{}: {}", input.display(), err))?;
+    print!("{}", mjml.to_plain_text());
+    Ok(())
+}
+
+fn parse(source: &str, format: Format) -> Result<Mjml, String> {
+    match format {
+        Format::Mjml => source.parse().map_err(|err: MarkupError| err.to_string()),
+        Format::MjmlJson => {
+            let value = serde_json::from_str(source).map_err(|err| err.to_string())?;
+            Mjml::from_json(&value).map_err(|err| err.to_string())
+        }
+        Format::Json => serde_json::from_str(source).map_err(|err| err.to_string()),
+        Format::Yaml => serde_yaml::from_str(source).map_err(|err| err.to_string()),
+    }
+}
+
+fn to_json(value: &impl serde::Serialize) -> Result<String, String> {
+    let mut json = serde_json::to_string_pretty(value).map_err(|err| err.to_string())?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Format from the extension of the file, then from its first characters.
+fn guess_format(path: &Path, source: &str) -> Format {
+    let is_mjml_json = || source.contains("\"tagName\"");
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("mjml") => Format::Mjml,
+        Some("yaml" | "yml") => Format::Yaml,
+        Some("json") if is_mjml_json() => Format::MjmlJson,
+        Some("json") => Format::Json,
+        _ => match source.trim_start().chars().next() {
+            Some('{') if is_mjml_json() => Format::MjmlJson,
+            Some('{') => Format::Json,
+            Some('<') | None => Format::Mjml,
+            _ => Format::Yaml,
+        },
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    let source = if path == Path::new(STDIO) {
+        let mut source = String::new();
+        std::io::stdin().read_to_string(&mut source).map(|_| source)
+    } else {
+        std::fs::read_to_string(path)
+    };
+    source.map_err(|err| err.to_string())
+}
+
+/// The given files, and the `.mjml` files found in the given directories.
+fn mjml_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            find_mjml_files(path, &mut files)
+                .map_err(|err| format!("{}: {}", path.display(), err))?;
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn find_mjml_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            find_mjml_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "mjml")
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
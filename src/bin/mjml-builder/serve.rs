@@ -0,0 +1,515 @@
+//! Local preview server of a directory of templates.
+//!
+//! Pages poll a stamp of the directory and reload when a template changes. The compiled HTML comes
+//! from the `mjml` command when it is installed, the crate itself does not compile MJML.
+
+use crate::find_mjml_files;
+use mjml_builder::model::markup::MarkupError;
+use mjml_builder::model::mjml::Mjml;
+use mjml_builder::template::CompiledTemplate;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+const RELOAD_INTERVAL_MS: u32 = 1000;
+
+struct Server {
+    root: PathBuf,
+    /// `mjml` command compiling to HTML, when available.
+    compiler: Option<PathBuf>,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn ok(content_type: &'static str, body: String) -> Self {
+        Self {
+            status: "200 OK",
+            content_type,
+            body,
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: message.to_string(),
+        }
+    }
+}
+
+pub fn serve(root: &Path, port: u16, compiler: &Path) -> Result<(), String> {
+    if !root.is_dir() {
+        return Err(format!("{}: not a directory", root.display()));
+    }
+    let available = Command::new(compiler)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !available {
+        eprintln!(
+            "{} is not available, compiled HTML is not shown",
+            compiler.display()
+        );
+    }
+    let server = Arc::new(Server {
+        root: root.to_path_buf(),
+        compiler: available.then(|| compiler.to_path_buf()),
+    });
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).map_err(|err| err.to_string())?;
+    eprintln!(
+        "serving {} on http://{}",
+        root.display(),
+        listener.local_addr().map_err(|err| err.to_string())?
+    );
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let server = Arc::clone(&server);
+        std::thread::spawn(move || {
+            if let Err(err) = server.handle(stream) {
+                eprintln!("{}", err);
+            }
+        });
+    }
+    Ok(())
+}
+
+impl Server {
+    fn handle(&self, stream: TcpStream) -> std::io::Result<()> {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // the headers are not needed
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 2 {
+            header.clear();
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let response = match (parts.next(), parts.next()) {
+            (Some("GET"), Some(target)) => self.route(target),
+            (Some(_), Some(_)) => Response::error("405 Method Not Allowed", "only GET is served"),
+            _ => Response::error("400 Bad Request", "malformed request"),
+        };
+
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+            response.status,
+            response.content_type,
+            response.body.len()
+        )?;
+        stream.write_all(response.body.as_bytes())?;
+        stream.flush()
+    }
+
+    fn route(&self, target: &str) -> Response {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let path = percent_decode(path);
+        let values = parse_query(query);
+        if path == "/" {
+            return self.index();
+        }
+        if path == "/_stamp" {
+            return Response::ok("text/plain", self.stamp().to_string());
+        }
+        let (page, template) = match path.split_once('/').map(|(_, rest)| rest.split_once('/')) {
+            Some(Some((page @ ("view" | "html"), template))) => (page, template),
+            _ => return Response::error("404 Not Found", "no such page"),
+        };
+        let Some(file) = self.template_path(template) else {
+            return Response::error("404 Not Found", "no such template");
+        };
+        let preview = match std::fs::read_to_string(&file) {
+            Ok(source) => Preview::new(source, &values),
+            Err(_) => return Response::error("404 Not Found", "no such template"),
+        };
+        match page {
+            "html" => match self.compile(&preview) {
+                Ok(html) => Response::ok("text/html; charset=utf-8", html),
+                Err(err) => Response::ok("text/html; charset=utf-8", message_page(&err)),
+            },
+            _ => Response::ok(
+                "text/html; charset=utf-8",
+                self.view(template, query, &preview),
+            ),
+        }
+    }
+
+    /// Path of a template below the root, `None` when it would leave it.
+    fn template_path(&self, template: &str) -> Option<PathBuf> {
+        let relative = Path::new(template);
+        let inside = relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        let is_mjml = relative
+            .extension()
+            .is_some_and(|extension| extension == "mjml");
+        (inside && is_mjml).then(|| self.root.join(relative))
+    }
+
+    fn templates(&self) -> Vec<String> {
+        let mut files = Vec::new();
+        // an unreadable directory shows as an empty list
+        let _ = find_mjml_files(&self.root, &mut files);
+        files
+            .iter()
+            .filter_map(|file| file.strip_prefix(&self.root).ok())
+            .map(|file| {
+                file.components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect()
+    }
+
+    /// Changes when a template is added, removed or modified.
+    fn stamp(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for template in self.templates() {
+            template.hash(&mut hasher);
+            if let Ok(metadata) = std::fs::metadata(self.root.join(&template)) {
+                metadata.len().hash(&mut hasher);
+                metadata.modified().ok().hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+
+    fn compile(&self, preview: &Preview) -> Result<String, String> {
+        let mjml = preview.mjml.as_ref().map_err(Clone::clone)?;
+        let compiler = self
+            .compiler
+            .as_deref()
+            .ok_or("Install the mjml command to see the compiled HTML.")?;
+        let mut child = Command::new(compiler)
+            .args(["-i", "-s"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| err.to_string())?;
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let source = mjml.to_string();
+        // written from another thread so that a big output cannot block the compiler
+        let writer = std::thread::spawn(move || stdin.write_all(source.as_bytes()));
+        let output = child.wait_with_output().map_err(|err| err.to_string())?;
+        writer
+            .join()
+            .expect("writing does not panic")
+            .map_err(|err| err.to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+        }
+        String::from_utf8(output.stdout).map_err(|err| err.to_string())
+    }
+
+    fn index(&self) -> Response {
+        let mut body = String::from("<h1>Templates</h1>\n<ul>\n");
+        for template in self.templates() {
+            body.push_str(&format!(
+                "<li><a href=\"/view/{}\">{}</a></li>\n",
+                escape(&percent_encode(&template)),
+                escape(&template)
+            ));
+        }
+        body.push_str("</ul>\n");
+        Response::ok("text/html; charset=utf-8", page("Templates", &body))
+    }
+
+    fn view(&self, template: &str, query: &str, preview: &Preview) -> String {
+        let mut body = format!(
+            "<p><a href=\"/\">Templates</a></p>\n<h1>{}</h1>\n",
+            escape(template)
+        );
+
+        if !preview.placeholders.is_empty() {
+            body.push_str("<form class=\"sample\">\n<h2>Sample data</h2>\n");
+            for (name, value) in &preview.placeholders {
+                body.push_str(&format!(
+                    "<label>{name}<input name=\"{name}\" value=\"{value}\"></label>\n",
+                    name = escape(name),
+                    value = escape(value.as_deref().unwrap_or_default())
+                ));
+            }
+            body.push_str("<button>Apply</button>\n</form>\n");
+        }
+
+        match &preview.mjml {
+            Ok(mjml) => {
+                let html_src = format!("/html/{}?{}", percent_encode(template), query);
+                body.push_str(&format!(
+                    "<h2>HTML</h2>\n<iframe src=\"{}\"></iframe>\n",
+                    escape(&html_src)
+                ));
+                body.push_str(&format!(
+                    "<h2>Text</h2>\n<pre>{}</pre>\n",
+                    escape(&mjml.to_plain_text())
+                ));
+                body.push_str(&format!(
+                    "<h2>MJML</h2>\n<pre>{}</pre>\n",
+                    escape(&mjml.to_string())
+                ));
+            }
+            Err(err) => {
+                body.push_str(&format!(
+                    "<h2>Error</h2>\n<pre class=\"error\">{}</pre>\n",
+                    escape(err)
+                ));
+                body.push_str(&format!(
+                    "<h2>MJML</h2>\n<pre>{}</pre>\n",
+                    escape(&preview.source)
+                ));
+            }
+        }
+        page(template, &body)
+    }
+}
+
+/// A template with its placeholders filled from the sample data.
+struct Preview {
+    /// Source with the sample data.
+    source: String,
+    /// Placeholders in order of appearance, with their sample value.
+    placeholders: Vec<(String, Option<String>)>,
+    mjml: Result<Mjml, String>,
+}
+
+impl Preview {
+    fn new(source: String, values: &BTreeMap<String, String>) -> Self {
+        let template = match CompiledTemplate::parse(source.clone()) {
+            Ok(template) => template,
+            Err(err) => {
+                return Self {
+                    source,
+                    placeholders: vec![],
+                    mjml: Err(err.to_string()),
+                }
+            }
+        };
+        let placeholders = template
+            .placeholders()
+            .iter()
+            .map(|name| {
+                (
+                    name.clone(),
+                    values.get(name).filter(|value| !value.is_empty()).cloned(),
+                )
+            })
+            .collect::<Vec<_>>();
+        // placeholders without sample data are kept as they are
+        let context = placeholders
+            .iter()
+            .map(|(name, value)| {
+                let value = value
+                    .clone()
+                    .unwrap_or_else(|| format!("{{{{ {} }}}}", name));
+                (name.clone(), value)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let source = match template.render_to_string(&context) {
+            Ok(filled) => filled,
+            Err(err) => {
+                return Self {
+                    source,
+                    placeholders,
+                    mjml: Err(err.to_string()),
+                }
+            }
+        };
+        let mjml = source.parse().map_err(|err: MarkupError| err.to_string());
+        Self {
+            source,
+            placeholders,
+            mjml,
+        }
+    }
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+iframe {{ width: 100%; height: 60vh; border: 1px solid #ccc; }}
+pre {{ background: #f6f6f6; padding: 1em; overflow: auto; }}
+.error {{ color: #b00020; }}
+.sample label {{ display: block; margin: 0.5em 0; }}
+.sample input {{ margin-left: 1em; }}
+</style>
+</head>
+<body>
+{body}<script>
+let stamp = null;
+setInterval(async () => {{
+  try {{
+    const current = await (await fetch("/_stamp")).text();
+    if (stamp !== null && current !== stamp) location.reload();
+    stamp = current;
+  }} catch (_) {{}}
+}}, {interval});
+</script>
+</body>
+</html>
+"#,
+        title = escape(title),
+        body = body,
+        interval = RELOAD_INTERVAL_MS
+    )
+}
+
+fn message_page(message: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><body><pre>{}</pre></body></html>\n",
+        escape(message)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn parse_query(query: &str) -> BTreeMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (
+                percent_decode(&name.replace('+', " ")),
+                percent_decode(&value.replace('+', " ")),
+            )
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_query, percent_decode, Server};
+    use std::path::{Path, PathBuf};
+
+    fn server() -> Server {
+        Server {
+            root: PathBuf::from("templates"),
+            compiler: None,
+        }
+    }
+
+    #[test]
+    fn template_path() {
+        let server = server();
+        assert_eq!(
+            server.template_path("emails/welcome.mjml"),
+            Some(Path::new("templates/emails/welcome.mjml").to_path_buf())
+        );
+        assert_eq!(server.template_path("../secret.mjml"), None);
+        assert_eq!(server.template_path("emails/../../secret.mjml"), None);
+        assert_eq!(server.template_path("./welcome.mjml"), None);
+        assert_eq!(server.template_path("/etc/secret.mjml"), None);
+        assert_eq!(server.template_path("emails/welcome.html"), None);
+        assert_eq!(server.template_path(""), None);
+        assert_eq!(
+            server.template_path(&percent_decode("%2e%2e/secret.mjml")),
+            None
+        );
+        assert_eq!(
+            server.template_path(&percent_decode("emails%2F%2E%2E%2F%2E%2E/secret.mjml")),
+            None
+        );
+    }
+
+    #[test]
+    fn route_outside_root() {
+        let server = server();
+        for target in [
+            "/view/../secret.mjml",
+            "/view/%2e%2e/secret.mjml",
+            "/html/%2E%2E%2Fsecret.mjml",
+            "/view//etc/secret.mjml",
+            "/view/%2Fetc/secret.mjml",
+        ] {
+            assert_eq!(server.route(target).status, "404 Not Found", "{}", target);
+        }
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(percent_decode("%2e%2e%2F"), "../");
+        assert_eq!(percent_decode("a%20b"), "a b");
+        assert_eq!(percent_decode("caf%C3%A9"), "café");
+        // invalid escapes are kept
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%2"), "%zz%2");
+        assert_eq!(percent_decode("%FF"), "\u{FFFD}");
+    }
+
+    #[test]
+    fn query() {
+        let values = parse_query("name=Ada+Lovelace&url=%2e%2e%2Fsecret&empty=&flag&&a%3Db=c%26d");
+        assert_eq!(values["name"], "Ada Lovelace");
+        assert_eq!(values["url"], "../secret");
+        assert_eq!(values["empty"], "");
+        assert_eq!(values["flag"], "");
+        assert_eq!(values["a=b"], "c&d");
+        assert_eq!(values.len(), 5);
+        assert!(parse_query("").is_empty());
+    }
+}
@@ -1,5 +1,9 @@
+#[cfg(feature = "async")]
+mod async_render;
+mod macros;
 #[forbid(unsafe_code)]
 pub mod model;
+pub mod template;
 
 // todo: mj-include
 
@@ -9,10 +13,15 @@ mod test {
         MjAccordion, MjAccordionElement, MjAccordionElementText, MjAccordionElementTitle,
     };
     use crate::model::mj_body::{MjBody, MjBodyAttributes};
-    use crate::model::mj_column::MjColumn;
+    use crate::model::mj_button::{MjButton, MjButtonAttributes};
+    use crate::model::mj_column::{MjColumn, MjColumnAttributes};
+    use crate::model::mj_head::{MjAttribute, MjAttributes, MjClass, MjHead};
+    use crate::model::mj_navbar::{MjNavbar, MjNavbarAttributes};
     use crate::model::mj_section::MjSection;
-    use crate::model::mjml::Mjml;
-    use crate::model::SizePx;
+    use crate::model::mj_text::MjText;
+    use crate::model::mjml::{Mjml, MjmlAttributes};
+    use crate::model::{BodyNode, ColumnNode, CustomComponent, SectionNode};
+    use crate::model::{Color, SizePx};
 
     #[test]
     fn basic_test() {
@@ -20,12 +29,12 @@ mod test {
             mj_raw_file_start: None,
             mj_body: MjBody {
                 content: vec![
-                    Box::new(MjSection{
+                    BodyNode::Section(MjSection{
                         attributes: Default::default(),
                         content: vec![
-                            Box::new(MjColumn{
+                            SectionNode::Column(MjColumn{
                                 content: vec![
-                                    Box::new(MjAccordion{
+                                    ColumnNode::Accordion(MjAccordion{
                                         elements: vec![
                                             MjAccordionElement{
                                                 title: MjAccordionElementTitle {
@@ -61,7 +70,8 @@ mod test {
                 attributes: MjBodyAttributes{
                     background_color: None,
                     width: SizePx::new(600),
-                    css_class: vec!["test_body".to_string()]
+                    css_class: vec!["test_body".to_string()],
+                    mj_class: vec![]
                 }
             },
             mj_head: None,
@@ -72,6 +82,462 @@ mod test {
 
         println!("{}", rendered);
 
-        assert_eq!(rendered, "");
+        assert_eq!(
+            rendered,
+            r#"<mjml>
+	<mj-body css-class="test_body" width="600px">
+			<mj-section>
+					<mj-column>
+							<mj-accordion>
+									<mj-accordion-element>
+										<mj-accordion-title>
+Why use an accordion?
+										</mj-accordion-title>
+										<mj-accordion-text>
+<span style="line-height:20px">Because emails with a lot of content are most of the time a very bad experience on mobile, mj-accordion comes handy when you want to deliver a lot of information in a concise way.</span>
+										</mj-accordion-text>
+									</mj-accordion-element>
+									<mj-accordion-element>
+										<mj-accordion-title>
+How it works
+										</mj-accordion-title>
+										<mj-accordion-text>
+<span style="line-height:20px">Content is stacked into tabs and users can expand them at will. If responsive styles are not supported (mostly on desktop clients), tabs are then expanded and your content is readable at once.</span>
+										</mj-accordion-text>
+									</mj-accordion-element>
+							</mj-accordion>
+					</mj-column>
+			</mj-section>
+	</mj-body>
+</mjml>
+"#
+        );
+    }
+
+    #[test]
+    fn model_is_shareable() {
+        fn assert_shareable<T: Send + Sync + Clone + std::fmt::Debug + PartialEq>() {}
+        assert_shareable::<Mjml>();
+
+        let mjml = Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![BodyNode::Section(MjSection {
+                    attributes: Default::default(),
+                    content: vec![SectionNode::Column(MjColumn {
+                        content: vec![ColumnNode::Text(MjText {
+                            content: "Hello".to_string(),
+                            attributes: Default::default(),
+                        })],
+                        attributes: Default::default(),
+                    })],
+                })],
+                attributes: Default::default(),
+            },
+            mj_head: None,
+            attributes: Default::default(),
+        };
+
+        let mut other = mjml.clone();
+        assert_eq!(mjml, other);
+
+        other.mj_body.content.push(BodyNode::Section(MjSection {
+            attributes: Default::default(),
+            content: vec![],
+        }));
+        assert_ne!(mjml, other);
+        assert!(format!("{:?}", other).contains("MjText"));
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Comment(String);
+
+    impl CustomComponent for Comment {
+        fn render(&self, writer: &mut dyn std::fmt::Write, depth: usize) -> std::fmt::Result {
+            writeln!(writer, "{}<!-- {} -->", "\t".repeat(depth), self.0)
+        }
+    }
+
+    #[test]
+    fn custom_components() {
+        let column = MjColumn {
+            content: vec![
+                ColumnNode::Custom(Box::new(Comment("first".to_string()))),
+                MjText {
+                    content: "Hello".to_string(),
+                    attributes: Default::default(),
+                }
+                .into(),
+            ],
+            attributes: Default::default(),
+        };
+        let mjml = Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![MjSection {
+                    attributes: Default::default(),
+                    content: vec![column.clone().into()],
+                }
+                .into()],
+                attributes: Default::default(),
+            },
+            mj_head: None,
+            attributes: Default::default(),
+        };
+
+        let texts = column
+            .content
+            .iter()
+            .filter(|node| matches!(node, ColumnNode::Text(_)))
+            .count();
+        assert_eq!(texts, 1);
+
+        let mut other = column.clone();
+        other.content[0] = ColumnNode::Custom(Box::new(Comment("second".to_string())));
+        assert_ne!(column, other);
+
+        let rendered = mjml.render_to_string().unwrap();
+        assert!(rendered.contains("\t\t\t\t\t\t<!-- first -->\n"));
+    }
+
+    #[test]
+    fn chunks_split_between_sections() {
+        let mjml = Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![
+                    MjSection {
+                        attributes: Default::default(),
+                        content: vec![MjColumn {
+                            content: vec![MjText {
+                                content: "Lorem ipsum dolor sit amet".to_string(),
+                                attributes: Default::default(),
+                            }
+                            .into()],
+                            attributes: Default::default(),
+                        }
+                        .into()],
+                    }
+                    .into();
+                    50
+                ],
+                attributes: Default::default(),
+            },
+            mj_head: None,
+            attributes: Default::default(),
+        };
+        let mut chunks = mjml.render_chunks(512);
+        let mut sizes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().unwrap() {
+            sizes.push(chunk.len());
+        }
+        assert!(sizes.len() > 1);
+        assert!(sizes[..sizes.len() - 1].iter().all(|size| *size >= 512));
+        assert_eq!(
+            sizes.iter().sum::<usize>(),
+            mjml.render_to_string().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn chunks_of_size_zero() {
+        let mjml = Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![
+                    MjSection {
+                        attributes: Default::default(),
+                        content: vec![],
+                    }
+                    .into();
+                    3
+                ],
+                attributes: Default::default(),
+            },
+            mj_head: None,
+            attributes: Default::default(),
+        };
+        let mut chunks = mjml.render_chunks(0);
+        let mut rendered = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().unwrap() {
+            rendered.push(chunk.to_string());
+        }
+        // the prologue, each section then the epilogue
+        assert_eq!(rendered.len(), 5);
+        assert_eq!(rendered.concat(), mjml.render_to_string().unwrap());
+    }
+
+    #[test]
+    fn display_components() {
+        let button = MjButton {
+            content: "Click".to_string(),
+            attributes: MjButtonAttributes {
+                href: Some("https://example.com".to_string()),
+                ..Default::default()
+            },
+        };
+        assert_eq!(
+            button.to_string(),
+            "<mj-button href=\"https://example.com\">\nClick\n</mj-button>\n"
+        );
+        assert_eq!(
+            ColumnNode::Button(button.clone()).to_string(),
+            button.to_string()
+        );
+
+        let mjml = Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![],
+                attributes: Default::default(),
+            },
+            mj_head: None,
+            attributes: Default::default(),
+        };
+        let mut bytes = Vec::new();
+        mjml.render(&mut bytes).unwrap();
+        assert_eq!(mjml.to_string().as_bytes(), bytes);
+        assert_eq!(
+            mjml.to_string(),
+            "<mjml>\n\t<mj-body width=\"600px\">\n\t</mj-body>\n</mjml>\n"
+        );
+    }
+
+    #[test]
+    fn derived_attributes() {
+        let navbar = MjNavbar {
+            links: vec![],
+            attributes: MjNavbarAttributes {
+                base_url: Some("https://example.com".to_string()),
+                ..Default::default()
+            },
+        };
+        let column = MjColumn {
+            content: vec![navbar.into()],
+            attributes: MjColumnAttributes {
+                background_color: Some(Color::WHITE),
+                ..Default::default()
+            },
+        };
+        let mjml = Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![MjSection {
+                    attributes: Default::default(),
+                    content: vec![column.into()],
+                }
+                .into()],
+                attributes: Default::default(),
+            },
+            mj_head: None,
+            attributes: MjmlAttributes {
+                lang: Some("en".to_string()),
+                ..Default::default()
+            },
+        };
+
+        let rendered = mjml.to_string();
+        assert!(rendered.starts_with("<mjml lang=\"en\">\n"));
+        assert!(rendered.contains("<mj-column background-color=\"#FFFFFF\">"));
+        assert!(rendered.contains("<mj-navbar base-url=\"https://example.com\">"));
+    }
+
+    #[test]
+    fn canonical_rendering() {
+        let document = |classes: &[(&str, &str)], text: &str| Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![MjSection {
+                    attributes: Default::default(),
+                    content: vec![MjColumn {
+                        content: vec![MjText {
+                            content: text.to_string(),
+                            attributes: Default::default(),
+                        }
+                        .into()],
+                        attributes: Default::default(),
+                    }
+                    .into()],
+                }
+                .into()],
+                attributes: Default::default(),
+            },
+            mj_head: Some(MjHead {
+                mj_attributes: Some(MjAttributes {
+                    content: vec![MjAttribute::MjClass(MjClass {
+                        name: "muted".to_string(),
+                        attributes: classes
+                            .iter()
+                            .map(|(key, value)| (key.to_string(), value.to_string()))
+                            .collect(),
+                    })],
+                }),
+                mj_breakpoint: None,
+                mj_font: vec![],
+                mj_html_attributes: vec![],
+                mj_preview: None,
+                mj_style: vec![],
+                mj_title: None,
+            }),
+            attributes: Default::default(),
+        };
+
+        let first = document(&[("color", "#999"), ("align", "left")], "Hello\n   world ");
+        let second = document(&[("align", "left"), ("color", "#999")], "Hello world");
+        assert_ne!(first.to_string(), second.to_string());
+        assert_eq!(first.render_canonical(), second.render_canonical());
+        assert!(first
+            .render_canonical()
+            .contains("\t\t<mj-class name=\"muted\" align=\"left\" color=\"#999\"/>\n"));
+    }
+
+    #[cfg(all(feature = "serde", feature = "json"))]
+    #[test]
+    fn serde_round_trip() {
+        let mjml: Mjml = r##"<mjml>
+  <mj-body>
+    <mj-raw><p>Raw</p></mj-raw>
+    <mj-section padding="10px 20px">
+      <mj-column>
+        <mj-text color="#333333" align="center">Hello</mj-text>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>"##
+            .parse()
+            .unwrap();
+
+        let value = serde_json::to_value(&mjml).unwrap();
+        let content = &value["mj_body"]["content"];
+        assert_eq!(content[0]["custom"], "<mj-raw>\n<p>Raw</p>\n</mj-raw>");
+        assert_eq!(
+            content[1]["section"]["attributes"]["padding"]["left"],
+            "20px"
+        );
+        let text = &content[1]["section"]["content"][0]["column"]["content"][0]["text"];
+        assert_eq!(text["attributes"]["color"], "#333333");
+        assert_eq!(text["attributes"]["align"], "center");
+
+        assert_eq!(serde_json::from_value::<Mjml>(value).unwrap(), mjml);
+    }
+
+    #[test]
+    fn mjml_macro() {
+        let items = ["First", "Second"];
+        let built = crate::mjml! {
+            mjml(lang = "en") {
+                mj-body(css-class = "test_body") {
+                    mj-section {
+                        mj-column(background-color = "#FFFFFF") {
+                            mj-accordion {
+                                mj-accordion-element {
+                                    mj-accordion-title { "Title" }
+                                    mj-accordion-text { "Text" }
+                                }
+                            }
+                            (..items.iter().map(|item| MjText {
+                                content: item.to_string(),
+                                attributes: Default::default(),
+                            }))
+                            mj-button(href = "https://example.com") { items.len() }
+                        }
+                    }
+                }
+            }
+        };
+
+        let expected = Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![MjSection {
+                    attributes: Default::default(),
+                    content: vec![MjColumn {
+                        content: vec![
+                            MjAccordion {
+                                elements: vec![MjAccordionElement {
+                                    title: MjAccordionElementTitle {
+                                        content: "Title".to_string(),
+                                        attributes: Default::default(),
+                                    },
+                                    text: MjAccordionElementText {
+                                        content: "Text".to_string(),
+                                        attributes: Default::default(),
+                                    },
+                                    attributes: Default::default(),
+                                }],
+                                attributes: Default::default(),
+                            }
+                            .into(),
+                            MjText {
+                                content: "First".to_string(),
+                                attributes: Default::default(),
+                            }
+                            .into(),
+                            MjText {
+                                content: "Second".to_string(),
+                                attributes: Default::default(),
+                            }
+                            .into(),
+                            MjButton {
+                                content: "2".to_string(),
+                                attributes: MjButtonAttributes {
+                                    href: Some("https://example.com".parse().unwrap()),
+                                    ..Default::default()
+                                },
+                            }
+                            .into(),
+                        ],
+                        attributes: MjColumnAttributes {
+                            background_color: Some(Color::WHITE),
+                            ..Default::default()
+                        },
+                    }
+                    .into()],
+                }
+                .into()],
+                attributes: MjBodyAttributes {
+                    css_class: vec!["test_body".to_string()],
+                    ..Default::default()
+                },
+            },
+            mj_head: None,
+            attributes: MjmlAttributes {
+                lang: Some("en".to_string()),
+                ..Default::default()
+            },
+        };
+        assert_eq!(built, expected);
+    }
+
+    #[test]
+    fn mjml_macro_raw() {
+        let built = crate::mjml! {
+            mj-body {
+                mj-raw { "<!-- body -->" }
+                mj-wrapper {
+                    mj-raw { "<!-- wrapper -->" }
+                    mj-section {
+                        mj-raw { "<!-- section -->" }
+                        mj-column {
+                            mj-raw { "<p>column</p>" }
+                        }
+                    }
+                }
+            }
+        };
+
+        let parsed: Mjml = r#"<mjml><mj-body>
+<mj-raw><!-- body --></mj-raw>
+<mj-wrapper>
+<mj-raw><!-- wrapper --></mj-raw>
+<mj-section>
+<mj-raw><!-- section --></mj-raw>
+<mj-column><mj-raw><p>column</p></mj-raw></mj-column>
+</mj-section>
+</mj-wrapper>
+</mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        assert_eq!(built.to_string(), parsed.to_string());
     }
 }
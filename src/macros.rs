@@ -0,0 +1,433 @@
+/// Builds the model from a markup-like syntax.
+///
+/// ```
+/// use mjml_builder::mjml;
+///
+/// let name = "World";
+/// let mjml = mjml! {
+///     mj-body {
+///         mj-section(padding = 20) {
+///             mj-column {
+///                 mj-text(color = "#333", font-size = 16) { format!("Hello {}", name) }
+///                 mj-image(src = "https://example.com/logo.png")
+///             }
+///         }
+///     }
+/// };
+/// assert!(mjml.to_string().contains("<mj-text color=\"#333333\" font-size=\"16px\">"));
+/// ```
+///
+/// A document starts with `mj-body`, or with `mjml(...)` to set its attributes, and gives a
+/// [`Mjml`](crate::model::mjml::Mjml) without head. Any other component gives its own struct.
+///
+/// Attribute values are Rust expressions, read from their displayed form as in markup. Unknown
+/// attributes, missing required ones and components that cannot be nested are compile errors,
+/// an invalid value panics. Content is any displayed expression. Among children, `(expr)` adds a
+/// node and `(..expr)` adds every node of an iterator.
+///
+/// ```compile_fail
+/// // `mj-text` has no attribute `colour`
+/// mjml_builder::mjml! { mj-text(colour = "red") { "Hello" } };
+/// ```
+///
+/// ```compile_fail
+/// // `mj-text` cannot be a child of `mj-section`
+/// mjml_builder::mjml! { mj-section { mj-text { "Hello" } } };
+/// ```
+///
+/// ```compile_fail
+/// // `mj-image` is missing a required attribute
+/// mjml_builder::mjml! { mj-column { mj-image(alt = "Logo") } };
+/// ```
+#[macro_export]
+macro_rules! mjml {
+    (mjml $(( $($attrs:tt)* ))? { mj-body $($body:tt)* }) => {
+        $crate::model::mjml::Mjml {
+            mj_raw_file_start: None,
+            mj_body: $crate::mjml!(@child [mj-body] $($body)*),
+            mj_head: None,
+            attributes: $crate::mjml!(
+                @attributes $crate::model::mjml::MjmlAttributes, "mjml",
+                <$crate::model::mjml::MjmlAttributes as Default>::default();
+                $($($attrs)*)?
+            ),
+        }
+    };
+    (mj-body $($body:tt)*) => {
+        $crate::model::mjml::Mjml {
+            mj_raw_file_start: None,
+            mj_body: $crate::mjml!(@child [mj-body] $($body)*),
+            mj_head: None,
+            attributes: Default::default(),
+        }
+    };
+
+    (@child [$($tag:tt)*] ( $($attrs:tt)* ) { $($body:tt)* }) => {
+        $crate::mjml!(@node [$($tag)*] ($($attrs)*) { $($body)* })
+    };
+    (@child [$($tag:tt)*] ( $($attrs:tt)* )) => {
+        $crate::mjml!(@node [$($tag)*] ($($attrs)*) {})
+    };
+    (@child [$($tag:tt)*] { $($body:tt)* }) => {
+        $crate::mjml!(@node [$($tag)*] () { $($body)* })
+    };
+    (@child [$($tag:tt)*]) => {
+        $crate::mjml!(@node [$($tag)*] () {})
+    };
+
+    (@children $vec:ident $kind:ident $parent:literal;) => {};
+    (@children $vec:ident $kind:ident $parent:literal; (.. $nodes:expr) $($rest:tt)*) => {
+        $vec.extend(
+            ::std::iter::IntoIterator::into_iter($nodes).map(::std::convert::Into::into),
+        );
+        $crate::mjml!(@children $vec $kind $parent; $($rest)*);
+    };
+    (@children $vec:ident $kind:ident $parent:literal; ($node:expr) $($rest:tt)*) => {
+        $vec.push(::std::convert::Into::into($node));
+        $crate::mjml!(@children $vec $kind $parent; $($rest)*);
+    };
+    (
+        @children $vec:ident $kind:ident $parent:literal;
+        $first:ident $(- $part:ident)* ( $($attrs:tt)* ) { $($body:tt)* } $($rest:tt)*
+    ) => {
+        $crate::mjml!(@expect $kind $parent; $first $(- $part)*);
+        $vec.push(::std::convert::Into::into(
+            $crate::mjml!(@node [$first $(- $part)*] ($($attrs)*) { $($body)* }),
+        ));
+        $crate::mjml!(@children $vec $kind $parent; $($rest)*);
+    };
+    (
+        @children $vec:ident $kind:ident $parent:literal;
+        $first:ident $(- $part:ident)* ( $($attrs:tt)* ) $($rest:tt)*
+    ) => {
+        $crate::mjml!(@expect $kind $parent; $first $(- $part)*);
+        $vec.push(::std::convert::Into::into(
+            $crate::mjml!(@node [$first $(- $part)*] ($($attrs)*) {}),
+        ));
+        $crate::mjml!(@children $vec $kind $parent; $($rest)*);
+    };
+    (
+        @children $vec:ident $kind:ident $parent:literal;
+        $first:ident $(- $part:ident)* { $($body:tt)* } $($rest:tt)*
+    ) => {
+        $crate::mjml!(@expect $kind $parent; $first $(- $part)*);
+        $vec.push(::std::convert::Into::into(
+            $crate::mjml!(@node [$first $(- $part)*] () { $($body)* }),
+        ));
+        $crate::mjml!(@children $vec $kind $parent; $($rest)*);
+    };
+    (@children $vec:ident $kind:ident $parent:literal; $first:ident $(- $part:ident)* $($rest:tt)*) => {
+        $crate::mjml!(@expect $kind $parent; $first $(- $part)*);
+        $vec.push(::std::convert::Into::into(
+            $crate::mjml!(@node [$first $(- $part)*] () {}),
+        ));
+        $crate::mjml!(@children $vec $kind $parent; $($rest)*);
+    };
+    (@children $vec:ident $kind:ident $parent:literal; $($rest:tt)*) => {
+        ::std::compile_error!(::std::concat!(
+            "expected a component in `", $parent, "`, found `", ::std::stringify!($($rest)*), "`"
+        ));
+    };
+
+    (@expect body $parent:literal; mj-section) => {};
+    (@expect body $parent:literal; mj-wrapper) => {};
+    (@expect body $parent:literal; mj-hero) => {};
+    (@expect body $parent:literal; mj-raw) => {};
+    (@expect wrapper $parent:literal; mj-section) => {};
+    (@expect wrapper $parent:literal; mj-hero) => {};
+    (@expect wrapper $parent:literal; mj-raw) => {};
+    (@expect section $parent:literal; mj-column) => {};
+    (@expect section $parent:literal; mj-group) => {};
+    (@expect section $parent:literal; mj-raw) => {};
+    (@expect group $parent:literal; mj-column) => {};
+    (@expect column $parent:literal; mj-accordion) => {};
+    (@expect column $parent:literal; mj-button) => {};
+    (@expect column $parent:literal; mj-carousel) => {};
+    (@expect column $parent:literal; mj-divider) => {};
+    (@expect column $parent:literal; mj-image) => {};
+    (@expect column $parent:literal; mj-navbar) => {};
+    (@expect column $parent:literal; mj-raw) => {};
+    (@expect column $parent:literal; mj-social) => {};
+    (@expect column $parent:literal; mj-spacer) => {};
+    (@expect column $parent:literal; mj-table) => {};
+    (@expect column $parent:literal; mj-text) => {};
+    (@expect accordion $parent:literal; mj-accordion-element) => {};
+    (@expect carousel $parent:literal; mj-carousel-image) => {};
+    (@expect navbar $parent:literal; mj-navbar-link) => {};
+    (@expect social $parent:literal; mj-social-element) => {};
+    (@expect $kind:ident $parent:literal; $first:ident $(- $part:ident)*) => {
+        ::std::compile_error!(::std::concat!(
+            "`", ::std::stringify!($first), $("-", ::std::stringify!($part),)*
+            "` cannot be a child of `", $parent, "`"
+        ));
+    };
+
+    (@node [mj-body] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_body, MjBody, MjBodyAttributes, "mj-body", content, body;
+            <$crate::model::mj_body::MjBodyAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-wrapper] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_wrapper, MjWrapper, MjWrapperAttributes, "mj-wrapper", content, wrapper;
+            <$crate::model::mj_wrapper::MjWrapperAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-section] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_section, MjSection, MjSectionAttributes, "mj-section", content, section;
+            <$crate::model::mj_section::MjSectionAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-hero] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_hero, MjHero, MjHeroAttributes, "mj-hero", content, column;
+            $crate::model::mj_hero::MjHeroAttributes::placeholder(); $attrs $body
+        )
+    };
+    (@node [mj-group] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_group, MjGroup, MjGroupAttributes, "mj-group", columns, group;
+            <$crate::model::mj_group::MjGroupAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-column] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_column, MjColumn, MjColumnAttributes, "mj-column", content, column;
+            <$crate::model::mj_column::MjColumnAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-accordion] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_accordion, MjAccordion, MjAccordionAttributes, "mj-accordion", elements, accordion;
+            <$crate::model::mj_accordion::MjAccordionAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-carousel] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_carousel, MjCarousel, MjCarouselAttributes, "mj-carousel", images, carousel;
+            <$crate::model::mj_carousel::MjCarouselAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-navbar] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_navbar, MjNavbar, MjNavbarAttributes, "mj-navbar", links, navbar;
+            <$crate::model::mj_navbar::MjNavbarAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-social] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @container mj_social, MjSocial, MjSocialAttributes, "mj-social", elements, social;
+            <$crate::model::mj_social::MjSocialAttributes as Default>::default(); $attrs $body
+        )
+    };
+    (@node [mj-accordion-element] $attrs:tt {
+        mj-accordion-title $(( $($title_attrs:tt)* ))? { $($title:tt)* }
+        mj-accordion-text $(( $($text_attrs:tt)* ))? { $($text:tt)* }
+    }) => {
+        $crate::model::mj_accordion::MjAccordionElement {
+            title: $crate::mjml!(@node [mj-accordion-title] ($($($title_attrs)*)?) { $($title)* }),
+            text: $crate::mjml!(@node [mj-accordion-text] ($($($text_attrs)*)?) { $($text)* }),
+            attributes: $crate::mjml!(
+                @attributes $crate::model::mj_accordion::MjAccordionElementAttributes,
+                "mj-accordion-element",
+                <$crate::model::mj_accordion::MjAccordionElementAttributes as Default>::default();
+                $attrs
+            ),
+        }
+    };
+    (@node [mj-accordion-element] $attrs:tt $body:tt) => {
+        ::std::compile_error!(
+            "`mj-accordion-element` holds an `mj-accordion-title` then an `mj-accordion-text`"
+        )
+    };
+    (@node [mj-accordion-title] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @leaf mj_accordion, MjAccordionElementTitle, MjAccordionElementTitleAttributes, "mj-accordion-title";
+            $attrs $body
+        )
+    };
+    (@node [mj-accordion-text] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @leaf mj_accordion, MjAccordionElementText, MjAccordionElementTextAttributes, "mj-accordion-text";
+            $attrs $body
+        )
+    };
+    (@node [mj-button] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @leaf mj_button, MjButton, MjButtonAttributes, "mj-button"; $attrs $body
+        )
+    };
+    (@node [mj-carousel-image] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @leaf mj_carousel, MjCarouselImage, MjCarouselImageAttributes, "mj-carousel-image";
+            $attrs $body
+        )
+    };
+    (@node [mj-navbar-link] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @leaf mj_navbar, MjNavbarLink, MjNavbarLinkAttributes, "mj-navbar-link"; $attrs $body
+        )
+    };
+    (@node [mj-social-element] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @leaf mj_social, MjSocialElement, MjSocialElementAttributes, "mj-social-element";
+            $attrs $body
+        )
+    };
+    (@node [mj-table] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @leaf mj_table, MjTable, MjTableAttributes, "mj-table"; $attrs $body
+        )
+    };
+    (@node [mj-text] $attrs:tt $body:tt) => {
+        $crate::mjml!(
+            @leaf mj_text, MjText, MjTextAttributes, "mj-text"; $attrs $body
+        )
+    };
+    (@node [mj-divider] $attrs:tt {}) => {
+        $crate::model::mj_divider::MjDivider {
+            attributes: $crate::mjml!(
+                @attributes $crate::model::mj_divider::MjDividerAttributes, "mj-divider",
+                <$crate::model::mj_divider::MjDividerAttributes as Default>::default(); $attrs
+            ),
+        }
+    };
+    (@node [mj-image] $attrs:tt {}) => {
+        $crate::model::mj_image::MjImage {
+            attributes: $crate::mjml!(
+                @attributes $crate::model::mj_image::MjImageAttributes, "mj-image",
+                <$crate::model::mj_image::MjImageAttributes as Default>::default(); $attrs
+            ),
+        }
+    };
+    (@node [mj-spacer] $attrs:tt {}) => {
+        $crate::model::mj_spacer::MjSpacer {
+            attributes: $crate::mjml!(
+                @attributes $crate::model::mj_spacer::MjSpacerAttributes, "mj-spacer",
+                <$crate::model::mj_spacer::MjSpacerAttributes as Default>::default(); $attrs
+            ),
+        }
+    };
+    (@node [mj-raw] () { $($content:tt)* }) => {
+        $crate::model::mj_raw::MjRaw {
+            content: $crate::mjml!(@content $($content)*),
+        }
+    };
+    (@node [mj-raw] $attrs:tt $body:tt) => {
+        ::std::compile_error!("`mj-raw` has no attributes")
+    };
+    (@node [$first:ident $(- $part:ident)*] $attrs:tt {}) => {
+        ::std::compile_error!(::std::concat!(
+            "unknown component `", ::std::stringify!($first), $("-", ::std::stringify!($part),)* "`"
+        ))
+    };
+    (@node [$first:ident $(- $part:ident)*] $attrs:tt $body:tt) => {
+        ::std::compile_error!(::std::concat!(
+            "`", ::std::stringify!($first), $("-", ::std::stringify!($part),)*
+            "` is unknown or has no content"
+        ))
+    };
+
+    (
+        @container $module:ident, $component:ident, $attributes:ident, $tag:literal,
+        $field:ident, $kind:ident; $default:expr; $attrs:tt { $($body:tt)* }
+    ) => {{
+        #[allow(unused_mut)]
+        let mut children = ::std::vec::Vec::new();
+        $crate::mjml!(@children children $kind $tag; $($body)*);
+        $crate::model::$module::$component {
+            attributes: $crate::mjml!(
+                @attributes $crate::model::$module::$attributes, $tag, $default; $attrs
+            ),
+            $field: children,
+        }
+    }};
+    (
+        @leaf $module:ident, $component:ident, $attributes:ident, $tag:literal;
+        $attrs:tt { $($content:tt)* }
+    ) => {
+        $crate::model::$module::$component {
+            attributes: $crate::mjml!(
+                @attributes $crate::model::$module::$attributes, $tag,
+                <$crate::model::$module::$attributes as Default>::default(); $attrs
+            ),
+            content: $crate::mjml!(@content $($content)*),
+        }
+    };
+
+    (@content) => {
+        ::std::string::String::new()
+    };
+    (@content $content:expr) => {
+        ::std::string::ToString::to_string(&$content)
+    };
+
+    (@attributes $attributes:path, $tag:literal, $default:expr; ($($attrs:tt)*)) => {
+        $crate::mjml!(@attributes $attributes, $tag, $default; $($attrs)*)
+    };
+    (@attributes $attributes:path, $tag:literal, $default:expr; $($attrs:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut attributes = $default;
+        $crate::mjml!(@set attributes, $attributes, $tag; $($attrs)*);
+        const _: () = ::std::assert!(
+            $crate::model::macro_support::has_required(
+                <$attributes as $crate::model::schema::AttributesSchema>::ATTRIBUTES,
+                $crate::mjml!(@names []; $($attrs)*),
+            ),
+            ::std::concat!("`", $tag, "` is missing a required attribute"),
+        );
+        attributes
+    }};
+
+    (@set $target:ident, $attributes:path, $tag:literal;) => {};
+    (
+        @set $target:ident, $attributes:path, $tag:literal;
+        $first:ident $(- $part:ident)* = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        const _: () = ::std::assert!(
+            $crate::model::macro_support::has_attribute(
+                <$attributes as $crate::model::schema::AttributesSchema>::ATTRIBUTES,
+                ::std::concat!(::std::stringify!($first), $("-", ::std::stringify!($part),)*),
+            ),
+            ::std::concat!(
+                "`", $tag, "` has no attribute `",
+                ::std::stringify!($first), $("-", ::std::stringify!($part),)* "`"
+            ),
+        );
+        $crate::model::macro_support::SetAttribute::set_attribute(
+            &mut $target,
+            $tag,
+            ::std::concat!(::std::stringify!($first), $("-", ::std::stringify!($part),)*),
+            &$value,
+        );
+        $crate::mjml!(@set $target, $attributes, $tag; $($($rest)*)?);
+    };
+
+    (@names [$($name:expr),*];) => {
+        &[$($name),*]
+    };
+    (@names [$($name:expr),*]; $first:ident $(- $part:ident)* = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::mjml!(
+            @names [
+                $($name,)*
+                ::std::concat!(::std::stringify!($first), $("-", ::std::stringify!($part),)*)
+            ];
+            $($($rest)*)?
+        )
+    };
+
+    ($first:ident $(- $part:ident)* ( $($attrs:tt)* ) { $($body:tt)* }) => {
+        $crate::mjml!(@node [$first $(- $part)*] ($($attrs)*) { $($body)* })
+    };
+    ($first:ident $(- $part:ident)* ( $($attrs:tt)* )) => {
+        $crate::mjml!(@node [$first $(- $part)*] ($($attrs)*) {})
+    };
+    ($first:ident $(- $part:ident)* { $($body:tt)* }) => {
+        $crate::mjml!(@node [$first $(- $part)*] () { $($body)* })
+    };
+    ($first:ident $(- $part:ident)*) => {
+        $crate::mjml!(@node [$first $(- $part)*] () {})
+    };
+}
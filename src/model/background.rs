@@ -0,0 +1,521 @@
+//! Backgrounds of sections, wrappers and heroes.
+
+use crate::model::schema::{self, attribute, AttributeSchema, AttributesSchema, ValueKind};
+use crate::model::{
+    impl_attribute_value, impl_from_str, impl_serde_str, AttributeValue, AttributeWriter, Color,
+    MjBaseComponentAttributes, ParseValueError, PxOrPercent, Url,
+};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The `background-*` attributes, inlined with `#[mj(flatten)]`.
+///
+/// `background-position-x` and `background-position-y` are read into [`Background::position`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Background {
+    /// Shown behind the image, and instead of it by the clients that do not load it.
+    pub color: Option<Color>,
+    pub position: Option<BackgroundPosition>,
+    pub repeat: Option<BackgroundRepeat>,
+    pub size: Option<BackgroundSize>,
+    pub url: Option<Url>,
+}
+
+impl MjBaseComponentAttributes for Background {
+    fn render(&self, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        self.color.render_value("background-color", writer)?;
+        self.position.render_value("background-position", writer)?;
+        self.repeat.render_value("background-repeat", writer)?;
+        self.size.render_value("background-size", writer)?;
+        self.url.render_value("background-url", writer)
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<bool, ParseValueError> {
+        match name {
+            "background-color" => self.color = Some(value.parse()?),
+            "background-repeat" => self.repeat = Some(value.parse()?),
+            "background-size" => self.size = Some(value.parse()?),
+            "background-url" => self.url = Some(value.to_string()),
+            _ => {
+                return set_position(
+                    &mut self.position,
+                    BackgroundPosition::default(),
+                    name,
+                    value,
+                )
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// The `background-*` attributes of `mj-hero`, which has no `background-repeat` nor
+/// `background-size`, inlined with `#[mj(flatten)]`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct HeroBackground {
+    pub color: Option<Color>,
+    pub position: Option<BackgroundPosition>,
+    pub url: Option<Url>,
+}
+
+impl MjBaseComponentAttributes for HeroBackground {
+    fn render(&self, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        self.color.render_value("background-color", writer)?;
+        self.position.render_value("background-position", writer)?;
+        self.url.render_value("background-url", writer)
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<bool, ParseValueError> {
+        match name {
+            "background-color" => self.color = Some(value.parse()?),
+            "background-url" => self.url = Some(value.to_string()),
+            _ => return set_position(&mut self.position, BackgroundPosition::CENTER, name, value),
+        }
+        Ok(true)
+    }
+}
+
+/// Reads `background-position` and its `-x` and `-y` parts, the missing part of the position
+/// comes from `default`.
+fn set_position(
+    position: &mut Option<BackgroundPosition>,
+    default: BackgroundPosition,
+    name: &str,
+    value: &str,
+) -> Result<bool, ParseValueError> {
+    let current = position.unwrap_or(default);
+    *position = Some(match name {
+        "background-position" => value.parse()?,
+        "background-position-x" => BackgroundPosition {
+            x: value.parse()?,
+            ..current
+        },
+        "background-position-y" => BackgroundPosition {
+            y: value.parse()?,
+            ..current
+        },
+        _ => return Ok(false),
+    });
+    Ok(true)
+}
+
+impl AttributesSchema for Background {
+    const ATTRIBUTES: &'static [AttributeSchema] = &[
+        attribute("background-color", ValueKind::Color, None),
+        attribute("background-position", ValueKind::String, None),
+        attribute("background-position-x", ValueKind::String, None),
+        attribute("background-position-y", ValueKind::String, None),
+        attribute(
+            "background-repeat",
+            schema::BACKGROUND_REPEAT,
+            Some("repeat"),
+        ),
+        attribute("background-size", ValueKind::String, Some("auto")),
+        attribute("background-url", ValueKind::Url, None),
+    ];
+}
+
+impl AttributesSchema for HeroBackground {
+    const ATTRIBUTES: &'static [AttributeSchema] = &[
+        attribute("background-color", ValueKind::Color, None),
+        attribute(
+            "background-position",
+            ValueKind::String,
+            Some("center center"),
+        ),
+        attribute("background-position-x", ValueKind::String, None),
+        attribute("background-position-y", ValueKind::String, None),
+        attribute("background-url", ValueKind::Url, None),
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundRepeat {
+    Repeat,
+    NoRepeat,
+}
+impl Display for BackgroundRepeat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Repeat => write!(f, "repeat"),
+            Self::NoRepeat => write!(f, "no-repeat"),
+        }
+    }
+}
+impl_from_str!(
+    BackgroundRepeat,
+    "background repeat",
+    "repeat" => Repeat,
+    "no-repeat" => NoRepeat,
+);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackgroundSize {
+    Auto,
+    Cover,
+    Contain,
+    /// Width then height of the image, the height keeps the ratio when missing.
+    Length(PxOrPercent, Option<PxOrPercent>),
+}
+impl Display for BackgroundSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Cover => write!(f, "cover"),
+            Self::Contain => write!(f, "contain"),
+            Self::Length(width, None) => write!(f, "{}", width),
+            Self::Length(width, Some(height)) => write!(f, "{} {}", width, height),
+        }
+    }
+}
+impl FromStr for BackgroundSize {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "cover" => Ok(Self::Cover),
+            "contain" => Ok(Self::Contain),
+            _ => {
+                let invalid = || ParseValueError::new("background size", s);
+                let mut values = s.split_whitespace();
+                let width = values.next().ok_or_else(invalid)?;
+                let width = width.parse().map_err(|_| invalid())?;
+                let height = match values.next() {
+                    None | Some("auto") => None,
+                    Some(height) => Some(height.parse().map_err(|_| invalid())?),
+                };
+                if values.next().is_some() {
+                    return Err(invalid());
+                }
+                Ok(Self::Length(width, height))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionX {
+    Left,
+    Center,
+    Right,
+    /// From the left edge.
+    Offset(PxOrPercent),
+}
+impl Display for PositionX {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left => write!(f, "left"),
+            Self::Center => write!(f, "center"),
+            Self::Right => write!(f, "right"),
+            Self::Offset(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+impl FromStr for PositionX {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Self::Left),
+            "center" => Ok(Self::Center),
+            "right" => Ok(Self::Right),
+            _ => s
+                .parse()
+                .map(Self::Offset)
+                .map_err(|_| ParseValueError::new("background position", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionY {
+    Top,
+    Center,
+    Bottom,
+    /// From the top edge.
+    Offset(PxOrPercent),
+}
+impl Display for PositionY {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Top => write!(f, "top"),
+            Self::Center => write!(f, "center"),
+            Self::Bottom => write!(f, "bottom"),
+            Self::Offset(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+impl FromStr for PositionY {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top" => Ok(Self::Top),
+            "center" => Ok(Self::Center),
+            "bottom" => Ok(Self::Bottom),
+            _ => s
+                .parse()
+                .map(Self::Offset)
+                .map_err(|_| ParseValueError::new("background position", s)),
+        }
+    }
+}
+
+/// Written `x y`, such as `center top` or `20% 10px`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundPosition {
+    pub x: PositionX,
+    pub y: PositionY,
+}
+impl BackgroundPosition {
+    /// `center center`, the default of heroes.
+    pub const CENTER: Self = Self {
+        x: PositionX::Center,
+        y: PositionY::Center,
+    };
+}
+impl Default for BackgroundPosition {
+    /// `top center`, the default of sections and wrappers.
+    fn default() -> Self {
+        Self {
+            x: PositionX::Center,
+            y: PositionY::Top,
+        }
+    }
+}
+impl Display for BackgroundPosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.x, self.y)
+    }
+}
+impl FromStr for BackgroundPosition {
+    type Err = ParseValueError;
+
+    /// Reads one or two values, keywords can come in any order as MJML does: `top`,
+    /// `top center` and `center top` are the same position.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseValueError::new("background position", s);
+        let is_vertical = |value: &str| matches!(value, "top" | "bottom");
+        let (x, y) = match s.split_whitespace().collect::<Vec<_>>()[..] {
+            [y] if is_vertical(y) => ("center", y),
+            [x] => (x, "center"),
+            [first, second]
+                if is_vertical(first)
+                    || (first == "center" && matches!(second, "left" | "right")) =>
+            {
+                (second, first)
+            }
+            [x, y] => (x, y),
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            x: x.parse().map_err(|_| invalid())?,
+            y: y.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl_serde_str!(BackgroundSize, PositionX, PositionY, BackgroundPosition);
+impl_attribute_value!(
+    BackgroundRepeat => schema::BACKGROUND_REPEAT,
+    BackgroundSize => ValueKind::String,
+    BackgroundPosition => ValueKind::String,
+);
+
+#[cfg(test)]
+mod test {
+    use crate::model::background::{
+        Background, BackgroundPosition, BackgroundRepeat, BackgroundSize, HeroBackground,
+        PositionX, PositionY,
+    };
+    use crate::model::mj_hero::{MjHero, MjHeroAttributes};
+    use crate::model::mj_section::{MjSection, MjSectionAttributes};
+    use crate::model::mjml::Mjml;
+    use crate::model::{schema, BodyNode, Color, PxOrPercent};
+
+    #[test]
+    fn background_position() {
+        let position = |x, y| BackgroundPosition { x, y };
+        for (value, expected, written) in [
+            (
+                "top center",
+                position(PositionX::Center, PositionY::Top),
+                "center top",
+            ),
+            (
+                "center top",
+                position(PositionX::Center, PositionY::Top),
+                "center top",
+            ),
+            (
+                "center left",
+                position(PositionX::Left, PositionY::Center),
+                "left center",
+            ),
+            (
+                "bottom",
+                position(PositionX::Center, PositionY::Bottom),
+                "center bottom",
+            ),
+            (
+                "right",
+                position(PositionX::Right, PositionY::Center),
+                "right center",
+            ),
+            (
+                "20% 10px",
+                position(
+                    PositionX::Offset(PxOrPercent::percent(20.0)),
+                    PositionY::Offset(PxOrPercent::px(10.0)),
+                ),
+                "20% 10px",
+            ),
+        ] {
+            let parsed = value.parse::<BackgroundPosition>().unwrap();
+            assert_eq!(parsed, expected, "{}", value);
+            assert_eq!(parsed.to_string(), written);
+        }
+        for value in ["", "top left center", "top top", "1em top", "middle"] {
+            assert!(value.parse::<BackgroundPosition>().is_err(), "{}", value);
+        }
+
+        assert_eq!("cover".parse(), Ok(BackgroundSize::Cover));
+        for (value, expected, written) in [
+            (
+                "50%",
+                BackgroundSize::Length(PxOrPercent::percent(50.0), None),
+                "50%",
+            ),
+            (
+                "100px 50px",
+                BackgroundSize::Length(PxOrPercent::px(100.0), Some(PxOrPercent::px(50.0))),
+                "100px 50px",
+            ),
+            (
+                "100%  25%",
+                BackgroundSize::Length(
+                    PxOrPercent::percent(100.0),
+                    Some(PxOrPercent::percent(25.0)),
+                ),
+                "100% 25%",
+            ),
+            (
+                "100px auto",
+                BackgroundSize::Length(PxOrPercent::px(100.0), None),
+                "100px",
+            ),
+        ] {
+            let parsed = value.parse::<BackgroundSize>().unwrap();
+            assert_eq!(parsed, expected, "{}", value);
+            assert_eq!(parsed.to_string(), written);
+            assert_eq!(written.parse::<BackgroundSize>().unwrap(), parsed);
+        }
+        for value in ["fill", "", "10px 10px 10px", "cover 10px", "10px cover"] {
+            assert!(value.parse::<BackgroundSize>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn render_background() {
+        let mjml: Mjml = r##"<mjml><mj-body>
+<mj-section background-url="https://example.com/a.png" background-color="#f0f0f0" background-position-x="right" background-position="bottom" background-repeat="no-repeat" background-size="cover"></mj-section>
+</mj-body></mjml>"##
+            .parse()
+            .unwrap();
+        let BodyNode::Section(section) = &mjml.mj_body.content[0] else {
+            panic!("expected a section");
+        };
+        assert_eq!(
+            section.attributes.background,
+            Some(Background {
+                color: Some(Color::rgb(0xF0, 0xF0, 0xF0)),
+                position: Some(BackgroundPosition {
+                    x: PositionX::Right,
+                    y: PositionY::Bottom,
+                }),
+                repeat: Some(BackgroundRepeat::NoRepeat),
+                size: Some(BackgroundSize::Cover),
+                url: Some("https://example.com/a.png".to_string()),
+            })
+        );
+        assert!(section.to_string().starts_with(
+            "<mj-section background-color=\"#F0F0F0\" background-position=\"right bottom\" background-repeat=\"no-repeat\" background-size=\"cover\" background-url=\"https://example.com/a.png\">"
+        ));
+
+        let source = mjml.to_rust("template");
+        assert!(source.contains("use mjml_builder::model::background::{"));
+        assert!(source.contains("x: PositionX::Right,"));
+        assert!(source.contains("size: Some(BackgroundSize::Cover),"));
+
+        let empty = MjSection {
+            attributes: MjSectionAttributes::default(),
+            content: vec![],
+        };
+        assert!(empty.to_string().starts_with("<mj-section>"));
+
+        let hero = MjHero {
+            content: vec![],
+            attributes: MjHeroAttributes {
+                background: Some(HeroBackground {
+                    color: Some(Color::BLACK),
+                    position: Some(BackgroundPosition::CENTER),
+                    ..Default::default()
+                }),
+                ..MjHeroAttributes::placeholder()
+            },
+        };
+        assert!(hero.to_string().starts_with(
+            "<mj-hero background-color=\"#000000\" background-position=\"center center\""
+        ));
+    }
+
+    #[test]
+    fn hero_background() {
+        let hero = |attributes: &str| {
+            format!(
+                r#"<mjml><mj-body><mj-hero background-height="100px" background-width="600px" {}></mj-hero></mj-body></mjml>"#,
+                attributes
+            )
+            .parse::<Mjml>()
+        };
+        assert!(hero(r#"background-repeat="no-repeat""#).is_err());
+        assert!(hero(r#"background-size="cover""#).is_err());
+
+        // the missing part of the position is centered
+        let mjml = hero(r#"background-position-y="bottom""#).unwrap();
+        let BodyNode::Hero(hero) = &mjml.mj_body.content[0] else {
+            panic!("expected a hero");
+        };
+        assert_eq!(
+            hero.attributes.background,
+            Some(HeroBackground {
+                position: Some(BackgroundPosition {
+                    x: PositionX::Center,
+                    y: PositionY::Bottom,
+                }),
+                ..Default::default()
+            })
+        );
+        let source = mjml.to_rust("template");
+        assert!(source.contains("background: Some(HeroBackground {"));
+
+        let schema = schema::component("mj-hero").unwrap();
+        let attribute = |name| schema.attributes.iter().find(|a| a.name == name);
+        assert!(attribute("background-repeat").is_none());
+        assert!(attribute("background-size").is_none());
+        assert_eq!(
+            attribute("background-position").unwrap().default,
+            Some("center center")
+        );
+        let section = schema::component("mj-section").unwrap();
+        assert!(section
+            .attributes
+            .iter()
+            .any(|a| a.name == "background-size"));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::model::mj_column::MjColumn;
+use crate::model::mjml::Mjml;
+use crate::model::{BodyNode, ColumnNode, Html, SectionNode};
+
+impl Mjml {
+    /// Normalises the document so that equivalent models always render to the same bytes.
+    ///
+    /// Runs of whitespace in text content are collapsed into a single space, except inside
+    /// `<pre>` elements. Raw markup, tables and custom components are left untouched.
+    pub fn canonicalize(&mut self) {
+        if let Some(head) = &mut self.mj_head {
+            if let Some(preview) = &mut head.mj_preview {
+                normalize(preview);
+            }
+            if let Some(title) = &mut head.mj_title {
+                normalize(title);
+            }
+        }
+        self.mj_body.content.iter_mut().for_each(canonicalize_body);
+    }
+
+    pub fn render_canonical(&self) -> String {
+        let mut canonical = self.clone();
+        canonical.canonicalize();
+        canonical.to_string()
+    }
+}
+
+fn canonicalize_body(node: &mut BodyNode) {
+    match node {
+        BodyNode::Section(section) => section.content.iter_mut().for_each(canonicalize_section),
+        BodyNode::Wrapper(wrapper) => wrapper.content.iter_mut().for_each(canonicalize_body),
+        BodyNode::Hero(hero) => hero.content.iter_mut().for_each(canonicalize_column),
+        BodyNode::Custom(_) => {}
+    }
+}
+
+fn canonicalize_section(node: &mut SectionNode) {
+    match node {
+        SectionNode::Column(column) => canonicalize_column_content(column),
+        SectionNode::Group(group) => group
+            .columns
+            .iter_mut()
+            .for_each(canonicalize_column_content),
+        SectionNode::Custom(_) => {}
+    }
+}
+
+fn canonicalize_column_content(column: &mut MjColumn) {
+    column.content.iter_mut().for_each(canonicalize_column);
+}
+
+fn canonicalize_column(node: &mut ColumnNode) {
+    match node {
+        ColumnNode::Accordion(accordion) => {
+            for element in &mut accordion.elements {
+                normalize(&mut element.title.content);
+                normalize(&mut element.text.content);
+            }
+        }
+        ColumnNode::Button(button) => normalize(&mut button.content),
+        ColumnNode::Carousel(carousel) => {
+            for image in &mut carousel.images {
+                normalize(&mut image.content);
+            }
+        }
+        ColumnNode::Navbar(navbar) => {
+            for link in &mut navbar.links {
+                normalize(&mut link.content);
+            }
+        }
+        ColumnNode::Social(social) => {
+            for element in &mut social.elements {
+                normalize(&mut element.content);
+            }
+        }
+        ColumnNode::Text(text) => normalize(&mut text.content),
+        // raw markup and tables may hold whitespace sensitive content
+        ColumnNode::Divider(_)
+        | ColumnNode::Image(_)
+        | ColumnNode::Raw(_)
+        | ColumnNode::Spacer(_)
+        | ColumnNode::Table(_)
+        | ColumnNode::Custom(_) => {}
+    }
+}
+
+/// Collapses runs of whitespace into a single space and trims the content, `<pre>` elements are
+/// kept as they are.
+fn normalize(content: &mut Html) {
+    let mut normalized = String::with_capacity(content.len());
+    let mut space = false;
+    let mut rest = content.as_str();
+    while !rest.is_empty() {
+        let (text, pre) = match find_pre(rest) {
+            Some((start, end)) => (&rest[..start], &rest[start..end]),
+            None => (rest, ""),
+        };
+        // the `<` opening the element goes through the loop to get the pending space
+        for c in text.chars().chain(pre.chars().take(1)) {
+            if c.is_whitespace() {
+                space = true;
+                continue;
+            }
+            if space && !normalized.is_empty() {
+                normalized.push(' ');
+            }
+            space = false;
+            normalized.push(c);
+        }
+        normalized.push_str(pre.get(1..).unwrap_or_default());
+        rest = &rest[text.len() + pre.len()..];
+    }
+    if *content != normalized {
+        *content = normalized;
+    }
+}
+
+/// Byte range of the first `<pre>` element, up to the end of the content when it is not closed.
+fn find_pre(content: &str) -> Option<(usize, usize)> {
+    // ascii lowercasing keeps the byte offsets
+    let lowercase = content.to_ascii_lowercase();
+    let start = lowercase
+        .match_indices("<pre")
+        .map(|(index, _)| index)
+        .find(|index| {
+            matches!(
+                lowercase.as_bytes().get(index + 4),
+                Some(b'>' | b' ' | b'\t' | b'\n' | b'\r' | b'/')
+            )
+        })?;
+    let end = lowercase[start..]
+        .find("</pre>")
+        .map_or(content.len(), |end| start + end + "</pre>".len());
+    Some((start, end))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::canonical::normalize;
+    use crate::model::mjml::Mjml;
+
+    #[test]
+    fn normalize_content() {
+        for (content, expected) in [
+            ("  Hello\n   world ", "Hello world"),
+            ("a <pre>x\n  y</pre>\n b", "a <pre>x\n  y</pre> b"),
+            (
+                "<PRE class=\"code\">\n  x  </PRE>",
+                "<PRE class=\"code\">\n  x  </PRE>",
+            ),
+            ("<prefix>  a</prefix>", "<prefix> a</prefix>"),
+            ("a<pre> unclosed  ", "a<pre> unclosed  "),
+        ] {
+            let mut normalized = content.to_string();
+            normalize(&mut normalized);
+            assert_eq!(normalized, expected);
+        }
+    }
+
+    #[test]
+    fn raw_content_is_kept() {
+        let source = "<mjml><mj-body><mj-section><mj-column>
+<mj-raw><pre>a\n  b</pre>  c</mj-raw>
+<mj-table><tr><td>  x  </td></tr></mj-table>
+<mj-text>  y\n  z</mj-text>
+</mj-column></mj-section></mj-body></mjml>";
+        let mut mjml: Mjml = source.parse().unwrap();
+        let original = mjml.clone();
+        mjml.canonicalize();
+        let rendered = mjml.to_string();
+        assert!(rendered.contains("<pre>a\n  b</pre>  c"));
+        assert!(rendered.contains("<td>  x  </td>"));
+        assert!(rendered.contains("\ny z\n"));
+        assert_ne!(mjml, original);
+    }
+}
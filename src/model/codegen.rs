@@ -0,0 +1,977 @@
+//! Rust source building a document, to move existing MJML templates into code.
+
+use crate::model::background::{
+    Background, BackgroundPosition, BackgroundRepeat, BackgroundSize, HeroBackground, PositionX,
+    PositionY,
+};
+use crate::model::length::{Unit, Units};
+use crate::model::mj_accordion::{
+    IconPosition, MjAccordion, MjAccordionElement, MjAccordionElementText, MjAccordionElementTitle,
+};
+use crate::model::mj_accordion::{
+    MjAccordionAttributes, MjAccordionElementAttributes, MjAccordionElementTextAttributes,
+    MjAccordionElementTitleAttributes,
+};
+use crate::model::mj_body::{MjBody, MjBodyAttributes};
+use crate::model::mj_button::{MjButton, MjButtonAttributes, MjButtonMobile};
+use crate::model::mj_carousel::{
+    MjCarousel, MjCarouselAttributes, MjCarouselImage, MjCarouselImageAttributes, Visibility,
+};
+use crate::model::mj_column::{MjColumn, MjColumnAttributes, MjColumnPaddingAttributes};
+use crate::model::mj_divider::{MjDivider, MjDividerAttributes};
+use crate::model::mj_group::{MjGroup, MjGroupAttributes};
+use crate::model::mj_head::{
+    MjAttribute, MjAttributes, MjClass, MjFont, MjHead, MjHtmlAttribute, MjSelector, MjStyle,
+    PartialAttributes,
+};
+use crate::model::mj_hero::{HeroMode, MjHero, MjHeroAttributes};
+use crate::model::mj_image::{MjImage, MjImageAttributes};
+use crate::model::mj_navbar::{
+    MjNavbar, MjNavbarAttributes, MjNavbarHamburgerAttributes, MjNavbarLink, MjNavbarLinkAttributes,
+};
+use crate::model::mj_raw::MjRaw;
+use crate::model::mj_section::{MjSection, MjSectionAttributes};
+use crate::model::mj_social::{
+    MjSocial, MjSocialAttributes, MjSocialElement, MjSocialElementAttributes, SocialMode,
+};
+use crate::model::mj_spacer::{MjSpacer, MjSpacerAttributes};
+use crate::model::mj_table::{MjTable, MjTableAttributes, TableLayout};
+use crate::model::mj_text::{MjText, MjTextAttributes, MjTextMobile};
+use crate::model::mj_wrapper::{MjWrapper, MjWrapperAttributes};
+use crate::model::mjml::{MjRawFileStart, Mjml, MjmlAttributes, Owa};
+use crate::model::schema::ValueKind;
+use crate::model::{
+    Alignment, BodyNode, BorderProperties, BorderStyle, Color, ColumnNode, CustomComponent,
+    DeviceVisibility, Direction, FontStyle, Length, Padding, PaddingValue, SectionNode,
+    TextAlignment, TextDecoration, TextTransform, VerticalAlignment,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+const INDENTATION: &str = "    ";
+
+impl Mjml {
+    /// Source of a function named `function_name` returning this document, with its imports.
+    ///
+    /// Struct literals only list the fields that differ from the default value, the others are
+    /// filled with `..Default::default()`. Custom components become `mj-raw` holding their markup.
+    pub fn to_rust(&self, function_name: &str) -> String {
+        let mut out = RustWriter::new(1);
+        ToRust::to_rust(self, &mut out);
+        format!(
+            "{}\npub fn {}() -> Mjml {{\n{}{}\n}}\n",
+            out.uses(),
+            function_name,
+            INDENTATION,
+            out.code
+        )
+    }
+
+    /// Block expression building this document, its imports included. The placeholder markers of
+    /// the strings become the matching parameter of `params`, see [`PLACEHOLDER_START`].
+    pub(crate) fn to_rust_expression(&self, params: &[String]) -> String {
+        let mut out = RustWriter::new(1);
+        out.params = params.to_vec();
+        ToRust::to_rust(self, &mut out);
+        let mut uses = String::new();
+        for line in out.uses().lines() {
+            uses.push_str(INDENTATION);
+            uses.push_str(line);
+            uses.push('\n');
+        }
+        format!("{{\n{}{}{}\n}}", uses, INDENTATION, out.code)
+    }
+}
+
+/// Start of the marker standing for a placeholder in a string, followed by the index of its
+/// parameter and [`PLACEHOLDER_END`]. Private use characters cannot clash with the template.
+pub const PLACEHOLDER_START: char = '\u{E000}';
+pub const PLACEHOLDER_END: char = '\u{E001}';
+
+/// Collects Rust source along with the paths it needs to import.
+pub(crate) struct RustWriter {
+    code: String,
+    depth: usize,
+    imports: BTreeSet<String>,
+    /// Start of each open struct literal in `code`, its path and its number of fields.
+    structs: Vec<(usize, &'static str, usize)>,
+    /// Parameters replacing the placeholder markers.
+    params: Vec<String>,
+}
+
+impl RustWriter {
+    fn new(depth: usize) -> Self {
+        Self {
+            code: String::new(),
+            depth,
+            imports: BTreeSet::new(),
+            structs: vec![],
+            params: vec![],
+        }
+    }
+
+    /// The `use` declarations of the imports, one per module in the order of the paths.
+    fn uses(&self) -> String {
+        let mut modules: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for import in &self.imports {
+            let (module, name) = import.rsplit_once("::").unwrap_or(("", import));
+            modules.entry(module).or_default().push(name);
+        }
+        let mut uses = String::new();
+        for (module, names) in modules {
+            match names[..] {
+                [name] => uses.push_str(&format!("use {}::{};\n", module, name)),
+                _ => uses.push_str(&format!("use {}::{{{}}};\n", module, names.join(", "))),
+            }
+        }
+        uses
+    }
+
+    pub(crate) fn write(&mut self, code: &str) {
+        self.code.push_str(code);
+    }
+
+    pub(crate) fn import(&mut self, path: &str) {
+        self.imports.insert(path.to_string());
+    }
+
+    fn indent(&mut self) {
+        self.code.push_str(&INDENTATION.repeat(self.depth));
+    }
+
+    /// Writes the type name and imports its path.
+    fn type_name<T: RustPath>(&mut self) {
+        self.import(T::PATH);
+        self.write(short_name(T::PATH));
+    }
+
+    pub(crate) fn open_struct<T: RustPath>(&mut self) {
+        self.structs.push((self.code.len(), T::PATH, 0));
+        self.write(short_name(T::PATH));
+        self.write(" {\n");
+        self.depth += 1;
+    }
+
+    pub(crate) fn field(&mut self, name: &str, value: &dyn ToRust) {
+        if let Some((_, _, fields)) = self.structs.last_mut() {
+            *fields += 1;
+        }
+        self.indent();
+        self.write(name);
+        self.write(": ");
+        value.to_rust(self);
+        self.write(",\n");
+    }
+
+    /// Ends the last struct literal, `rest_default` fills the missing fields from `Default`.
+    pub(crate) fn close_struct(&mut self, rest_default: bool) {
+        let (start, path, fields) = self.structs.pop().expect("an open struct");
+        self.depth -= 1;
+        if rest_default && fields == 0 {
+            self.code.truncate(start);
+            self.write("Default::default()");
+            return;
+        }
+        self.import(path);
+        if rest_default {
+            self.depth += 1;
+            self.indent();
+            self.write("..Default::default()\n");
+            self.depth -= 1;
+        }
+        self.indent();
+        self.write("}");
+    }
+
+    /// Writes a value on its own, to decide of the layout of a list.
+    fn inline(&mut self, value: &dyn ToRust) -> String {
+        let mut out = RustWriter::new(self.depth + 1);
+        out.params = self.params.clone();
+        value.to_rust(&mut out);
+        self.imports.append(&mut out.imports);
+        out.code
+    }
+
+    /// Writes the `format!` replacing the placeholder markers of `value` by their parameter.
+    fn placeholders(&mut self, value: &str) {
+        let escape = |text: &str| text.replace('{', "{{").replace('}', "}}");
+        let mut format = String::new();
+        let mut arguments = vec![];
+        let mut rest = value;
+        while let Some(start) = rest.find(PLACEHOLDER_START) {
+            let marker = &rest[start + PLACEHOLDER_START.len_utf8()..];
+            let end = marker.find(PLACEHOLDER_END).expect("a closed marker");
+            let index: usize = marker[..end].parse().expect("a parameter index");
+            format.push_str(&escape(&rest[..start]));
+            format.push_str("{}");
+            arguments.push(self.params[index].as_str());
+            rest = &marker[end + PLACEHOLDER_END.len_utf8()..];
+        }
+        format.push_str(&escape(rest));
+        self.write(&format!("format!({:?}, {})", format, arguments.join(", ")));
+    }
+
+    /// Writes `open`, the items separated by commas, then `close`. Items go on their own lines
+    /// unless they are all short.
+    fn list(&mut self, open: &str, items: &[&dyn ToRust], close: &str) {
+        let items: Vec<String> = items.iter().map(|item| self.inline(*item)).collect();
+        self.write(open);
+        let short = items.iter().map(String::len).sum::<usize>() <= 60;
+        if short && items.iter().all(|item| !item.contains('\n')) {
+            self.write(&items.join(", "));
+        } else {
+            self.write("\n");
+            self.depth += 1;
+            for item in items {
+                self.indent();
+                self.write(&item);
+                self.write(",\n");
+            }
+            self.depth -= 1;
+            self.indent();
+        }
+        self.write(close);
+    }
+}
+
+fn short_name(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// A value that can be written as the Rust expression building it.
+pub(crate) trait ToRust {
+    fn to_rust(&self, out: &mut RustWriter);
+}
+
+/// Path imported by the generated source to name a type.
+pub(crate) trait RustPath {
+    const PATH: &'static str;
+}
+
+macro_rules! rust_paths {
+    ($($module:literal => [$($type:ident),+ $(,)?]),+ $(,)?) => {
+        $($(
+            impl RustPath for $type {
+                const PATH: &'static str = concat!($module, "::", stringify!($type));
+            }
+        )+)+
+    };
+}
+
+rust_paths!(
+    "mjml_builder::model" => [
+        Alignment,
+        BorderProperties,
+        BorderStyle,
+        DeviceVisibility,
+        Direction,
+        FontStyle,
+        Padding,
+        PaddingValue,
+        TextAlignment,
+        TextDecoration,
+        TextTransform,
+        VerticalAlignment,
+    ],
+    "mjml_builder::model::background" => [
+        Background,
+        BackgroundPosition,
+        BackgroundRepeat,
+        BackgroundSize,
+        HeroBackground,
+        PositionX,
+        PositionY,
+    ],
+    "mjml_builder::model::mj_accordion" => [
+        IconPosition,
+        MjAccordion,
+        MjAccordionAttributes,
+        MjAccordionElement,
+        MjAccordionElementAttributes,
+        MjAccordionElementText,
+        MjAccordionElementTextAttributes,
+        MjAccordionElementTitle,
+        MjAccordionElementTitleAttributes,
+    ],
+    "mjml_builder::model::mj_body" => [MjBody, MjBodyAttributes],
+    "mjml_builder::model::mj_button" => [MjButton, MjButtonAttributes, MjButtonMobile],
+    "mjml_builder::model::mj_carousel" => [
+        MjCarousel,
+        MjCarouselAttributes,
+        MjCarouselImage,
+        MjCarouselImageAttributes,
+        Visibility,
+    ],
+    "mjml_builder::model::mj_column" => [MjColumn, MjColumnAttributes, MjColumnPaddingAttributes],
+    "mjml_builder::model::mj_divider" => [MjDivider, MjDividerAttributes],
+    "mjml_builder::model::mj_group" => [MjGroup, MjGroupAttributes],
+    "mjml_builder::model::mj_head" => [
+        MjAttribute,
+        MjAttributes,
+        MjClass,
+        MjFont,
+        MjHead,
+        MjHtmlAttribute,
+        MjSelector,
+        MjStyle,
+    ],
+    "mjml_builder::model::mj_hero" => [HeroMode, MjHero, MjHeroAttributes],
+    "mjml_builder::model::mj_image" => [MjImage, MjImageAttributes],
+    "mjml_builder::model::mj_navbar" => [
+        MjNavbar,
+        MjNavbarAttributes,
+        MjNavbarHamburgerAttributes,
+        MjNavbarLink,
+        MjNavbarLinkAttributes,
+    ],
+    "mjml_builder::model::mj_raw" => [MjRaw],
+    "mjml_builder::model::mj_section" => [MjSection, MjSectionAttributes],
+    "mjml_builder::model::mj_social" => [
+        MjSocial,
+        MjSocialAttributes,
+        MjSocialElement,
+        MjSocialElementAttributes,
+        SocialMode,
+    ],
+    "mjml_builder::model::mj_spacer" => [MjSpacer, MjSpacerAttributes],
+    "mjml_builder::model::mj_table" => [MjTable, MjTableAttributes, TableLayout],
+    "mjml_builder::model::mj_text" => [MjText, MjTextAttributes, MjTextMobile],
+    "mjml_builder::model::mj_wrapper" => [MjWrapper, MjWrapperAttributes],
+    "mjml_builder::model::mjml" => [MjRawFileStart, Mjml, MjmlAttributes, Owa],
+);
+
+impl ToRust for String {
+    fn to_rust(&self, out: &mut RustWriter) {
+        if !out.params.is_empty() && self.contains(PLACEHOLDER_START) {
+            return out.placeholders(self);
+        }
+        if self.contains(['\n', '"']) && !self.contains("\"#") {
+            out.write(&format!("r#\"{}\"#.to_string()", self));
+        } else {
+            out.write(&format!("{:?}.to_string()", self));
+        }
+    }
+}
+
+impl ToRust for u32 {
+    fn to_rust(&self, out: &mut RustWriter) {
+        out.write(&self.to_string());
+    }
+}
+
+impl ToRust for f32 {
+    fn to_rust(&self, out: &mut RustWriter) {
+        if self.is_nan() {
+            out.write("f32::NAN");
+        } else if self.is_infinite() {
+            out.write(if *self > 0.0 {
+                "f32::INFINITY"
+            } else {
+                "f32::NEG_INFINITY"
+            });
+        } else {
+            out.write(&format!("{:?}", self));
+        }
+    }
+}
+
+impl ToRust for bool {
+    fn to_rust(&self, out: &mut RustWriter) {
+        out.write(&self.to_string());
+    }
+}
+
+impl<T: ToRust> ToRust for Option<T> {
+    fn to_rust(&self, out: &mut RustWriter) {
+        match self {
+            Some(value) => {
+                out.write("Some(");
+                value.to_rust(out);
+                out.write(")");
+            }
+            None => out.write("None"),
+        }
+    }
+}
+
+impl<T: ToRust> ToRust for Vec<T> {
+    fn to_rust(&self, out: &mut RustWriter) {
+        let items: Vec<&dyn ToRust> = self.iter().map(|item| item as &dyn ToRust).collect();
+        out.list("vec![", &items, "]");
+    }
+}
+
+impl<A: ToRust, B: ToRust> ToRust for (A, B) {
+    fn to_rust(&self, out: &mut RustWriter) {
+        out.write("(");
+        self.0.to_rust(out);
+        out.write(", ");
+        self.1.to_rust(out);
+        out.write(")");
+    }
+}
+
+impl ToRust for BTreeMap<String, String> {
+    fn to_rust(&self, out: &mut RustWriter) {
+        out.import("std::collections::BTreeMap");
+        out.write("BTreeMap");
+        if self.is_empty() {
+            out.write("::new()");
+            return;
+        }
+        let pairs: Vec<(String, String)> = self
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let items: Vec<&dyn ToRust> = pairs.iter().map(|item| item as &dyn ToRust).collect();
+        out.list("::from([", &items, "])");
+    }
+}
+
+impl ToRust for Color {
+    fn to_rust(&self, out: &mut RustWriter) {
+        out.import("mjml_builder::model::Color");
+        if self.a == u8::MAX {
+            out.write(&format!("Color::rgb({}, {}, {})", self.r, self.g, self.b));
+        } else {
+            out.write(&format!(
+                "Color::rgba({}, {}, {}, {})",
+                self.r, self.g, self.b, self.a
+            ));
+        }
+    }
+}
+
+/// Whole pixels are written `SizePx::new(10)`, other lengths `Length::em(1.5)`.
+impl<U: Units> ToRust for Length<U> {
+    fn to_rust(&self, out: &mut RustWriter) {
+        let whole = self.value() >= 0.0 && self.value().fract() == 0.0;
+        if U::KIND == ValueKind::SizePx && whole {
+            out.import("mjml_builder::model::SizePx");
+            out.write(&format!("SizePx::new({})", self.value()));
+            return;
+        }
+        out.import("mjml_builder::model::Length");
+        let constructor = match self.unit() {
+            Unit::Px => "px",
+            Unit::Em => "em",
+            Unit::Rem => "rem",
+            Unit::Percent => "percent",
+            Unit::Unitless => "unitless",
+        };
+        out.write(&format!("Length::{}(", constructor));
+        self.value().to_rust(out);
+        out.write(")");
+    }
+}
+
+/// Writes `Type::Variant(value)`.
+fn variant<T: RustPath>(out: &mut RustWriter, variant: &str, value: Option<&dyn ToRust>) {
+    out.type_name::<T>();
+    out.write("::");
+    out.write(variant);
+    if let Some(value) = value {
+        out.write("(");
+        value.to_rust(out);
+        out.write(")");
+    }
+}
+
+impl ToRust for PaddingValue {
+    fn to_rust(&self, out: &mut RustWriter) {
+        match self {
+            Self::Px(px) => variant::<Self>(out, "Px", Some(px)),
+            Self::Inherit => variant::<Self>(out, "Inherit", None),
+        }
+    }
+}
+
+impl ToRust for Padding {
+    fn to_rust(&self, out: &mut RustWriter) {
+        out.open_struct::<Self>();
+        out.field("top", &self.top);
+        out.field("right", &self.right);
+        out.field("bottom", &self.bottom);
+        out.field("left", &self.left);
+        out.close_struct(false);
+    }
+}
+
+impl ToRust for BackgroundSize {
+    fn to_rust(&self, out: &mut RustWriter) {
+        match self {
+            Self::Auto => variant::<Self>(out, "Auto", None),
+            Self::Cover => variant::<Self>(out, "Cover", None),
+            Self::Contain => variant::<Self>(out, "Contain", None),
+            Self::Length(width, height) => {
+                variant::<Self>(out, "Length", None);
+                out.write("(");
+                width.to_rust(out);
+                out.write(", ");
+                height.to_rust(out);
+                out.write(")");
+            }
+        }
+    }
+}
+
+impl ToRust for PositionX {
+    fn to_rust(&self, out: &mut RustWriter) {
+        match self {
+            Self::Left => variant::<Self>(out, "Left", None),
+            Self::Center => variant::<Self>(out, "Center", None),
+            Self::Right => variant::<Self>(out, "Right", None),
+            Self::Offset(offset) => variant::<Self>(out, "Offset", Some(offset)),
+        }
+    }
+}
+
+impl ToRust for PositionY {
+    fn to_rust(&self, out: &mut RustWriter) {
+        match self {
+            Self::Top => variant::<Self>(out, "Top", None),
+            Self::Center => variant::<Self>(out, "Center", None),
+            Self::Bottom => variant::<Self>(out, "Bottom", None),
+            Self::Offset(offset) => variant::<Self>(out, "Offset", Some(offset)),
+        }
+    }
+}
+
+impl ToRust for BorderProperties {
+    fn to_rust(&self, out: &mut RustWriter) {
+        out.open_struct::<Self>();
+        out.field("width", &self.width);
+        out.field("style", &self.style);
+        out.field("color", &self.color);
+        out.close_struct(false);
+    }
+}
+
+/// Fieldless enums, written from the name of their variant.
+macro_rules! enum_to_rust {
+    ($($type:ty),+ $(,)?) => {
+        $(
+            impl ToRust for $type {
+                fn to_rust(&self, out: &mut RustWriter) {
+                    variant::<Self>(out, &format!("{:?}", self), None);
+                }
+            }
+        )+
+    };
+}
+
+enum_to_rust!(
+    Alignment,
+    BackgroundRepeat,
+    BorderStyle,
+    DeviceVisibility,
+    Direction,
+    FontStyle,
+    HeroMode,
+    IconPosition,
+    Owa,
+    SocialMode,
+    TableLayout,
+    TextAlignment,
+    TextDecoration,
+    TextTransform,
+    VerticalAlignment,
+    Visibility,
+);
+
+/// Structs without `Default`, written with all their fields.
+macro_rules! struct_to_rust {
+    ($($type:ty { $($field:ident),+ }),+ $(,)?) => {
+        $(
+            impl ToRust for $type {
+                fn to_rust(&self, out: &mut RustWriter) {
+                    out.open_struct::<Self>();
+                    $(out.field(stringify!($field), &self.$field);)+
+                    out.close_struct(false);
+                }
+            }
+        )+
+    };
+}
+
+/// Structs of optional values, only the fields that are set are written.
+macro_rules! options_to_rust {
+    ($($type:ty { $($field:ident),+ }),+ $(,)?) => {
+        $(
+            impl ToRust for $type {
+                fn to_rust(&self, out: &mut RustWriter) {
+                    out.open_struct::<Self>();
+                    $(
+                        if self.$field.is_some() {
+                            out.field(stringify!($field), &self.$field);
+                        }
+                    )+
+                    out.close_struct(true);
+                }
+            }
+        )+
+    };
+}
+
+options_to_rust!(
+    Background {
+        color,
+        position,
+        repeat,
+        size,
+        url
+    },
+    HeroBackground {
+        color,
+        position,
+        url
+    },
+    MjButtonMobile {
+        align,
+        font_size,
+        padding,
+        width
+    },
+    MjTextMobile {
+        align,
+        font_size,
+        line_height,
+        padding
+    },
+);
+
+struct_to_rust!(
+    Mjml {
+        mj_raw_file_start,
+        mj_body,
+        mj_head,
+        attributes
+    },
+    MjRawFileStart { content },
+    BackgroundPosition { x, y },
+    MjHead {
+        mj_attributes,
+        mj_breakpoint,
+        mj_font,
+        mj_html_attributes,
+        mj_preview,
+        mj_style,
+        mj_title
+    },
+    MjAttributes { content },
+    MjClass { name, attributes },
+    MjFont { name, href },
+    MjSelector {
+        path,
+        mj_html_attributes
+    },
+    MjHtmlAttribute { name, value },
+    MjStyle { inline, content },
+    MjBody {
+        attributes,
+        content
+    },
+    MjWrapper {
+        attributes,
+        content
+    },
+    MjSection {
+        attributes,
+        content
+    },
+    MjHero {
+        attributes,
+        content
+    },
+    MjGroup {
+        attributes,
+        columns
+    },
+    MjColumn {
+        attributes,
+        content
+    },
+    MjAccordion {
+        attributes,
+        elements
+    },
+    MjAccordionElement {
+        title,
+        text,
+        attributes
+    },
+    MjAccordionElementTitle {
+        content,
+        attributes
+    },
+    MjAccordionElementText {
+        content,
+        attributes
+    },
+    MjButton {
+        content,
+        attributes
+    },
+    MjCarousel { attributes, images },
+    MjCarouselImage {
+        content,
+        attributes
+    },
+    MjDivider { attributes },
+    MjImage { attributes },
+    MjNavbar { attributes, links },
+    MjNavbarLink {
+        content,
+        attributes
+    },
+    MjRaw { content },
+    MjSocial {
+        attributes,
+        elements
+    },
+    MjSocialElement {
+        content,
+        attributes
+    },
+    MjSpacer { attributes },
+    MjTable {
+        content,
+        attributes
+    },
+    MjText {
+        content,
+        attributes
+    },
+);
+
+/// Writes `value.into()`, for the children of a component.
+fn node(out: &mut RustWriter, value: &dyn ToRust) {
+    value.to_rust(out);
+    out.write(".into()");
+}
+
+impl ToRust for Box<dyn CustomComponent> {
+    fn to_rust(&self, out: &mut RustWriter) {
+        if let Some(raw) = self.as_any().downcast_ref::<MjRaw>() {
+            return node(out, raw);
+        }
+        let mut markup = String::new();
+        // a failing custom component also fails the markup rendering, keep what it produced
+        let _ = self.render(&mut markup, 0);
+        node(
+            out,
+            &MjRaw {
+                content: markup.trim_end().to_string(),
+            },
+        );
+    }
+}
+
+impl ToRust for BodyNode {
+    fn to_rust(&self, out: &mut RustWriter) {
+        match self {
+            Self::Section(section) => node(out, section),
+            Self::Wrapper(wrapper) => node(out, wrapper),
+            Self::Hero(hero) => node(out, hero),
+            Self::Custom(custom) => custom.to_rust(out),
+        }
+    }
+}
+
+impl ToRust for SectionNode {
+    fn to_rust(&self, out: &mut RustWriter) {
+        match self {
+            Self::Column(column) => node(out, column),
+            Self::Group(group) => node(out, group),
+            Self::Custom(custom) => custom.to_rust(out),
+        }
+    }
+}
+
+impl ToRust for ColumnNode {
+    fn to_rust(&self, out: &mut RustWriter) {
+        match self {
+            Self::Accordion(accordion) => node(out, accordion),
+            Self::Button(button) => node(out, button),
+            Self::Carousel(carousel) => node(out, carousel),
+            Self::Divider(divider) => node(out, divider),
+            Self::Image(image) => node(out, image),
+            Self::Navbar(navbar) => node(out, navbar),
+            Self::Raw(raw) => node(out, raw),
+            Self::Social(social) => node(out, social),
+            Self::Spacer(spacer) => node(out, spacer),
+            Self::Table(table) => node(out, table),
+            Self::Text(text) => node(out, text),
+            Self::Custom(custom) => custom.to_rust(out),
+        }
+    }
+}
+
+impl<A> RustPath for PartialAttributes<A> {
+    const PATH: &'static str = "mjml_builder::model::mj_head::PartialAttributes";
+}
+
+impl<A: ToRust> ToRust for PartialAttributes<A> {
+    fn to_rust(&self, out: &mut RustWriter) {
+        out.open_struct::<Self>();
+        out.field("attributes", &self.attributes);
+        out.field("unset", &self.unset);
+        out.close_struct(false);
+    }
+}
+
+macro_rules! mj_attribute_to_rust {
+    ($($variant:ident),+ $(,)?) => {
+        impl ToRust for MjAttribute {
+            fn to_rust(&self, out: &mut RustWriter) {
+                match self {
+                    $(Self::$variant(value) => {
+                        variant::<Self>(out, stringify!($variant), Some(value))
+                    })+
+                }
+            }
+        }
+    };
+}
+
+mj_attribute_to_rust!(
+    MjAll,
+    MjClass,
+    MjAccordion,
+    MjAccordionElement,
+    MjAccordionElementTitle,
+    MjAccordionElementText,
+    MjButton,
+    MjCarousel,
+    MjCarouselImage,
+    MjColumn,
+    MjDivider,
+    MjGroup,
+    MjHero,
+    MjImage,
+    MjNavbar,
+    MjNavbarLink,
+    MjSection,
+    MjSocial,
+    MjSocialElement,
+    MjSpacer,
+    MjTable,
+    MjText,
+    MjWrapper,
+);
+
+#[cfg(test)]
+mod test {
+    use crate::model::codegen::{RustWriter, ToRust};
+    use crate::model::length::AnyUnits;
+    use crate::model::mjml::Mjml;
+    use crate::model::Length;
+
+    #[test]
+    fn rust_expression() {
+        let mjml: Mjml = "<mjml><mj-body><mj-section><mj-column>
+<mj-text css-class=\"a \u{E000}1\u{E001}\">Hi {\u{E000}0\u{E001}}</mj-text>
+</mj-column></mj-section></mj-body></mjml>"
+            .parse()
+            .unwrap();
+        let expression = mjml.to_rust_expression(&["name".to_string(), "class".to_string()]);
+        assert!(expression.starts_with("{\n    use mjml_builder::model::mj_body::MjBody;\n"));
+        assert!(expression.contains("content: format!(\"Hi {{{}}}\", name),"));
+        assert!(expression.contains("css_class: vec![\"a\".to_string(), format!(\"{}\", class)],"));
+        assert!(expression.ends_with("    }\n}"));
+    }
+
+    #[test]
+    fn floats() {
+        for (value, expected) in [
+            (1.5, "1.5"),
+            (-0.25, "-0.25"),
+            (f32::NAN, "f32::NAN"),
+            (f32::INFINITY, "f32::INFINITY"),
+            (f32::NEG_INFINITY, "f32::NEG_INFINITY"),
+        ] {
+            let mut out = RustWriter::new(0);
+            value.to_rust(&mut out);
+            assert_eq!(out.code, expected);
+        }
+        let mut out = RustWriter::new(0);
+        Length::<AnyUnits>::em(f32::INFINITY).to_rust(&mut out);
+        assert_eq!(out.code, "Length::em(f32::INFINITY)");
+    }
+
+    #[test]
+    fn rust_source() {
+        let mjml: Mjml = r##"<mjml lang="en">
+  <mj-body>
+    <mj-section padding="10px 20px">
+      <mj-column>
+        <mj-text color="#333333" css-class="title">Hello</mj-text>
+        <mj-divider/>
+      </mj-column>
+    </mj-section>
+    <mj-raw><hr></mj-raw>
+  </mj-body>
+</mjml>"##
+            .parse()
+            .unwrap();
+        assert_eq!(
+            mjml.to_rust("welcome"),
+            r#"use mjml_builder::model::{Color, Padding, PaddingValue, SizePx};
+use mjml_builder::model::mj_body::MjBody;
+use mjml_builder::model::mj_column::MjColumn;
+use mjml_builder::model::mj_divider::MjDivider;
+use mjml_builder::model::mj_raw::MjRaw;
+use mjml_builder::model::mj_section::{MjSection, MjSectionAttributes};
+use mjml_builder::model::mj_text::{MjText, MjTextAttributes};
+use mjml_builder::model::mjml::{Mjml, MjmlAttributes};
+
+pub fn welcome() -> Mjml {
+    Mjml {
+        mj_raw_file_start: None,
+        mj_body: MjBody {
+            attributes: Default::default(),
+            content: vec![
+                MjSection {
+                    attributes: MjSectionAttributes {
+                        padding: Some(Padding {
+                            top: PaddingValue::Px(SizePx::new(10)),
+                            right: PaddingValue::Px(SizePx::new(20)),
+                            bottom: PaddingValue::Px(SizePx::new(10)),
+                            left: PaddingValue::Px(SizePx::new(20)),
+                        }),
+                        ..Default::default()
+                    },
+                    content: vec![
+                        MjColumn {
+                            attributes: Default::default(),
+                            content: vec![
+                                MjText {
+                                    content: "Hello".to_string(),
+                                    attributes: MjTextAttributes {
+                                        color: Some(Color::rgb(51, 51, 51)),
+                                        css_class: vec!["title".to_string()],
+                                        ..Default::default()
+                                    },
+                                }.into(),
+                                MjDivider {
+                                    attributes: Default::default(),
+                                }.into(),
+                            ],
+                        }.into(),
+                    ],
+                }.into(),
+                MjRaw {
+                    content: "<hr>".to_string(),
+                }.into(),
+            ],
+        },
+        mj_head: None,
+        attributes: MjmlAttributes {
+            lang: Some("en".to_string()),
+            ..Default::default()
+        },
+    }
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,468 @@
+//! Colors as accepted by MJML, with the operations needed to derive shades from a palette.
+
+use crate::model::ParseValueError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An sRGB color with an alpha channel.
+///
+/// It reads hex, `rgb()`, `rgba()`, `hsl()`, `hsla()`, named colors and `transparent`, and is
+/// written in the syntax the most email clients understand: `#RRGGBB` when opaque,
+/// `transparent`, or `rgba()` otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, u8::MAX)
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Hue in degrees, saturation and lightness from 0 to 1.
+    pub fn hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        Self::rgb(channel(r + m), channel(g + m), channel(b + m))
+    }
+
+    /// Color of a CSS named color, in lowercase.
+    pub fn named(name: &str) -> Option<Self> {
+        NAMED_COLORS
+            .binary_search_by_key(&name, |(name, _)| name)
+            .ok()
+            .map(|index| {
+                let [_, r, g, b] = NAMED_COLORS[index].1.to_be_bytes();
+                Self::rgb(r, g, b)
+            })
+    }
+
+    /// Hue in degrees, saturation and lightness from 0 to 1.
+    pub fn to_hsl(self) -> (f64, f64, f64) {
+        let [r, g, b] = [self.r, self.g, self.b].map(|c| f64::from(c) / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        (hue * 60.0, saturation, lightness)
+    }
+
+    /// Opacity from 0 to 1.
+    pub fn alpha(self) -> f64 {
+        f64::from(self.a) / 255.0
+    }
+
+    /// The same color with an opacity from 0 to 1.
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self {
+            a: channel(alpha),
+            ..self
+        }
+    }
+
+    /// Adds `amount`, from 0 to 1, to the lightness.
+    pub fn lighten(self, amount: f64) -> Self {
+        let (hue, saturation, lightness) = self.to_hsl();
+        Self::hsl(hue, saturation, lightness + amount).with_alpha(self.alpha())
+    }
+
+    /// Removes `amount`, from 0 to 1, from the lightness.
+    pub fn darken(self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Blends with `other`, `weight` is its share from 0 to 1.
+    pub fn mix(self, other: Color, weight: f64) -> Self {
+        let weight = weight.clamp(0.0, 1.0);
+        let blend = |from: u8, to: u8| {
+            channel((f64::from(from) * (1.0 - weight) + f64::from(to) * weight) / 255.0)
+        };
+        Self::rgba(
+            blend(self.r, other.r),
+            blend(self.g, other.g),
+            blend(self.b, other.b),
+            blend(self.a, other.a),
+        )
+    }
+
+    /// Relative luminance as defined by WCAG, from 0 for black to 1 for white.
+    pub fn luminance(self) -> f64 {
+        let linear = |c: u8| {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// Contrast ratio as defined by WCAG, from 1 to 21. AA asks for 4.5 for body text.
+    pub fn contrast(self, other: Color) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+/// Converts a value from 0 to 1 into a channel.
+fn channel(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.a {
+            u8::MAX => write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b),
+            _ if *self == Self::TRANSPARENT => write!(f, "transparent"),
+            _ => {
+                // three decimals are enough to read the same alpha back
+                let alpha = format!("{:.3}", self.alpha());
+                let alpha = alpha.trim_end_matches('0').trim_end_matches('.');
+                write!(f, "rgba({}, {}, {}, {})", self.r, self.g, self.b, alpha)
+            }
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseValueError::new("color", s);
+        let value = s.trim().to_ascii_lowercase();
+        if let Some(hex) = value.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(error);
+        }
+        if value == "transparent" {
+            return Ok(Self::TRANSPARENT);
+        }
+        if let Some((function, arguments)) = value
+            .strip_suffix(')')
+            .and_then(|value| value.split_once('('))
+        {
+            return parse_function(function.trim(), arguments).ok_or_else(error);
+        }
+        Self::named(&value).ok_or_else(error)
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |index: usize| u8::from_str_radix(&hex[index..index + 1], 16).ok();
+    let pair = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    match hex.len() {
+        3 | 4 => {
+            let short = |index: usize| digit(index).map(|digit| digit * 17);
+            let a = if hex.len() == 4 { short(3)? } else { u8::MAX };
+            Some(Color::rgba(short(0)?, short(1)?, short(2)?, a))
+        }
+        6 | 8 => {
+            let a = if hex.len() == 8 { pair(6)? } else { u8::MAX };
+            Some(Color::rgba(pair(0)?, pair(2)?, pair(4)?, a))
+        }
+        _ => None,
+    }
+}
+
+/// Reads `rgb()`, `rgba()`, `hsl()` and `hsla()`, with commas or spaces and `/` before alpha.
+fn parse_function(function: &str, arguments: &str) -> Option<Color> {
+    let arguments = arguments
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|argument| !argument.is_empty())
+        .collect::<Vec<_>>();
+    let (values, alpha) = match arguments[..] {
+        [a, b, c] => ([a, b, c], None),
+        [a, b, c, alpha] => ([a, b, c], Some(alpha)),
+        _ => return None,
+    };
+    let alpha = match alpha {
+        Some(alpha) => fraction(alpha, 1.0)?,
+        None => 1.0,
+    };
+    let color = match function {
+        "rgb" | "rgba" => {
+            let [r, g, b] = values.map(|value| fraction(value, 255.0));
+            Color::rgb(channel(r?), channel(g?), channel(b?))
+        }
+        "hsl" | "hsla" => {
+            let hue = values[0].strip_suffix("deg").unwrap_or(values[0]);
+            let saturation = values[1].strip_suffix('%')?.parse::<f64>().ok()?;
+            let lightness = values[2].strip_suffix('%')?.parse::<f64>().ok()?;
+            Color::hsl(hue.parse().ok()?, saturation / 100.0, lightness / 100.0)
+        }
+        _ => return None,
+    };
+    Some(color.with_alpha(alpha))
+}
+
+/// A number relative to `max`, or a percentage, as a value from 0 to 1.
+fn fraction(value: &str, max: f64) -> Option<f64> {
+    let fraction = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f64>().ok()? / 100.0,
+        None => value.parse::<f64>().ok()? / max,
+    };
+    fraction.is_finite().then_some(fraction)
+}
+
+crate::model::impl_serde_str!(Color);
+
+/// CSS named colors, sorted by name.
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
+#[cfg(test)]
+mod test {
+    use crate::model::Color;
+
+    #[test]
+    fn parse_colors() {
+        let red = Color::rgb(255, 0, 0);
+        for value in [
+            "#f00",
+            "#FF0000",
+            "#ff0000ff",
+            "rgb(255, 0, 0)",
+            "rgb(100% 0% 0%)",
+            "hsl(0, 100%, 50%)",
+            "hsl(360deg 100% 50%)",
+            "Red",
+        ] {
+            assert_eq!(value.parse(), Ok(red), "{}", value);
+        }
+        assert_eq!(
+            "rgba(255, 0, 0, 0.5)".parse(),
+            Ok(Color::rgba(255, 0, 0, 128))
+        );
+        assert_eq!(
+            "rgb(255 0 0 / 50%)".parse(),
+            Ok(Color::rgba(255, 0, 0, 128))
+        );
+        assert_eq!("#f008".parse(), Ok(Color::rgba(255, 0, 0, 0x88)));
+        assert_eq!("transparent".parse(), Ok(Color::TRANSPARENT));
+        assert_eq!("rebeccapurple".parse(), Ok(Color::rgb(0x66, 0x33, 0x99)));
+        assert_eq!("hsl(120, 50%, 25%)".parse(), Ok(Color::rgb(32, 96, 32)));
+        for value in ["#ff000", "#ggg", "rgb(1, 2)", "hsl(0, 1, 2)", "blurple", ""] {
+            assert!(value.parse::<Color>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn render_colors() {
+        assert_eq!(Color::rgb(0x0A, 0xBC, 0xDE).to_string(), "#0ABCDE");
+        assert_eq!(Color::TRANSPARENT.to_string(), "transparent");
+        assert_eq!(
+            Color::rgba(255, 0, 0, 128).to_string(),
+            "rgba(255, 0, 0, 0.502)"
+        );
+        assert_eq!(Color::rgba(255, 0, 0, 0).to_string(), "rgba(255, 0, 0, 0)");
+        for a in 0..=u8::MAX {
+            let color = Color::rgba(1, 2, 3, a);
+            assert_eq!(color.to_string().parse(), Ok(color));
+        }
+    }
+
+    #[test]
+    fn color_operations() {
+        let blue = Color::rgb(0x00, 0x66, 0xFF);
+        assert_eq!(blue.lighten(0.1), Color::rgb(0x33, 0x85, 0xFF));
+        assert_eq!(blue.darken(0.1), Color::rgb(0x00, 0x52, 0xCC));
+        assert_eq!(blue.darken(1.0), Color::BLACK);
+        assert_eq!(
+            Color::BLACK.mix(Color::WHITE, 0.5),
+            Color::rgb(128, 128, 128)
+        );
+        assert_eq!(blue.mix(Color::WHITE, 0.0), blue);
+        assert_eq!(blue.with_alpha(0.25).lighten(0.1).a, 64);
+        assert_eq!(Color::WHITE.luminance(), 1.0);
+        assert_eq!(Color::BLACK.luminance(), 0.0);
+        assert_eq!(Color::BLACK.contrast(Color::WHITE), 21.0);
+        let contrast = Color::rgb(0x77, 0x77, 0x77).contrast(Color::WHITE);
+        assert!((contrast - 4.48).abs() < 0.01, "{}", contrast);
+        assert_eq!(blue.contrast(blue), 1.0);
+    }
+}
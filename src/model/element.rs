@@ -0,0 +1,1193 @@
+//! Generic tree of MJML tags, the shape of the MJML JSON format used by mjml-parser-xml, the
+//! mjml-app or the GrapesJS plugin.
+
+use crate::model::mj_accordion::{
+    MjAccordion, MjAccordionElement, MjAccordionElementText, MjAccordionElementTitle,
+};
+use crate::model::mj_body::MjBody;
+use crate::model::mj_button::MjButton;
+use crate::model::mj_carousel::{MjCarousel, MjCarouselImage};
+use crate::model::mj_column::MjColumn;
+use crate::model::mj_divider::MjDivider;
+use crate::model::mj_group::MjGroup;
+use crate::model::mj_head::{
+    MjAttribute, MjAttributes, MjClass, MjFont, MjHead, MjHtmlAttribute, MjSelector, MjStyle,
+    PartialAttributes,
+};
+use crate::model::mj_hero::{MjHero, MjHeroAttributes};
+use crate::model::mj_image::MjImage;
+use crate::model::mj_navbar::{MjNavbar, MjNavbarLink};
+use crate::model::mj_raw::MjRaw;
+use crate::model::mj_section::MjSection;
+use crate::model::mj_social::{MjSocial, MjSocialElement};
+use crate::model::mj_spacer::MjSpacer;
+use crate::model::mj_table::MjTable;
+use crate::model::mj_text::MjText;
+use crate::model::mj_wrapper::MjWrapper;
+use crate::model::mjml::{MjRawFileStart, Mjml, MjmlAttributes};
+use crate::model::schema::{self, ValueKind};
+use crate::model::{
+    BodyNode, ColumnNode, CustomComponent, MjBaseComponentAttributes, MjBaseComponentSimplified,
+    Padding, ParseValueError, SectionNode,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// One tag of an MJML document.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Element {
+    pub tag_name: String,
+    pub attributes: BTreeMap<String, String>,
+    pub children: Vec<Element>,
+    /// Raw content of ending tags such as `mj-text`.
+    pub content: Option<String>,
+    /// Comments written right before the tag, without `<!--` and `-->`.
+    pub comments: Vec<String>,
+    /// Comments written after the last child, before the end tag.
+    pub end_comments: Vec<String>,
+}
+
+impl Element {
+    pub fn new(tag_name: &str) -> Self {
+        Self {
+            tag_name: tag_name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn with_attribute(mut self, name: &str, value: impl ToString) -> Self {
+        self.attributes.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    fn with_content(mut self, content: &str) -> Self {
+        self.content = Some(content.to_string());
+        self
+    }
+
+    fn attribute(&self, name: &str) -> Result<&str, ElementError> {
+        self.attributes
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| ElementError::MissingAttribute {
+                tag: self.tag_name.clone(),
+                name: name.to_string(),
+            })
+    }
+
+    fn content(&self) -> String {
+        self.content.clone().unwrap_or_default()
+    }
+
+    fn only_attributes(&self, names: &[&str]) -> Result<(), ElementError> {
+        match self
+            .attributes
+            .keys()
+            .find(|name| !names.contains(&name.as_str()))
+        {
+            Some(name) => Err(ElementError::UnknownAttribute {
+                tag: self.tag_name.clone(),
+                name: name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn no_children(&self) -> Result<(), ElementError> {
+        match self.children.first() {
+            Some(child) => Err(self.unexpected(child)),
+            None => Ok(()),
+        }
+    }
+
+    fn unexpected(&self, child: &Element) -> ElementError {
+        ElementError::UnexpectedTag {
+            parent: self.tag_name.clone(),
+            tag: child.tag_name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementError {
+    UnexpectedRoot(String),
+    UnexpectedTag {
+        parent: String,
+        tag: String,
+    },
+    UnknownAttribute {
+        tag: String,
+        name: String,
+    },
+    InvalidAttribute {
+        tag: String,
+        name: String,
+        source: ParseValueError,
+    },
+    MissingAttribute {
+        tag: String,
+        name: String,
+    },
+    MissingChild {
+        tag: String,
+        child: String,
+    },
+    /// The JSON is not a tree of `{tagName, attributes, children, content}` objects.
+    Malformed(String),
+}
+
+impl Display for ElementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedRoot(tag) => write!(f, "expected <mjml> at the root, found <{}>", tag),
+            Self::UnexpectedTag { parent, tag } => {
+                write!(f, "<{}> is not allowed in <{}>", tag, parent)
+            }
+            Self::UnknownAttribute { tag, name } => {
+                write!(f, "unknown attribute \"{}\" on <{}>", name, tag)
+            }
+            Self::InvalidAttribute { tag, name, source } => {
+                write!(f, "attribute \"{}\" of <{}>: {}", name, tag, source)
+            }
+            Self::MissingAttribute { tag, name } => {
+                write!(f, "missing attribute \"{}\" on <{}>", name, tag)
+            }
+            Self::MissingChild { tag, child } => write!(f, "<{}> needs a <{}>", tag, child),
+            Self::Malformed(reason) => write!(f, "malformed MJML JSON: {}", reason),
+        }
+    }
+}
+
+impl Error for ElementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidAttribute { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Mjml> for Element {
+    fn from(mjml: &Mjml) -> Self {
+        // the mobile overrides only exist in the model, the markup holds their classes and rules
+        let mjml = mjml.responsive();
+        let mjml = mjml.as_ref();
+        let mut element = Element::new("mjml");
+        element.attributes = attribute_map(&mjml.attributes);
+        if let Some(raw) = &mjml.mj_raw_file_start {
+            element.children.push(
+                Element::new("mj-raw")
+                    .with_attribute("position", "file-start")
+                    .with_content(&raw.content),
+            );
+        }
+        if let Some(head) = &mjml.mj_head {
+            element.children.push(export_head(head));
+        }
+        let body = &mjml.mj_body;
+        element.children.push(parent(
+            body,
+            body.content.iter().map(export_body_node).collect(),
+        ));
+        element
+    }
+}
+
+impl TryFrom<&Element> for Mjml {
+    type Error = ElementError;
+
+    fn try_from(element: &Element) -> Result<Self, Self::Error> {
+        if element.tag_name != "mjml" {
+            return Err(ElementError::UnexpectedRoot(element.tag_name.clone()));
+        }
+        let attributes = import_attributes(element, MjmlAttributes::default())?;
+
+        let mut mj_raw_file_start = None;
+        let mut mj_head = None;
+        let mut mj_body = None;
+        for child in &element.children {
+            match child.tag_name.as_str() {
+                // only the `file-start` one can sit at the root, once
+                "mj-raw" => {
+                    child.only_attributes(&["position"])?;
+                    child.no_children()?;
+                    match child.attributes.get("position").map(String::as_str) {
+                        Some("file-start") => {}
+                        Some(position) => {
+                            return Err(ElementError::InvalidAttribute {
+                                tag: child.tag_name.clone(),
+                                name: "position".to_string(),
+                                source: ParseValueError::new("position", position),
+                            })
+                        }
+                        None => {
+                            return Err(ElementError::MissingAttribute {
+                                tag: child.tag_name.clone(),
+                                name: "position".to_string(),
+                            })
+                        }
+                    }
+                    if mj_raw_file_start.is_some() {
+                        return Err(element.unexpected(child));
+                    }
+                    mj_raw_file_start = Some(MjRawFileStart {
+                        content: child.content(),
+                    });
+                }
+                "mj-head" => mj_head = Some(import_head(child)?),
+                "mj-body" => {
+                    mj_body = Some(MjBody {
+                        attributes: import_attributes(child, Default::default())?,
+                        content: import_body_nodes(child)?,
+                    })
+                }
+                _ => return Err(element.unexpected(child)),
+            }
+        }
+
+        Ok(Mjml {
+            mj_raw_file_start,
+            mj_body: mj_body.ok_or_else(|| ElementError::MissingChild {
+                tag: element.tag_name.clone(),
+                child: "mj-body".to_string(),
+            })?,
+            mj_head,
+            attributes,
+        })
+    }
+}
+
+pub(super) fn attribute_map(
+    attributes: &dyn MjBaseComponentAttributes,
+) -> BTreeMap<String, String> {
+    let mut map = BTreeMap::new();
+    // collecting into a map cannot fail
+    let _ = attributes.render(&mut map);
+    map
+}
+
+fn leaf<T: MjBaseComponentSimplified>(component: &T, content: Option<&str>) -> Element {
+    Element {
+        attributes: component
+            .attributes()
+            .map(attribute_map)
+            .unwrap_or_default(),
+        content: content.map(str::to_string),
+        ..Element::new(component.name())
+    }
+}
+
+fn parent<T: MjBaseComponentSimplified>(component: &T, children: Vec<Element>) -> Element {
+    Element {
+        children,
+        ..leaf(component, None)
+    }
+}
+
+fn export_head(head: &MjHead) -> Element {
+    let mut element = Element::new("mj-head");
+    if let Some(mj_attributes) = &head.mj_attributes {
+        let mut attributes = Element::new("mj-attributes");
+        attributes.children = mj_attributes
+            .content
+            .iter()
+            .map(export_mj_attribute)
+            .collect();
+        element.children.push(attributes);
+    }
+    if let Some(breakpoint) = &head.mj_breakpoint {
+        element
+            .children
+            .push(Element::new("mj-breakpoint").with_attribute("width", breakpoint));
+    }
+    for font in &head.mj_font {
+        element.children.push(
+            Element::new("mj-font")
+                .with_attribute("name", &font.name)
+                .with_attribute("href", &font.href),
+        );
+    }
+    if !head.mj_html_attributes.is_empty() {
+        let mut html_attributes = Element::new("mj-html-attributes");
+        for selector in &head.mj_html_attributes {
+            let mut element = Element::new("mj-selector").with_attribute("path", &selector.path);
+            for attribute in &selector.mj_html_attributes {
+                element.children.push(
+                    Element::new("mj-html-attribute")
+                        .with_attribute("name", &attribute.name)
+                        .with_content(&attribute.value),
+                );
+            }
+            html_attributes.children.push(element);
+        }
+        element.children.push(html_attributes);
+    }
+    if let Some(preview) = &head.mj_preview {
+        element
+            .children
+            .push(Element::new("mj-preview").with_content(preview));
+    }
+    for style in &head.mj_style {
+        let mut element_style = Element::new("mj-style").with_content(&style.content);
+        if style.inline {
+            element_style = element_style.with_attribute("inline", "inline");
+        }
+        element.children.push(element_style);
+    }
+    if let Some(title) = &head.mj_title {
+        element
+            .children
+            .push(Element::new("mj-title").with_content(title));
+    }
+    element
+}
+
+fn export_mj_attribute(attribute: &MjAttribute) -> Element {
+    match attribute {
+        MjAttribute::MjAll(attributes) => Element {
+            attributes: attributes.clone(),
+            ..Element::new("mj-all")
+        },
+        MjAttribute::MjClass(class) => Element {
+            attributes: class.attributes.clone(),
+            ..Element::new("mj-class")
+        }
+        .with_attribute("name", &class.name),
+        _ => {
+            let (tag, _) = attribute.component().expect("a component");
+            let mut element = Element::new(tag);
+            // collecting into a map cannot fail
+            let _ = attribute.render_defaults(&mut element.attributes);
+            element
+        }
+    }
+}
+
+/// Custom components become `mj-raw` tags holding their markup.
+fn export_custom(custom: &dyn CustomComponent) -> Element {
+    if let Some(raw) = custom.as_any().downcast_ref::<MjRaw>() {
+        return leaf(raw, Some(&raw.content));
+    }
+    let mut markup = String::new();
+    // a failing custom component also fails the markup rendering, keep what it produced
+    let _ = custom.render(&mut markup, 0);
+    Element::new("mj-raw").with_content(markup.trim_end())
+}
+
+fn export_body_node(node: &BodyNode) -> Element {
+    match node {
+        BodyNode::Section(section) => parent(
+            section,
+            section.content.iter().map(export_section_node).collect(),
+        ),
+        BodyNode::Wrapper(wrapper) => parent(
+            wrapper,
+            wrapper.content.iter().map(export_body_node).collect(),
+        ),
+        BodyNode::Hero(hero) => parent(hero, hero.content.iter().map(export_column_node).collect()),
+        BodyNode::Custom(custom) => export_custom(custom.as_ref()),
+    }
+}
+
+fn export_section_node(node: &SectionNode) -> Element {
+    match node {
+        SectionNode::Column(column) => export_column(column),
+        SectionNode::Group(group) => {
+            parent(group, group.columns.iter().map(export_column).collect())
+        }
+        SectionNode::Custom(custom) => export_custom(custom.as_ref()),
+    }
+}
+
+fn export_column(column: &MjColumn) -> Element {
+    parent(
+        column,
+        column.content.iter().map(export_column_node).collect(),
+    )
+}
+
+fn export_column_node(node: &ColumnNode) -> Element {
+    match node {
+        ColumnNode::Accordion(accordion) => parent(
+            accordion,
+            accordion
+                .elements
+                .iter()
+                .map(|element| {
+                    parent(
+                        element,
+                        vec![
+                            leaf(&element.title, Some(&element.title.content)),
+                            leaf(&element.text, Some(&element.text.content)),
+                        ],
+                    )
+                })
+                .collect(),
+        ),
+        ColumnNode::Button(button) => leaf(button, Some(&button.content)),
+        ColumnNode::Carousel(carousel) => parent(
+            carousel,
+            carousel
+                .images
+                .iter()
+                .map(|image| leaf(image, Some(&image.content)))
+                .collect(),
+        ),
+        ColumnNode::Divider(divider) => leaf(divider, None),
+        ColumnNode::Image(image) => leaf(image, None),
+        ColumnNode::Navbar(navbar) => parent(
+            navbar,
+            navbar
+                .links
+                .iter()
+                .map(|link| leaf(link, Some(&link.content)))
+                .collect(),
+        ),
+        ColumnNode::Raw(raw) => leaf(raw, Some(&raw.content)),
+        ColumnNode::Social(social) => parent(
+            social,
+            social
+                .elements
+                .iter()
+                .map(|element| leaf(element, Some(&element.content)))
+                .collect(),
+        ),
+        ColumnNode::Spacer(spacer) => leaf(spacer, None),
+        ColumnNode::Table(table) => leaf(table, Some(&table.content)),
+        ColumnNode::Text(text) => leaf(text, Some(&text.content)),
+        ColumnNode::Custom(custom) => export_custom(custom.as_ref()),
+    }
+}
+
+/// Sets every attribute of the element on `attributes`, after folding the `padding-top` like
+/// sides into their shorthand.
+fn import_attributes<A: MjBaseComponentAttributes>(
+    element: &Element,
+    attributes: A,
+) -> Result<A, ElementError> {
+    let attributes = import_defaults(element, attributes)?;
+    let schema = schema::component(&element.tag_name);
+    for attribute in schema.iter().flat_map(|schema| schema.attributes) {
+        if attribute.required && !element.attributes.contains_key(attribute.name) {
+            return Err(ElementError::MissingAttribute {
+                tag: element.tag_name.clone(),
+                name: attribute.name.to_string(),
+            });
+        }
+    }
+    Ok(attributes)
+}
+
+/// Reads the attributes of an `mj-attributes` entry, noting the required ones that are not set.
+fn import_partial<A: MjBaseComponentAttributes>(
+    element: &Element,
+    attributes: A,
+) -> Result<PartialAttributes<A>, ElementError> {
+    let attributes = import_defaults(element, attributes)?;
+    let unset = schema::component(&element.tag_name)
+        .into_iter()
+        .flat_map(|schema| schema.attributes)
+        .filter(|attribute| attribute.required && !element.attributes.contains_key(attribute.name))
+        .map(|attribute| attribute.name.to_string())
+        .collect();
+    Ok(PartialAttributes { attributes, unset })
+}
+
+/// Reads the attributes without requiring any, as `mj-attributes` entries do.
+fn import_defaults<A: MjBaseComponentAttributes>(
+    element: &Element,
+    mut attributes: A,
+) -> Result<A, ElementError> {
+    let tag = element.tag_name.as_str();
+    let invalid = |name: &str, source| ElementError::InvalidAttribute {
+        tag: tag.to_string(),
+        name: name.to_string(),
+        source,
+    };
+
+    let mut values = element.attributes.clone();
+    let schema = schema::component(tag);
+    let paddings = schema
+        .iter()
+        .flat_map(|schema| schema.attributes)
+        .filter(|attribute| attribute.kind == ValueKind::Padding);
+    for padding in paddings {
+        let sides = ["top", "right", "bottom", "left"]
+            .map(|side| values.remove(&format!("{}-{}", padding.name, side)));
+        if sides.iter().all(Option::is_none) {
+            continue;
+        }
+        let base = values
+            .get(padding.name)
+            .map(String::as_str)
+            .or(padding.default)
+            .unwrap_or("0");
+        let mut value: Padding = base
+            .parse()
+            .map_err(|source| invalid(padding.name, source))?;
+        let [top, right, bottom, left] = sides;
+        for (side, target) in [
+            (top, &mut value.top),
+            (right, &mut value.right),
+            (bottom, &mut value.bottom),
+            (left, &mut value.left),
+        ] {
+            if let Some(side) = side {
+                *target = side
+                    .parse()
+                    .map_err(|source| invalid(padding.name, source))?;
+            }
+        }
+        values.insert(padding.name.to_string(), value.to_string());
+    }
+
+    for (name, value) in &values {
+        if !attributes
+            .set(name, value)
+            .map_err(|source| invalid(name, source))?
+        {
+            return Err(ElementError::UnknownAttribute {
+                tag: tag.to_string(),
+                name: name.clone(),
+            });
+        }
+    }
+
+    Ok(attributes)
+}
+
+fn import_head(element: &Element) -> Result<MjHead, ElementError> {
+    element.only_attributes(&[])?;
+    let mut head = MjHead {
+        mj_attributes: None,
+        mj_breakpoint: None,
+        mj_font: vec![],
+        mj_html_attributes: vec![],
+        mj_preview: None,
+        mj_style: vec![],
+        mj_title: None,
+    };
+    for child in &element.children {
+        match child.tag_name.as_str() {
+            "mj-attributes" => {
+                child.only_attributes(&[])?;
+                head.mj_attributes = Some(MjAttributes {
+                    content: child
+                        .children
+                        .iter()
+                        .map(|attribute| import_mj_attribute(child, attribute))
+                        .collect::<Result<_, _>>()?,
+                });
+            }
+            "mj-breakpoint" => {
+                child.only_attributes(&["width"])?;
+                let width = child.attribute("width")?;
+                head.mj_breakpoint =
+                    Some(
+                        width
+                            .parse()
+                            .map_err(|source| ElementError::InvalidAttribute {
+                                tag: child.tag_name.clone(),
+                                name: "width".to_string(),
+                                source,
+                            })?,
+                    );
+            }
+            "mj-font" => {
+                child.only_attributes(&["name", "href"])?;
+                head.mj_font.push(MjFont {
+                    name: child.attribute("name")?.to_string(),
+                    href: child.attribute("href")?.to_string(),
+                });
+            }
+            "mj-html-attributes" => {
+                child.only_attributes(&[])?;
+                for selector in &child.children {
+                    if selector.tag_name != "mj-selector" {
+                        return Err(child.unexpected(selector));
+                    }
+                    selector.only_attributes(&["path"])?;
+                    let mut mj_html_attributes = vec![];
+                    for attribute in &selector.children {
+                        if attribute.tag_name != "mj-html-attribute" {
+                            return Err(selector.unexpected(attribute));
+                        }
+                        attribute.only_attributes(&["name"])?;
+                        mj_html_attributes.push(MjHtmlAttribute {
+                            name: attribute.attribute("name")?.to_string(),
+                            value: attribute.content(),
+                        });
+                    }
+                    head.mj_html_attributes.push(MjSelector {
+                        path: selector.attribute("path")?.to_string(),
+                        mj_html_attributes,
+                    });
+                }
+            }
+            "mj-preview" => {
+                child.only_attributes(&[])?;
+                head.mj_preview = Some(child.content());
+            }
+            "mj-style" => {
+                child.only_attributes(&["inline"])?;
+                head.mj_style.push(MjStyle {
+                    inline: child.attributes.get("inline").map(String::as_str) == Some("inline"),
+                    content: child.content(),
+                });
+            }
+            "mj-title" => {
+                child.only_attributes(&[])?;
+                head.mj_title = Some(child.content());
+            }
+            _ => return Err(element.unexpected(child)),
+        }
+    }
+    Ok(head)
+}
+
+fn import_mj_attribute(parent: &Element, element: &Element) -> Result<MjAttribute, ElementError> {
+    element.no_children()?;
+    let default = &element.attributes;
+    Ok(match element.tag_name.as_str() {
+        "mj-all" => MjAttribute::MjAll(default.clone()),
+        "mj-class" => {
+            let mut attributes = default.clone();
+            let name = attributes.remove("name");
+            MjAttribute::MjClass(MjClass {
+                name: name.ok_or_else(|| ElementError::MissingAttribute {
+                    tag: element.tag_name.clone(),
+                    name: "name".to_string(),
+                })?,
+                attributes,
+            })
+        }
+        "mj-accordion" => MjAttribute::MjAccordion(import_defaults(element, Default::default())?),
+        "mj-accordion-element" => {
+            MjAttribute::MjAccordionElement(import_defaults(element, Default::default())?)
+        }
+        "mj-accordion-title" => {
+            MjAttribute::MjAccordionElementTitle(import_defaults(element, Default::default())?)
+        }
+        "mj-accordion-text" => {
+            MjAttribute::MjAccordionElementText(import_defaults(element, Default::default())?)
+        }
+        "mj-button" => MjAttribute::MjButton(import_defaults(element, Default::default())?),
+        "mj-carousel" => MjAttribute::MjCarousel(import_defaults(element, Default::default())?),
+        "mj-carousel-image" => {
+            MjAttribute::MjCarouselImage(import_defaults(element, Default::default())?)
+        }
+        "mj-column" => MjAttribute::MjColumn(import_defaults(element, Default::default())?),
+        "mj-divider" => MjAttribute::MjDivider(import_defaults(element, Default::default())?),
+        "mj-group" => MjAttribute::MjGroup(import_defaults(element, Default::default())?),
+        "mj-hero" => MjAttribute::MjHero(import_partial(element, MjHeroAttributes::placeholder())?),
+        "mj-image" => MjAttribute::MjImage(import_partial(element, Default::default())?),
+        "mj-navbar" => MjAttribute::MjNavbar(import_defaults(element, Default::default())?),
+        "mj-navbar-link" => {
+            MjAttribute::MjNavbarLink(import_defaults(element, Default::default())?)
+        }
+        "mj-section" => MjAttribute::MjSection(import_defaults(element, Default::default())?),
+        "mj-social" => MjAttribute::MjSocial(import_defaults(element, Default::default())?),
+        "mj-social-element" => {
+            MjAttribute::MjSocialElement(import_defaults(element, Default::default())?)
+        }
+        "mj-spacer" => MjAttribute::MjSpacer(import_defaults(element, Default::default())?),
+        "mj-table" => MjAttribute::MjTable(import_defaults(element, Default::default())?),
+        "mj-text" => MjAttribute::MjText(import_defaults(element, Default::default())?),
+        "mj-wrapper" => MjAttribute::MjWrapper(import_defaults(element, Default::default())?),
+        _ => return Err(parent.unexpected(element)),
+    })
+}
+
+fn import_raw(element: &Element) -> Result<MjRaw, ElementError> {
+    element.only_attributes(&[])?;
+    element.no_children()?;
+    Ok(MjRaw {
+        content: element.content(),
+    })
+}
+
+fn import_body_nodes(element: &Element) -> Result<Vec<BodyNode>, ElementError> {
+    element
+        .children
+        .iter()
+        .map(|child| {
+            Ok(match child.tag_name.as_str() {
+                "mj-section" => BodyNode::Section(MjSection {
+                    attributes: import_attributes(child, Default::default())?,
+                    content: import_section_nodes(child)?,
+                }),
+                "mj-wrapper" => BodyNode::Wrapper(MjWrapper {
+                    attributes: import_attributes(child, Default::default())?,
+                    content: import_body_nodes(child)?,
+                }),
+                "mj-hero" => BodyNode::Hero(MjHero {
+                    attributes: import_attributes(child, MjHeroAttributes::placeholder())?,
+                    content: import_column_nodes(child)?,
+                }),
+                "mj-raw" => BodyNode::Custom(Box::new(import_raw(child)?)),
+                _ => return Err(element.unexpected(child)),
+            })
+        })
+        .collect()
+}
+
+fn import_section_nodes(element: &Element) -> Result<Vec<SectionNode>, ElementError> {
+    element
+        .children
+        .iter()
+        .map(|child| {
+            Ok(match child.tag_name.as_str() {
+                "mj-column" => SectionNode::Column(import_column(child)?),
+                "mj-group" => SectionNode::Group(MjGroup {
+                    attributes: import_attributes(child, Default::default())?,
+                    columns: child
+                        .children
+                        .iter()
+                        .map(|column| match column.tag_name.as_str() {
+                            "mj-column" => import_column(column),
+                            _ => Err(child.unexpected(column)),
+                        })
+                        .collect::<Result<_, _>>()?,
+                }),
+                "mj-raw" => SectionNode::Custom(Box::new(import_raw(child)?)),
+                _ => return Err(element.unexpected(child)),
+            })
+        })
+        .collect()
+}
+
+fn import_column(element: &Element) -> Result<MjColumn, ElementError> {
+    Ok(MjColumn {
+        content: import_column_nodes(element)?,
+        attributes: import_attributes(element, Default::default())?,
+    })
+}
+
+/// Children of a component that only holds tags of a single kind.
+fn import_items<T>(
+    element: &Element,
+    tag: &str,
+    import: impl Fn(&Element) -> Result<T, ElementError>,
+) -> Result<Vec<T>, ElementError> {
+    element
+        .children
+        .iter()
+        .map(|child| {
+            if child.tag_name == tag {
+                import(child)
+            } else {
+                Err(element.unexpected(child))
+            }
+        })
+        .collect()
+}
+
+fn import_column_nodes(element: &Element) -> Result<Vec<ColumnNode>, ElementError> {
+    element
+        .children
+        .iter()
+        .map(|child| {
+            let leaf = || child.no_children();
+            Ok(match child.tag_name.as_str() {
+                "mj-accordion" => ColumnNode::Accordion(MjAccordion {
+                    elements: import_items(
+                        child,
+                        "mj-accordion-element",
+                        import_accordion_element,
+                    )?,
+                    attributes: import_attributes(child, Default::default())?,
+                }),
+                "mj-button" => {
+                    leaf()?;
+                    ColumnNode::Button(MjButton {
+                        content: child.content(),
+                        attributes: import_attributes(child, Default::default())?,
+                    })
+                }
+                "mj-carousel" => ColumnNode::Carousel(MjCarousel {
+                    images: import_items(child, "mj-carousel-image", |image| {
+                        image.no_children()?;
+                        Ok(MjCarouselImage {
+                            content: image.content(),
+                            attributes: import_attributes(image, Default::default())?,
+                        })
+                    })?,
+                    attributes: import_attributes(child, Default::default())?,
+                }),
+                "mj-divider" => {
+                    leaf()?;
+                    ColumnNode::Divider(MjDivider {
+                        attributes: import_attributes(child, Default::default())?,
+                    })
+                }
+                "mj-image" => {
+                    leaf()?;
+                    ColumnNode::Image(MjImage {
+                        attributes: import_attributes(child, Default::default())?,
+                    })
+                }
+                "mj-navbar" => ColumnNode::Navbar(MjNavbar {
+                    links: import_items(child, "mj-navbar-link", |link| {
+                        link.no_children()?;
+                        Ok(MjNavbarLink {
+                            content: link.content(),
+                            attributes: import_attributes(link, Default::default())?,
+                        })
+                    })?,
+                    attributes: import_attributes(child, Default::default())?,
+                }),
+                "mj-raw" => ColumnNode::Raw(import_raw(child)?),
+                "mj-social" => ColumnNode::Social(MjSocial {
+                    elements: import_items(child, "mj-social-element", |social| {
+                        social.no_children()?;
+                        Ok(MjSocialElement {
+                            content: social.content(),
+                            attributes: import_attributes(social, Default::default())?,
+                        })
+                    })?,
+                    attributes: import_attributes(child, Default::default())?,
+                }),
+                "mj-spacer" => {
+                    leaf()?;
+                    ColumnNode::Spacer(MjSpacer {
+                        attributes: import_attributes(child, Default::default())?,
+                    })
+                }
+                "mj-table" => {
+                    leaf()?;
+                    ColumnNode::Table(MjTable {
+                        content: child.content(),
+                        attributes: import_attributes(child, Default::default())?,
+                    })
+                }
+                "mj-text" => {
+                    leaf()?;
+                    ColumnNode::Text(MjText {
+                        content: child.content(),
+                        attributes: import_attributes(child, Default::default())?,
+                    })
+                }
+                _ => return Err(element.unexpected(child)),
+            })
+        })
+        .collect()
+}
+
+fn import_accordion_element(element: &Element) -> Result<MjAccordionElement, ElementError> {
+    let mut title = None;
+    let mut text = None;
+    for child in &element.children {
+        child.no_children()?;
+        match child.tag_name.as_str() {
+            "mj-accordion-title" => {
+                title = Some(MjAccordionElementTitle {
+                    content: child.content(),
+                    attributes: import_attributes(child, Default::default())?,
+                })
+            }
+            "mj-accordion-text" => {
+                text = Some(MjAccordionElementText {
+                    content: child.content(),
+                    attributes: import_attributes(child, Default::default())?,
+                })
+            }
+            _ => return Err(element.unexpected(child)),
+        }
+    }
+    let missing = |child: &str| ElementError::MissingChild {
+        tag: element.tag_name.clone(),
+        child: child.to_string(),
+    };
+    Ok(MjAccordionElement {
+        title: title.ok_or_else(|| missing("mj-accordion-title"))?,
+        text: text.ok_or_else(|| missing("mj-accordion-text"))?,
+        attributes: import_attributes(element, Default::default())?,
+    })
+}
+
+#[cfg(feature = "json")]
+impl Element {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut object = serde_json::Map::new();
+        object.insert("tagName".into(), self.tag_name.clone().into());
+        object.insert(
+            "attributes".into(),
+            self.attributes
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone().into()))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+        );
+        match &self.content {
+            Some(content) => {
+                object.insert("content".into(), content.clone().into());
+            }
+            None => {
+                object.insert(
+                    "children".into(),
+                    self.children.iter().map(Element::to_json).collect(),
+                );
+            }
+        }
+        object.into()
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, ElementError> {
+        use serde_json::Value;
+
+        let malformed = |reason: &str| ElementError::Malformed(reason.to_string());
+        let object = value
+            .as_object()
+            .ok_or_else(|| malformed("a node is not an object"))?;
+        let tag_name = object
+            .get("tagName")
+            .and_then(Value::as_str)
+            .ok_or_else(|| malformed("a node has no tagName"))?;
+
+        let mut attributes = BTreeMap::new();
+        match object.get("attributes") {
+            Some(Value::Object(values)) => {
+                for (name, value) in values {
+                    let value = match value {
+                        Value::String(value) => value.clone(),
+                        Value::Number(value) => value.to_string(),
+                        Value::Bool(value) => value.to_string(),
+                        _ => return Err(malformed(&format!("attribute {} is not a string", name))),
+                    };
+                    attributes.insert(name.clone(), value);
+                }
+            }
+            Some(Value::Null) | None => {}
+            Some(_) => {
+                return Err(malformed(&format!(
+                    "attributes of {} is not an object",
+                    tag_name
+                )))
+            }
+        }
+
+        let children = match object.get("children") {
+            Some(Value::Array(children)) => children
+                .iter()
+                .map(Element::from_json)
+                .collect::<Result<_, _>>()?,
+            Some(Value::Null) | None => vec![],
+            Some(_) => {
+                return Err(malformed(&format!(
+                    "children of {} is not an array",
+                    tag_name
+                )))
+            }
+        };
+
+        let content = match object.get("content") {
+            Some(Value::String(content)) => Some(content.clone()),
+            Some(Value::Null) | None => None,
+            Some(_) => {
+                return Err(malformed(&format!(
+                    "content of {} is not a string",
+                    tag_name
+                )))
+            }
+        };
+
+        Ok(Self {
+            attributes,
+            children,
+            content,
+            ..Self::new(tag_name)
+        })
+    }
+}
+
+#[cfg(feature = "json")]
+impl Mjml {
+    /// Converts to the MJML JSON format, `{tagName, attributes, children, content}` nodes.
+    pub fn to_json(&self) -> serde_json::Value {
+        Element::from(self).to_json()
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, ElementError> {
+        Mjml::try_from(&Element::from_json(value)?)
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+    use crate::model::element::ElementError;
+    use crate::model::mj_text::MjTextMobile;
+    use crate::model::mjml::Mjml;
+    use crate::model::{BodyNode, ColumnNode, DeviceVisibility, SectionNode, SizePx};
+    use serde_json::json;
+
+    fn sample() -> serde_json::Value {
+        json!({
+            "tagName": "mjml",
+            "attributes": { "lang": "en" },
+            "children": [
+                {
+                    "tagName": "mj-head",
+                    "attributes": {},
+                    "children": [
+                        {
+                            "tagName": "mj-attributes",
+                            "attributes": {},
+                            "children": [
+                                { "tagName": "mj-all", "attributes": { "font-family": "Arial" } },
+                                { "tagName": "mj-text", "attributes": { "color": "#333333" } }
+                            ]
+                        },
+                        { "tagName": "mj-title", "attributes": {}, "content": "Hello" }
+                    ]
+                },
+                {
+                    "tagName": "mj-body",
+                    "attributes": { "width": "500px" },
+                    "children": [
+                        {
+                            "tagName": "mj-section",
+                            "attributes": { "padding": "10px 20px", "full-width": "full-width" },
+                            "children": [
+                                {
+                                    "tagName": "mj-column",
+                                    "attributes": { "width": "50%", "background-color": "#FFEEDD" },
+                                    "children": [
+                                        {
+                                            "tagName": "mj-text",
+                                            "attributes": {
+                                                "font-size": "20px",
+                                                "padding-left": "5px",
+                                                "css-class": "title"
+                                            },
+                                            "content": "Hello <b>World</b>"
+                                        },
+                                        {
+                                            "tagName": "mj-image",
+                                            "attributes": { "src": "https://example.com/a.png" }
+                                        },
+                                        {
+                                            "tagName": "mj-navbar",
+                                            "attributes": {
+                                                "align": "left",
+                                                "base-url": "https://example.com",
+                                                "hamburger": "hamburger",
+                                                "ico-color": "#FF0000"
+                                            },
+                                            "children": [
+                                                {
+                                                    "tagName": "mj-navbar-link",
+                                                    "attributes": { "href": "/about" },
+                                                    "content": "About"
+                                                }
+                                            ]
+                                        }
+                                    ]
+                                }
+                            ]
+                        },
+                        { "tagName": "mj-raw", "attributes": {}, "content": "<hr>" }
+                    ]
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn round_trip() {
+        let mjml = Mjml::from_json(&sample()).unwrap();
+        assert_eq!(mjml.attributes.lang.as_deref(), Some("en"));
+
+        let json = mjml.to_json();
+        let text = &json["children"][1]["children"][0]["children"][0]["children"][0];
+        assert_eq!(text["tagName"], "mj-text");
+        assert_eq!(text["content"], "Hello <b>World</b>");
+        assert_eq!(text["attributes"]["font-size"], "20px");
+        // the missing sides come from the default padding of mj-text
+        assert_eq!(text["attributes"]["padding"], "10px 25px 10px 5px");
+        assert_eq!(json["children"][1]["children"][1]["content"], "<hr>");
+        let column = &json["children"][1]["children"][0]["children"][0];
+        assert_eq!(column["attributes"]["background-color"], "#FFEEDD");
+        let navbar = &column["children"][2];
+        assert_eq!(navbar["attributes"]["base-url"], "https://example.com");
+        assert_eq!(navbar["attributes"]["hamburger"], "hamburger");
+
+        assert_eq!(Mjml::from_json(&json).unwrap(), mjml);
+    }
+
+    #[test]
+    fn mobile_overrides() {
+        let mut mjml = Mjml::from_json(&sample()).unwrap();
+        let BodyNode::Section(section) = &mut mjml.mj_body.content[0] else {
+            panic!("expected a section");
+        };
+        let SectionNode::Column(column) = &mut section.content[0] else {
+            panic!("expected a column");
+        };
+        column.attributes.visibility = Some(DeviceVisibility::DesktopOnly);
+        let ColumnNode::Text(text) = &mut column.content[0] else {
+            panic!("expected a text");
+        };
+        text.attributes.mobile = Some(MjTextMobile {
+            font_size: Some(SizePx::new(14)),
+            ..Default::default()
+        });
+
+        let json = mjml.to_json();
+        let column = &json["children"][1]["children"][0]["children"][0];
+        assert_eq!(column["attributes"]["css-class"], "mjb-desktop-only");
+        let text = &column["children"][0];
+        assert_eq!(text["attributes"]["css-class"], "title mjb-mobile-1");
+        let head = json["children"][0]["children"].as_array().unwrap();
+        let style = head
+            .iter()
+            .find(|node| node["tagName"] == "mj-style")
+            .unwrap();
+        assert_eq!(
+            style["content"],
+            "@media only screen and (max-width:479px) {
+  .mjb-desktop-only { display: none !important; max-height: 0 !important; overflow: hidden !important; }
+  .mjb-mobile-1 div { font-size: 14px !important; }
+}"
+        );
+
+        // the rendered document has the same classes and rules
+        let mut rendered = mjml.clone();
+        rendered.apply_mobile_overrides();
+        assert_eq!(Mjml::from_json(&json).unwrap(), rendered);
+    }
+
+    #[test]
+    fn errors() {
+        let mut json = sample();
+        json["children"][1]["children"][0]["attributes"]["padding"] = "wide".into();
+        assert!(matches!(
+            Mjml::from_json(&json),
+            Err(ElementError::InvalidAttribute { name, .. }) if name == "padding"
+        ));
+
+        let mut json = sample();
+        json["children"][1]["attributes"]["colour"] = "red".into();
+        assert_eq!(
+            Mjml::from_json(&json),
+            Err(ElementError::UnknownAttribute {
+                tag: "mj-body".to_string(),
+                name: "colour".to_string()
+            })
+        );
+
+        let mut json = sample();
+        json["children"][1]["children"][0]["children"][0]["tagName"] = "mj-section".into();
+        assert!(matches!(
+            Mjml::from_json(&json),
+            Err(ElementError::UnexpectedTag { .. })
+        ));
+
+        let mut json = sample();
+        json["children"][1]["children"][0]["children"][0]["children"][1]["attributes"] = json!({});
+        assert!(matches!(
+            Mjml::from_json(&json),
+            Err(ElementError::MissingAttribute { name, .. }) if name == "src"
+        ));
+    }
+}
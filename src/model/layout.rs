@@ -0,0 +1,383 @@
+//! Width available to the content of every node of the body, computed the way MJML does.
+
+use crate::model::element::attribute_map;
+use crate::model::length::Unit;
+use crate::model::mj_body::MjBody;
+use crate::model::mj_column::MjColumn;
+use crate::model::mj_head::{MjAttribute, MjAttributes, DEFAULT_BREAKPOINT};
+use crate::model::mjml::Mjml;
+use crate::model::schema;
+use crate::model::{
+    BodyNode, BorderProperties, ColumnNode, MjBaseComponentSimplified, Padding, PaddingValue,
+    PxOrPercent, SectionNode, SizePx,
+};
+use std::collections::BTreeMap;
+
+/// Widths of the nodes of a document, from [`Mjml::layout`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Layout {
+    widths: BTreeMap<Vec<usize>, NodeWidth>,
+}
+
+/// Width available to the content of a node: inside the paddings and borders of sections,
+/// wrappers, heroes and columns, the size of images and the inner width of other components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeWidth {
+    pub desktop: SizePx,
+    /// Once the columns are stacked, on the widest screen below the breakpoint.
+    pub mobile: SizePx,
+}
+
+impl Layout {
+    /// Width of the node at `path`, the index of the node in the content of each of its
+    /// ancestors: `[1, 0, 2]` is the third child of the first column of the second section. The
+    /// columns of a group are its children.
+    pub fn width(&self, path: &[usize]) -> Option<NodeWidth> {
+        self.widths.get(path).copied()
+    }
+
+    /// Every node with a width, in document order. Custom components have none.
+    pub fn iter(&self) -> impl Iterator<Item = (&[usize], NodeWidth)> + '_ {
+        self.widths
+            .iter()
+            .map(|(path, width)| (path.as_slice(), *width))
+    }
+}
+
+impl Mjml {
+    /// Computes the width of every node of the body, see [`Layout`].
+    ///
+    /// Attributes are read the way MJML does: from the component, then its `mj-class`, the
+    /// `mj-attributes` of its tag, `mj-all` and the MJML default.
+    pub fn layout(&self) -> Layout {
+        let head = self.mj_head.as_ref();
+        let mj_attributes = head.and_then(|head| head.mj_attributes.as_ref());
+        let breakpoint = head
+            .and_then(|head| head.mj_breakpoint)
+            .unwrap_or(DEFAULT_BREAKPOINT);
+        let width = self.mj_body.attributes.width.value();
+        // mobile layouts are shown up to one pixel below the breakpoint
+        let mobile_width = width.min(breakpoint.value() - 1.0);
+
+        let desktop = Pass::run(mj_attributes, &self.mj_body, width, false);
+        let mobile = Pass::run(mj_attributes, &self.mj_body, mobile_width, true);
+        Layout {
+            widths: desktop
+                .into_iter()
+                .zip(mobile.into_values())
+                .map(|((path, desktop), mobile)| {
+                    let width = NodeWidth {
+                        desktop: SizePx::px(desktop),
+                        mobile: SizePx::px(mobile),
+                    };
+                    (path, width)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Computes the widths of every node, for desktop or for stacked columns.
+struct Pass<'a> {
+    mj_attributes: Option<&'a MjAttributes>,
+    stacked: bool,
+    path: Vec<usize>,
+    widths: BTreeMap<Vec<usize>, f32>,
+}
+
+impl<'a> Pass<'a> {
+    fn run(
+        mj_attributes: Option<&'a MjAttributes>,
+        body: &MjBody,
+        width: f32,
+        stacked: bool,
+    ) -> BTreeMap<Vec<usize>, f32> {
+        let mut pass = Self {
+            mj_attributes,
+            stacked,
+            path: vec![],
+            widths: BTreeMap::new(),
+        };
+        pass.children(&body.content, |pass, node| pass.body_node(node, width));
+        pass.widths
+    }
+
+    /// Visits `nodes`, with their index pushed to the path.
+    fn children<T>(&mut self, nodes: &[T], mut visit: impl FnMut(&mut Self, &T)) {
+        for (index, node) in nodes.iter().enumerate() {
+            self.path.push(index);
+            visit(self, node);
+            self.path.pop();
+        }
+    }
+
+    fn insert(&mut self, width: f32) {
+        self.widths.insert(self.path.clone(), width.max(0.0));
+    }
+
+    fn attributes(&self, component: &dyn MjBaseComponentSimplified) -> Attributes {
+        Attributes::resolve(self.mj_attributes, component)
+    }
+
+    fn body_node(&mut self, node: &BodyNode, container: f32) {
+        match node {
+            BodyNode::Section(section) => {
+                let width = self.attributes(section).box_width(container);
+                self.insert(width);
+                self.section_content(&section.content, width);
+            }
+            BodyNode::Wrapper(wrapper) => {
+                let width = self.attributes(wrapper).box_width(container);
+                self.insert(width);
+                self.children(&wrapper.content, |pass, node| pass.body_node(node, width));
+            }
+            BodyNode::Hero(hero) => {
+                // heroes have no border
+                let width = container - self.attributes(hero).paddings("padding");
+                self.insert(width);
+                self.children(&hero.content, |pass, node| pass.column_node(node, width));
+            }
+            BodyNode::Custom(_) => {}
+        }
+    }
+
+    fn section_content(&mut self, nodes: &[SectionNode], container: f32) {
+        let siblings = nodes
+            .iter()
+            .filter(|node| !matches!(node, SectionNode::Custom(_)))
+            .count();
+        self.children(nodes, |pass, node| match node {
+            SectionNode::Column(column) => pass.column(column, container, siblings, pass.stacked),
+            SectionNode::Group(group) => {
+                let attributes = pass.attributes(group);
+                let outer = if pass.stacked {
+                    container
+                } else {
+                    share(attributes.width(), container, siblings)
+                };
+                let width = outer - attributes.paddings("padding");
+                pass.insert(width);
+                let siblings = group.columns.len();
+                // the columns of a group are never stacked
+                pass.children(&group.columns, |pass, column| {
+                    pass.column(column, width, siblings, false)
+                });
+            }
+            SectionNode::Custom(_) => {}
+        });
+    }
+
+    fn column(&mut self, column: &MjColumn, container: f32, siblings: usize, stacked: bool) {
+        let attributes = self.attributes(column);
+        let outer = if stacked {
+            container
+        } else {
+            share(attributes.width(), container, siblings)
+        };
+        let width = outer
+            - attributes.paddings("padding")
+            - attributes.borders("border")
+            - attributes.borders("inner-border");
+        self.insert(width);
+        self.children(&column.content, |pass, node| pass.column_node(node, width));
+    }
+
+    fn column_node(&mut self, node: &ColumnNode, container: f32) {
+        let component: &dyn MjBaseComponentSimplified = match node {
+            ColumnNode::Accordion(accordion) => accordion,
+            ColumnNode::Button(button) => button,
+            ColumnNode::Carousel(carousel) => carousel,
+            ColumnNode::Divider(divider) => divider,
+            ColumnNode::Image(image) => image,
+            ColumnNode::Navbar(navbar) => navbar,
+            ColumnNode::Raw(raw) => raw,
+            ColumnNode::Social(social) => social,
+            ColumnNode::Spacer(spacer) => spacer,
+            ColumnNode::Table(table) => table,
+            ColumnNode::Text(text) => text,
+            ColumnNode::Custom(_) => return,
+        };
+        let attributes = self.attributes(component);
+        let mut width = attributes.box_width(container);
+        if let ColumnNode::Image(_) = node {
+            let fluid = self.stacked && attributes.get("fluid-on-mobile") == Some("true");
+            let image_width = attributes
+                .get("width")
+                .and_then(|width| width.parse::<SizePx>().ok());
+            if let (false, Some(image_width)) = (fluid, image_width) {
+                width = width.min(image_width.value().trunc());
+            }
+        }
+        self.insert(width);
+    }
+}
+
+/// Outer width of a column or a group: its own width, or an equal share of the container.
+fn share(width: Option<PxOrPercent>, container: f32, siblings: usize) -> f32 {
+    match width {
+        Some(width) if width.unit() == Unit::Percent => container * width.value() / 100.0,
+        Some(width) => width.value(),
+        None => container / siblings.max(1) as f32,
+    }
+}
+
+/// Attributes of a component resolved from the head.
+struct Attributes {
+    tag: &'static str,
+    values: BTreeMap<String, String>,
+}
+
+impl Attributes {
+    fn resolve(
+        mj_attributes: Option<&MjAttributes>,
+        component: &dyn MjBaseComponentSimplified,
+    ) -> Self {
+        let tag = component.name();
+        let own = component
+            .attributes()
+            .map(attribute_map)
+            .unwrap_or_default();
+        let mut values = BTreeMap::new();
+        let head = mj_attributes.map_or(&[][..], |attributes| &attributes.content[..]);
+        for attribute in head {
+            if let MjAttribute::MjAll(all) = attribute {
+                values.extend(all.clone());
+            }
+        }
+        for attribute in head {
+            if let Some((name, _)) = attribute.component() {
+                if name == tag {
+                    // collecting into a map cannot fail
+                    let _ = attribute.render_defaults(&mut values);
+                }
+            }
+        }
+        let classes = own.get("mj-class").map_or("", String::as_str);
+        for class in classes.split_whitespace() {
+            for attribute in head {
+                match attribute {
+                    MjAttribute::MjClass(mj_class) if mj_class.name == class => {
+                        values.extend(mj_class.attributes.clone())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        values.extend(own);
+        Self { tag, values }
+    }
+
+    /// Value set on the component or in the head.
+    fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Like [`Attributes::get`], falling back to the MJML default.
+    fn get_or_default(&self, name: &str) -> Option<&str> {
+        self.get(name)
+            .or_else(|| schema::component(self.tag)?.attribute(name)?.default)
+    }
+
+    /// Set width, the default widths depend on the siblings.
+    fn width(&self) -> Option<PxOrPercent> {
+        self.get("width")?.parse().ok()
+    }
+
+    /// Left and right values of a padding, `padding-left` taking precedence over `padding`.
+    fn paddings(&self, name: &str) -> f32 {
+        let side = |side: &str, shorthand: fn(Padding) -> PaddingValue| {
+            let value = match self.get(&format!("{}-{}", name, side)) {
+                Some(value) => value.parse().ok(),
+                None => self
+                    .get_or_default(name)
+                    .and_then(|value| value.parse::<Padding>().ok())
+                    .map(shorthand),
+            };
+            match value {
+                Some(PaddingValue::Px(px)) => px.value(),
+                Some(PaddingValue::Inherit) | None => 0.0,
+            }
+        };
+        side("left", |padding| padding.left) + side("right", |padding| padding.right)
+    }
+
+    /// Left and right widths of a border, `border-left` taking precedence over `border`.
+    fn borders(&self, name: &str) -> f32 {
+        let side = |side: &str| {
+            self.get(&format!("{}-{}", name, side))
+                .or_else(|| self.get_or_default(name))
+                .and_then(|value| value.parse::<BorderProperties>().ok())
+                .and_then(|border| border.width)
+                .map_or(0.0, |width| width.value())
+        };
+        side("left") + side("right")
+    }
+
+    /// Width inside the paddings and borders, from the whole pixels of the container.
+    fn box_width(&self, container: f32) -> f32 {
+        container.trunc() - self.paddings("padding") - self.borders("border")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::layout::NodeWidth;
+    use crate::model::mjml::Mjml;
+    use crate::model::SizePx;
+
+    fn width(desktop: f32, mobile: f32) -> Option<NodeWidth> {
+        Some(NodeWidth {
+            desktop: SizePx::px(desktop),
+            mobile: SizePx::px(mobile),
+        })
+    }
+
+    #[test]
+    fn layout() {
+        let mjml: Mjml = r#"<mjml><mj-head><mj-attributes>
+<mj-all padding="0px" />
+<mj-column padding="5px" />
+<mj-class name="wide" padding="0 40px" />
+</mj-attributes></mj-head><mj-body width="640px">
+<mj-section padding="10px 20px" border-left="2px solid #000000">
+<mj-column width="25%"><mj-image src="https://example.com/a.png" width="300px" /></mj-column>
+<mj-column padding="4px" inner-border="1px solid #000000"><mj-text mj-class="wide">Hello</mj-text><mj-image src="https://example.com/b.png" fluid-on-mobile="true" width="300px" /></mj-column>
+</mj-section>
+<mj-wrapper padding="0 10px"><mj-section><mj-group width="400px"><mj-column><mj-button>Go</mj-button></mj-column><mj-column width="25%"></mj-column></mj-group></mj-section></mj-wrapper>
+<mj-hero background-width="600px" background-height="300px" padding="0 30px"><mj-text padding="0 10px">Hi</mj-text></mj-hero>
+</mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        let layout = mjml.layout();
+
+        // 640 - 2 * 20 - 2, and 479 - 2 * 20 - 2 once stacked
+        assert_eq!(layout.width(&[0]), width(598.0, 437.0));
+        // 25% of 598 - 2 * 5
+        assert_eq!(layout.width(&[0, 0]), width(139.5, 427.0));
+        assert_eq!(layout.width(&[0, 0, 0]), width(139.0, 300.0));
+        // half of 598 - 2 * 4 - 2 * 1
+        assert_eq!(layout.width(&[0, 1]), width(289.0, 427.0));
+        assert_eq!(layout.width(&[0, 1, 0]), width(209.0, 347.0));
+        assert_eq!(layout.width(&[0, 1, 1]), width(289.0, 427.0));
+
+        assert_eq!(layout.width(&[1]), width(620.0, 459.0));
+        assert_eq!(layout.width(&[1, 0]), width(620.0, 459.0));
+        assert_eq!(layout.width(&[1, 0, 0]), width(400.0, 459.0));
+        assert_eq!(layout.width(&[1, 0, 0, 0]), width(190.0, 219.5));
+        assert_eq!(layout.width(&[1, 0, 0, 0, 0]), width(190.0, 219.0));
+        assert_eq!(layout.width(&[1, 0, 0, 1]), width(90.0, 104.75));
+
+        assert_eq!(layout.width(&[2]), width(580.0, 419.0));
+        assert_eq!(layout.width(&[2, 0]), width(560.0, 399.0));
+        assert_eq!(layout.width(&[3]), None);
+        assert_eq!(layout.iter().count(), 14);
+
+        // without attributes, the MJML defaults apply
+        let mjml: Mjml = r#"<mjml><mj-body><mj-section><mj-column><mj-text>Hello</mj-text></mj-column><mj-column /></mj-section></mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        let layout = mjml.layout();
+        assert_eq!(layout.width(&[0, 0]), width(300.0, 479.0));
+        assert_eq!(layout.width(&[0, 0, 0]), width(250.0, 429.0));
+    }
+}
@@ -0,0 +1,397 @@
+//! CSS lengths, restricted at the type level to the units accepted by each attribute.
+
+use crate::model::schema::ValueKind;
+use crate::model::{impl_serde_str, AttributeValue, AttributeWriter, ParseValueError};
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Font size relative lengths are resolved against when nothing else is known.
+pub const DEFAULT_FONT_SIZE: f32 = 16.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Px,
+    Em,
+    Rem,
+    Percent,
+    /// A bare number, as in `line-height="1.5"`.
+    Unitless,
+}
+
+impl Unit {
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Px => "px",
+            Self::Em => "em",
+            Self::Rem => "rem",
+            Self::Percent => "%",
+            Self::Unitless => "",
+        }
+    }
+}
+
+/// Set of units a [`Length`] may hold.
+pub trait Units: Debug + Clone + Copy + PartialEq + 'static {
+    const UNITS: &'static [Unit];
+    const KIND: ValueKind;
+    /// Whether percentages and bare numbers are relative to the font size rather than the width.
+    const FONT_RELATIVE: bool = false;
+    /// Whether negative values are rejected when reading, as for sizes and widths.
+    const NON_NEGATIVE: bool = false;
+}
+
+pub trait AllowsPx: Units {}
+pub trait AllowsEm: Units {}
+pub trait AllowsRem: Units {}
+pub trait AllowsPercent: Units {}
+pub trait AllowsUnitless: Units {}
+
+macro_rules! units {
+    ($(#[$doc:meta])* $name:ident => $kind:expr, [$($unit:ident: $allows:ident),+] $(, $flag:ident)*) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $name;
+
+        impl Units for $name {
+            const UNITS: &'static [Unit] = &[$(Unit::$unit),+];
+            const KIND: ValueKind = $kind;
+            $(const $flag: bool = true;)*
+        }
+
+        $(impl $allows for $name {})+
+    };
+}
+
+units!(
+    /// Pixels only, bare numbers are read as pixels.
+    PxUnits => ValueKind::SizePx, [Px: AllowsPx], NON_NEGATIVE
+);
+units!(
+    /// Units of font sizes and of `letter-spacing`, which may be negative.
+    PxEmUnits => ValueKind::PxOrEm, [Px: AllowsPx, Em: AllowsEm]
+);
+units!(
+    PxPercentUnits => ValueKind::PxOrPercent,
+    [Px: AllowsPx, Percent: AllowsPercent],
+    NON_NEGATIVE
+);
+units!(
+    /// Units of `line-height`, where percentages and bare numbers scale the font size.
+    LineHeightUnits => ValueKind::LineHeight,
+    [Px: AllowsPx, Percent: AllowsPercent, Unitless: AllowsUnitless],
+    FONT_RELATIVE,
+    NON_NEGATIVE
+);
+units!(
+    AnyUnits => ValueKind::Length,
+    [
+        Px: AllowsPx,
+        Em: AllowsEm,
+        Rem: AllowsRem,
+        Percent: AllowsPercent,
+        Unitless: AllowsUnitless
+    ]
+);
+
+pub type SizePx = Length<PxUnits>;
+pub type PxOrEm = Length<PxEmUnits>;
+pub type PxOrPercent = Length<PxPercentUnits>;
+pub type LineHeight = Length<LineHeightUnits>;
+
+/// A number with a unit among `U`, read from and written as `"12.5px"`.
+///
+/// Constructors only exist for the units of `U`, so an attribute cannot be given a unit MJML
+/// would reject. Holding a float, it is not `Eq`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Length<U: Units = AnyUnits> {
+    value: f32,
+    unit: Unit,
+    units: PhantomData<U>,
+}
+
+/// What relative lengths are resolved against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LengthContext {
+    /// Width of the containing box, in pixels.
+    pub width: f32,
+    pub font_size: f32,
+    pub root_font_size: f32,
+}
+
+impl LengthContext {
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            font_size: DEFAULT_FONT_SIZE,
+            root_font_size: DEFAULT_FONT_SIZE,
+        }
+    }
+}
+
+impl<U: Units> Length<U> {
+    const fn with_unit(value: f32, unit: Unit) -> Self {
+        Self {
+            value,
+            unit,
+            units: PhantomData,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn unit(&self) -> Unit {
+        self.unit
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0.0
+    }
+
+    /// Sum of both lengths, `None` when their units differ or the result overflows.
+    ///
+    /// Zero has no unit, so adding or subtracting it always succeeds.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let unit = match (self.unit == rhs.unit, self.is_zero(), rhs.is_zero()) {
+            (true, _, _) | (_, _, true) => self.unit,
+            (_, true, _) => rhs.unit,
+            _ => return None,
+        };
+        finite(self.value + rhs.value, unit)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(Self::with_unit(-rhs.value, rhs.unit))
+    }
+
+    pub fn checked_mul(self, factor: f32) -> Option<Self> {
+        finite(self.value * factor, self.unit)
+    }
+
+    /// `None` when dividing by zero.
+    pub fn checked_div(self, divisor: f32) -> Option<Self> {
+        finite(self.value / divisor, self.unit)
+    }
+
+    /// The same length in pixels.
+    pub fn to_px(&self, context: &LengthContext) -> Option<SizePx> {
+        let px = match self.unit {
+            Unit::Px => self.value,
+            Unit::Em => self.value * context.font_size,
+            Unit::Rem => self.value * context.root_font_size,
+            Unit::Percent if U::FONT_RELATIVE => self.value * context.font_size / 100.0,
+            Unit::Percent => self.value * context.width / 100.0,
+            Unit::Unitless if U::FONT_RELATIVE => self.value * context.font_size,
+            Unit::Unitless => self.value,
+        };
+        finite(px, Unit::Px)
+    }
+
+    /// The same length for an attribute accepting other units, `None` when they lack its unit.
+    pub fn convert<V: Units>(self) -> Option<Length<V>> {
+        if self.is_zero() && V::UNITS.contains(&Unit::Px) {
+            return Some(Length::with_unit(0.0, Unit::Px));
+        }
+        V::UNITS
+            .contains(&self.unit)
+            .then(|| Length::with_unit(self.value, self.unit))
+    }
+}
+
+fn finite<U: Units>(value: f32, unit: Unit) -> Option<Length<U>> {
+    value.is_finite().then(|| Length::with_unit(value, unit))
+}
+
+impl<U: AllowsPx> Length<U> {
+    pub const fn px(value: f32) -> Self {
+        Self::with_unit(value, Unit::Px)
+    }
+}
+
+impl<U: AllowsEm> Length<U> {
+    pub const fn em(value: f32) -> Self {
+        Self::with_unit(value, Unit::Em)
+    }
+}
+
+impl<U: AllowsRem> Length<U> {
+    pub const fn rem(value: f32) -> Self {
+        Self::with_unit(value, Unit::Rem)
+    }
+}
+
+impl<U: AllowsPercent> Length<U> {
+    pub const fn percent(value: f32) -> Self {
+        Self::with_unit(value, Unit::Percent)
+    }
+}
+
+impl<U: AllowsUnitless> Length<U> {
+    pub const fn unitless(value: f32) -> Self {
+        Self::with_unit(value, Unit::Unitless)
+    }
+}
+
+impl SizePx {
+    /// Whole pixels.
+    pub const fn new(px: u32) -> Self {
+        Self::px(px as f32)
+    }
+
+    /// Whole pixels, rounded.
+    #[deprecated(note = "sizes may hold fractions of pixels, use `value`")]
+    pub fn get(&self) -> u32 {
+        self.value.max(0.0).round() as u32
+    }
+}
+
+impl<U: Units> Display for Length<U> {
+    /// Zero pixels are written `0`, as MJML does.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.unit {
+            Unit::Px if self.is_zero() => write!(f, "0"),
+            // avoids writing `-0`
+            _ if self.is_zero() => write!(f, "0{}", self.unit.suffix()),
+            _ => write!(f, "{}{}", self.value, self.unit.suffix()),
+        }
+    }
+}
+
+impl<U: Units> FromStr for Length<U> {
+    type Err = ParseValueError;
+
+    /// Reads a number followed by one of the units of `U`, a bare number is read as pixels
+    /// when `U` has no unitless values. Negative numbers are rejected for sizes and widths.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseValueError::new("size", s);
+        let split = s
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+            .unwrap_or(s.len());
+        let value = s[..split].parse::<f32>().map_err(|_| invalid())?;
+        let unit = match &s[split..] {
+            "px" => Unit::Px,
+            "em" => Unit::Em,
+            "rem" => Unit::Rem,
+            "%" => Unit::Percent,
+            "" if !U::UNITS.contains(&Unit::Unitless) => Unit::Px,
+            "" => Unit::Unitless,
+            _ => return Err(invalid()),
+        };
+        if !value.is_finite() || (U::NON_NEGATIVE && value < 0.0) || !U::UNITS.contains(&unit) {
+            return Err(invalid());
+        }
+        Ok(Self::with_unit(value, unit))
+    }
+}
+
+impl<U: Units> AttributeValue for Length<U> {
+    const KIND: ValueKind = U::KIND;
+
+    fn parse_value(value: &str) -> Result<Self, ParseValueError> {
+        value.parse()
+    }
+
+    fn render_value(&self, name: &str, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        writer.attribute(name, self)
+    }
+}
+
+impl_serde_str!(SizePx, PxOrEm, PxOrPercent, LineHeight, Length);
+
+#[cfg(test)]
+mod test {
+    use crate::model::length::{
+        AnyUnits, Length, LengthContext, LineHeight, PxPercentUnits, PxUnits, Unit,
+    };
+    use crate::model::mjml::Mjml;
+    use crate::model::{PxOrEm, PxOrPercent, SizePx};
+
+    #[test]
+    fn parse_length() {
+        assert_eq!("12.5px".parse(), Ok(SizePx::px(12.5)));
+        assert_eq!("600".parse(), Ok(SizePx::new(600)));
+        assert_eq!("0".parse(), Ok(PxOrEm::px(0.0)));
+        assert_eq!("-0.5em".parse(), Ok(PxOrEm::em(-0.5)));
+        assert_eq!("33.33%".parse(), Ok(PxOrPercent::percent(33.33)));
+        assert_eq!("1.5".parse(), Ok(LineHeight::unitless(1.5)));
+        assert_eq!("2rem".parse::<Length>(), Ok(Length::rem(2.0)));
+        assert!("1em".parse::<SizePx>().is_err());
+        assert!("1rem".parse::<PxOrEm>().is_err());
+        assert!("1.5em".parse::<LineHeight>().is_err());
+        assert!("px".parse::<SizePx>().is_err());
+        assert!("10 px".parse::<SizePx>().is_err());
+        assert!("10pt".parse::<Length>().is_err());
+        assert!("-600px".parse::<SizePx>().is_err());
+        assert!("-50%".parse::<PxOrPercent>().is_err());
+        assert!("-1.5".parse::<LineHeight>().is_err());
+        assert_eq!("-1px".parse(), Ok(PxOrEm::px(-1.0)));
+        for markup in [
+            r#"<mjml><mj-body width="-600px"></mj-body></mjml>"#,
+            r#"<mjml><mj-body><mj-section><mj-column width="-50%"></mj-column></mj-section></mj-body></mjml>"#,
+        ] {
+            assert!(markup.parse::<Mjml>().is_err(), "{}", markup);
+        }
+
+        for value in ["0", "12.5px", "-1px", "0.5em", "100%", "1.5", "2rem"] {
+            assert_eq!(value.parse::<Length>().unwrap().to_string(), value);
+        }
+        assert_eq!("0%".parse::<PxOrPercent>().unwrap().to_string(), "0%");
+        assert_eq!("-0px".parse::<SizePx>().unwrap().to_string(), "0");
+    }
+
+    #[test]
+    fn length_arithmetic() {
+        let ten = SizePx::new(10);
+        assert_eq!(ten.checked_add(SizePx::px(2.5)), Some(SizePx::px(12.5)));
+        assert_eq!(ten.checked_sub(SizePx::new(20)), Some(SizePx::px(-10.0)));
+        assert_eq!(ten.checked_mul(1.5), Some(SizePx::new(15)));
+        assert_eq!(ten.checked_div(4.0), Some(SizePx::px(2.5)));
+        assert_eq!(ten.checked_div(0.0), None);
+        assert_eq!(SizePx::px(f32::MAX).checked_add(SizePx::px(f32::MAX)), None);
+
+        assert_eq!(PxOrEm::em(1.0).checked_add(PxOrEm::px(2.0)), None);
+        assert_eq!(
+            PxOrEm::em(1.0).checked_add(PxOrEm::px(0.0)),
+            Some(PxOrEm::em(1.0))
+        );
+        assert_eq!(
+            PxOrEm::px(0.0).checked_sub(PxOrEm::em(1.0)),
+            Some(PxOrEm::em(-1.0))
+        );
+    }
+
+    #[test]
+    fn length_conversions() {
+        let context = LengthContext {
+            width: 600.0,
+            font_size: 20.0,
+            root_font_size: 16.0,
+        };
+        let px = |length: Length| length.to_px(&context).map(|px| px.value());
+        assert_eq!(px(Length::px(12.5)), Some(12.5));
+        assert_eq!(px(Length::percent(25.0)), Some(150.0));
+        assert_eq!(px(Length::em(1.5)), Some(30.0));
+        assert_eq!(px(Length::rem(2.0)), Some(32.0));
+        assert_eq!(px(Length::unitless(3.0)), Some(3.0));
+
+        // line heights scale the font size instead of the width
+        let line_height = |length: LineHeight| length.to_px(&context).map(|px| px.value());
+        assert_eq!(line_height(LineHeight::percent(150.0)), Some(30.0));
+        assert_eq!(line_height(LineHeight::unitless(1.2)), Some(24.0));
+
+        assert_eq!(
+            PxOrEm::px(4.0).convert::<PxPercentUnits>(),
+            Some(PxOrPercent::px(4.0))
+        );
+        assert_eq!(PxOrEm::em(4.0).convert::<PxUnits>(), None);
+        assert_eq!(PxOrEm::em(0.0).convert(), Some(SizePx::new(0)));
+        assert_eq!(
+            LineHeight::unitless(1.0)
+                .convert::<AnyUnits>()
+                .map(|length| length.unit()),
+            Some(Unit::Unitless)
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Items used by the expansion of [`mjml!`](crate::mjml) and of `include_mjml!`, not meant to be
+//! used directly.
+
+use crate::model::mjml::Mjml;
+use crate::model::schema::AttributeSchema;
+use crate::model::MjBaseComponentAttributes;
+use std::fmt::Display;
+
+pub use crate::model::codegen::{PLACEHOLDER_END, PLACEHOLDER_START};
+
+/// Source of the block expression building `mjml`, for `include_mjml!`. Each placeholder marker of
+/// its strings becomes a `format!` argument taken from `params`.
+pub fn rust_expression(mjml: &Mjml, params: &[String]) -> String {
+    mjml.to_rust_expression(params)
+}
+
+/// Sets an attribute from the displayed form of any value.
+pub trait SetAttribute {
+    /// Panics when the value is not valid for the attribute.
+    fn set_attribute(&mut self, tag: &str, name: &str, value: &dyn Display);
+}
+
+impl<T: MjBaseComponentAttributes> SetAttribute for T {
+    fn set_attribute(&mut self, tag: &str, name: &str, value: &dyn Display) {
+        match self.set(name, &value.to_string()) {
+            Ok(true) => {}
+            Ok(false) => panic!("`{}` has no attribute `{}`", tag, name),
+            Err(err) => panic!("`{}` attribute `{}`: {}", tag, name, err),
+        }
+    }
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn contains(names: &[&str], name: &str) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        if str_eq(names[i], name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+pub const fn has_attribute(attributes: &[AttributeSchema], name: &str) -> bool {
+    let mut i = 0;
+    while i < attributes.len() {
+        if str_eq(attributes[i].name, name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Whether every required attribute is in `names`.
+pub const fn has_required(attributes: &[AttributeSchema], names: &[&str]) -> bool {
+    let mut i = 0;
+    while i < attributes.len() {
+        if attributes[i].required && !contains(names, attributes[i].name) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,388 @@
+//! Reading and writing of MJML markup as an [`Element`] tree.
+//!
+//! The reader is as lenient as the MJML one: the content of ending tags such as `mj-text` is
+//! kept as-is without being parsed, and attribute values are not unescaped, the same way they are
+//! written when rendering.
+
+use crate::model::element::{Element, ElementError};
+use crate::model::mjml::Mjml;
+use crate::model::schema::{self, ContentKind};
+use crate::model::utils::build_indentation;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupError {
+    Syntax { line: usize, reason: String },
+    Element(ElementError),
+}
+
+impl Display for MarkupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax { line, reason } => write!(f, "line {}: {}", line, reason),
+            Self::Element(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for MarkupError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Element(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ElementError> for MarkupError {
+    fn from(err: ElementError) -> Self {
+        Self::Element(err)
+    }
+}
+
+impl Element {
+    /// Reads a document holding a single root tag. Declarations are skipped, and the comments after
+    /// the root tag are kept at the end of its children.
+    pub fn from_markup(source: &str) -> Result<Self, MarkupError> {
+        let mut reader = Reader { source, offset: 0 };
+        let comments = reader.skip_misc()?;
+        if reader.at_end() {
+            return Err(reader.error("no root tag"));
+        }
+        let mut root = reader.element()?;
+        root.comments = comments;
+        let end_comments = reader.skip_misc()?;
+        root.end_comments.extend(end_comments);
+        if !reader.at_end() {
+            return Err(reader.error("unexpected content after the root tag"));
+        }
+        Ok(root)
+    }
+
+    /// Writes the tree as markup, one tag per line. Attributes are sorted by name and the content
+    /// of ending tags is written as it is.
+    pub fn to_markup(&self) -> String {
+        let mut markup = String::new();
+        // writing to a string cannot fail
+        let _ = self.write_markup(&mut markup, 0);
+        markup
+    }
+
+    fn write_markup(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
+        let indentation = build_indentation(depth);
+        for comment in &self.comments {
+            writeln!(writer, "{}<!--{}-->", indentation, comment)?;
+        }
+        write!(writer, "{}<{}", indentation, self.tag_name)?;
+        for (name, value) in &self.attributes {
+            let quote = if value.contains('"') { '\'' } else { '"' };
+            write!(writer, " {}={}{}{}", name, quote, value, quote)?;
+        }
+        let is_empty =
+            self.children.is_empty() && self.content.is_none() && self.end_comments.is_empty();
+        if is_empty {
+            return writeln!(writer, "/>");
+        }
+        write!(writer, ">")?;
+        match &self.content {
+            Some(content) if content.contains('\n') => {
+                write!(writer, "\n{}\n{}", content, indentation)?;
+            }
+            Some(content) => write!(writer, "{}", content)?,
+            None => {
+                writeln!(writer)?;
+                for child in &self.children {
+                    child.write_markup(writer, depth + 1)?;
+                }
+                for comment in &self.end_comments {
+                    writeln!(writer, "{}<!--{}-->", build_indentation(depth + 1), comment)?;
+                }
+                write!(writer, "{}", indentation)?;
+            }
+        }
+        writeln!(writer, "</{}>", self.tag_name)
+    }
+}
+
+impl FromStr for Mjml {
+    type Err = MarkupError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Ok(Mjml::try_from(&Element::from_markup(source)?)?)
+    }
+}
+
+struct Reader<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    fn at_end(&self) -> bool {
+        self.offset == self.source.len()
+    }
+
+    fn error(&self, reason: impl ToString) -> MarkupError {
+        MarkupError::Syntax {
+            line: self.source[..self.offset].matches('\n').count() + 1,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips until after `end`, which must be found.
+    fn skip_past(&mut self, end: &str) -> Result<&'a str, MarkupError> {
+        let rest = self.rest();
+        match rest.find(end) {
+            Some(position) => {
+                self.offset += position + end.len();
+                Ok(&rest[..position])
+            }
+            None => Err(self.error(format!("missing `{}`", end))),
+        }
+    }
+
+    /// Skips whitespace, comments, `<?xml ?>` and `<!DOCTYPE>` declarations, returning the text of
+    /// the comments.
+    fn skip_misc(&mut self) -> Result<Vec<String>, MarkupError> {
+        let mut comments = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("<!--") {
+                self.offset += "<!--".len();
+                comments.push(self.skip_past("-->")?.to_string());
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(comments);
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, MarkupError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.offset += len;
+        Ok(&rest[..len])
+    }
+
+    fn element(&mut self) -> Result<Element, MarkupError> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("expected a tag"));
+        }
+        self.offset += 1;
+        let mut element = Element::new(self.name()?);
+
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.offset += 2;
+                return Ok(element);
+            }
+            if rest.starts_with('>') {
+                self.offset += 1;
+                break;
+            }
+            if rest.is_empty() {
+                return Err(self.error(format!("unclosed tag <{}>", element.tag_name)));
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            let value = if self.rest().starts_with('=') {
+                self.offset += 1;
+                self.skip_whitespace();
+                self.attribute_value()?
+            } else {
+                name
+            };
+            element
+                .attributes
+                .insert(name.to_string(), value.to_string());
+        }
+
+        let close = format!("</{}", element.tag_name);
+        let ending = schema::component(&element.tag_name)
+            .is_some_and(|component| component.content == ContentKind::Html);
+        if ending {
+            let content = self.skip_past(&close)?;
+            element.content = Some(content.trim().to_string());
+        } else {
+            loop {
+                let comments = self.skip_misc()?;
+                if self.rest().starts_with(&close) {
+                    self.offset += close.len();
+                    element.end_comments = comments;
+                    break;
+                }
+                if self.rest().starts_with("</") || self.at_end() {
+                    return Err(self.error(format!("unclosed tag <{}>", element.tag_name)));
+                }
+                if !self.rest().starts_with('<') {
+                    return Err(self.error(format!("unexpected text in <{}>", element.tag_name)));
+                }
+                let mut child = self.element()?;
+                child.comments = comments;
+                element.children.push(child);
+            }
+        }
+        self.skip_whitespace();
+        if !self.rest().starts_with('>') {
+            return Err(self.error(format!("expected `>` to close </{}>", element.tag_name)));
+        }
+        self.offset += 1;
+        Ok(element)
+    }
+
+    fn attribute_value(&mut self) -> Result<&'a str, MarkupError> {
+        let rest = self.rest();
+        match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                self.offset += 1;
+                self.skip_past(&quote.to_string())
+            }
+            _ => {
+                let len = rest
+                    .char_indices()
+                    .find(|&(i, c)| c.is_whitespace() || c == '>' || rest[i..].starts_with("/>"))
+                    .map_or(rest.len(), |(i, _)| i);
+                if len == 0 {
+                    return Err(self.error("expected an attribute value"));
+                }
+                self.offset += len;
+                Ok(&rest[..len])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::element::{Element, ElementError};
+    use crate::model::markup::MarkupError;
+    use crate::model::mjml::Mjml;
+
+    const SAMPLE: &str = r#"<?xml version="1.0"?>
+<!-- welcome email -->
+<mjml lang="en">
+  <mj-head>
+    <mj-title>Welcome</mj-title>
+    <mj-attributes>
+      <mj-all font-family="Arial" />
+    </mj-attributes>
+  </mj-head>
+  <mj-body width=500px>
+    <mj-section full-width>
+      <!-- main column -->
+      <mj-column>
+        <mj-text font-size="20px" css-class='title'>
+          Hello <b>World</b><br>
+        </mj-text>
+        <mj-image src="https://example.com/a.png"/>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>
+"#;
+
+    #[test]
+    fn read_markup() {
+        let element = Element::from_markup(SAMPLE).unwrap();
+        let section = &element.children[1].children[0];
+        assert_eq!(section.attributes["full-width"], "full-width");
+        let text = &section.children[0].children[0];
+        assert_eq!(text.content.as_deref(), Some("Hello <b>World</b><br>"));
+        assert_eq!(text.attributes["css-class"], "title");
+
+        let mjml: Mjml = SAMPLE.parse().unwrap();
+        assert_eq!(mjml.to_string().parse::<Mjml>().unwrap(), mjml);
+    }
+
+    #[test]
+    fn write_markup() {
+        let element = Element::from_markup(SAMPLE).unwrap();
+        let markup = element.to_markup();
+        assert_eq!(Element::from_markup(&markup).unwrap(), element);
+        assert_eq!(Element::from_markup(&markup).unwrap().to_markup(), markup);
+        assert!(markup.starts_with("<!-- welcome email -->\n<mjml lang=\"en\">\n"));
+        assert!(markup.contains("\t\t\t<!-- main column -->\n\t\t\t<mj-column>\n"));
+        assert!(markup.contains(
+            "<mj-text css-class=\"title\" font-size=\"20px\">Hello <b>World</b><br></mj-text>\n"
+        ));
+        assert!(markup.contains("<mj-section full-width=\"full-width\">"));
+
+        let element = Element::from_markup(
+            "<mjml><mj-body a='\"'><!-- end --></mj-body></mjml><!-- after -->",
+        )
+        .unwrap();
+        assert_eq!(element.end_comments, [" after "]);
+        assert_eq!(
+            element.to_markup(),
+            "<mjml>\n\t<mj-body a='\"'>\n\t\t<!-- end -->\n\t</mj-body>\n\t<!-- after -->\n</mjml>\n"
+        );
+    }
+
+    #[test]
+    fn markup_errors() {
+        assert_eq!(
+            Element::from_markup("<mjml>\n  <mj-body>\n</mjml>"),
+            Err(MarkupError::Syntax {
+                line: 3,
+                reason: "unclosed tag <mj-body>".to_string()
+            })
+        );
+        assert!(matches!(
+            Element::from_markup("<mjml><mj-body>text</mj-body></mjml>"),
+            Err(MarkupError::Syntax { line: 1, .. })
+        ));
+        assert_eq!(
+            "<mjml><mj-body><mj-text/></mj-body></mjml>".parse::<Mjml>(),
+            Err(MarkupError::Element(ElementError::UnexpectedTag {
+                parent: "mj-body".to_string(),
+                tag: "mj-text".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn root_raw() {
+        let document = |raws: &str| format!("<mjml>{}<mj-body></mj-body></mjml>", raws);
+        let mjml: Mjml = document(r#"<mj-raw position="file-start">{% if a %}</mj-raw>"#)
+            .parse()
+            .unwrap();
+        assert_eq!(mjml.mj_raw_file_start.unwrap().content, "{% if a %}");
+
+        assert!(matches!(
+            document("<mj-raw><p>a</p></mj-raw>").parse::<Mjml>(),
+            Err(MarkupError::Element(ElementError::MissingAttribute { .. }))
+        ));
+        assert!(matches!(
+            document(r#"<mj-raw position="end"><p>a</p></mj-raw>"#).parse::<Mjml>(),
+            Err(MarkupError::Element(ElementError::InvalidAttribute { .. }))
+        ));
+        assert_eq!(
+            document(r#"<mj-raw position="file-start">a</mj-raw><mj-raw position="file-start">b</mj-raw>"#)
+                .parse::<Mjml>(),
+            Err(MarkupError::Element(ElementError::UnexpectedTag {
+                parent: "mjml".to_string(),
+                tag: "mj-raw".to_string()
+            }))
+        );
+    }
+}
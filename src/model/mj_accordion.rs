@@ -1,17 +1,21 @@
 use crate::model::{
-    render_css_class, render_font_family, render_generic_attribute, Alignment, BaseComponent,
-    BorderProperties, Color, CssClass, Html, MjBaseComponentAttributes, MjBaseComponentSimplified,
-    MjBodyComponent, Padding, PxOrEm, SizePx, Url,
+    impl_attribute_value, impl_from_str, parse_font_weight, schema, Alignment, BorderProperties,
+    Color, CssClass, Html, LineHeight, MjBaseComponent, MjBaseComponentAttributes,
+    MjBaseComponentSimplified, Padding, PxOrEm, SizePx, Url,
 };
-use crate::model::{render_padding_opt, MjBaseComponent};
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 use std::fmt::{Display, Formatter};
-use std::io::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjAccordion {
     pub elements: Vec<MjAccordionElement>,
     pub attributes: MjAccordionAttributes,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjAccordionElement {
     pub title: MjAccordionElementTitle,
     pub text: MjAccordionElementText,
@@ -19,38 +23,59 @@ pub struct MjAccordionElement {
     pub attributes: MjAccordionElementAttributes,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjAccordionElementTitle {
     pub content: Html,
 
     pub attributes: MjAccordionElementTitleAttributes,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjAccordionElementText {
     pub content: Html,
 
     pub attributes: MjAccordionElementTextAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjAccordionElementTitleAttributes {
     pub background_color: Option<Color>,
     pub color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(list = "font-family")]
     pub font_family: Vec<String>,
+    #[mj(default = "13px")]
     pub font_size: Option<SizePx>,
+    #[mj(default = "16px")]
     pub padding: Option<Padding>,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjAccordionElementTextAttributes {
     pub background_color: Option<Color>,
     pub color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(list = "font-family")]
     pub font_family: Vec<String>,
+    #[mj(default = "13px")]
     pub font_size: Option<SizePx>,
+    #[mj(parse = "parse_font_weight")]
     pub font_weight: Option<u32>,
     pub letter_spacing: Option<PxOrEm>,
-    pub line_height: Option<SizePx>,
+    pub line_height: Option<LineHeight>,
+    #[mj(default = "16px")]
     pub padding: Option<Padding>,
 }
 
@@ -67,12 +92,20 @@ impl Display for IconPosition {
         }
     }
 }
+impl_from_str!(IconPosition, "icon position", "left" => Left, "right" => Right);
+impl_attribute_value!(IconPosition => schema::ICON_POSITION);
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjAccordionElementAttributes {
     pub background_color: Option<Color>,
     pub border: Option<BorderProperties>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(list = "font-family")]
     pub font_family: Vec<String>,
     pub icon_align: Option<Alignment>,
     pub icon_height: Option<SizePx>,
@@ -84,34 +117,43 @@ pub struct MjAccordionElementAttributes {
     pub icon_wrapped_url: Option<Url>,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjAccordionAttributes {
+    #[mj(default = "2px solid black")]
     pub border: Option<BorderProperties>,
     pub container_background_color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "Ubuntu, Helvetica, Arial, sans-serif", list = "font-family")]
     pub font_family: Vec<String>,
     pub icon_align: Option<Alignment>,
+    #[mj(default = "32px")]
     pub icon_height: Option<SizePx>,
+    #[mj(default = "right")]
     pub icon_position: Option<IconPosition>,
+    #[mj(default = "-")]
     pub icon_unwrapped_alt: Option<String>,
+    #[mj(default = "https://i.imgur.com/w4uTygT.png")]
     pub icon_unwrapped_url: Option<Url>,
+    #[mj(default = "32px")]
     pub icon_width: Option<SizePx>,
+    #[mj(default = "+")]
     pub icon_wrapped_alt: Option<String>,
+    #[mj(default = "https://i.imgur.com/bIXv1bk.png")]
     pub icon_wrapped_url: Option<Url>,
 
+    #[mj(default = "10px 25px")]
     pub padding: Option<Padding>,
 }
 
-impl MjBodyComponent for MjAccordion {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
-
 impl MjBaseComponentSimplified for MjAccordion {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for element in &self.elements {
-            Box::new(element).render(writer, depth + 1)?;
+            element.render(writer, depth + 1)?;
         }
         Ok(())
     }
@@ -129,31 +171,8 @@ impl MjBaseComponentSimplified for MjAccordion {
     }
 }
 
-impl MjBaseComponentAttributes for MjAccordionAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "border", &self.border)?;
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "icon-align", &self.icon_align)?;
-        render_generic_attribute(writer, "icon-height", &self.icon_height)?;
-        render_generic_attribute(writer, "icon-position", &self.icon_position)?;
-        render_generic_attribute(writer, "icon-unwrapped-alt", &self.icon_unwrapped_alt)?;
-        render_generic_attribute(writer, "icon-unwrapped-url", &self.icon_unwrapped_url)?;
-        render_generic_attribute(writer, "icon-width", &self.icon_width)?;
-        render_generic_attribute(writer, "icon-wrapped-alt", &self.icon_wrapped_alt)?;
-        render_generic_attribute(writer, "icon-wrapped-url", &self.icon_wrapped_url)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        Ok(())
-    }
-}
-
 impl MjBaseComponentSimplified for MjAccordionElement {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         self.title.render(writer, depth)?;
         self.text.render(writer, depth)?;
         Ok(())
@@ -172,26 +191,8 @@ impl MjBaseComponentSimplified for MjAccordionElement {
     }
 }
 
-impl MjBaseComponentAttributes for MjAccordionElementAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "border", &self.border)?;
-        render_generic_attribute(writer, "background-color", &self.background_color)?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "icon-align", &self.icon_align)?;
-        render_generic_attribute(writer, "icon-height", &self.icon_height)?;
-        render_generic_attribute(writer, "icon-position", &self.icon_position)?;
-        render_generic_attribute(writer, "icon-unwrapped-alt", &self.icon_unwrapped_alt)?;
-        render_generic_attribute(writer, "icon-unwrapped-url", &self.icon_unwrapped_url)?;
-        render_generic_attribute(writer, "icon-width", &self.icon_width)?;
-        render_generic_attribute(writer, "icon-wrapped-alt", &self.icon_wrapped_alt)?;
-        render_generic_attribute(writer, "icon-wrapped-url", &self.icon_wrapped_url)?;
-        Ok(())
-    }
-}
-
 impl MjBaseComponentSimplified for MjAccordionElementTitle {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -208,20 +209,8 @@ impl MjBaseComponentSimplified for MjAccordionElementTitle {
     }
 }
 
-impl MjBaseComponentAttributes for MjAccordionElementTitleAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "background-color", &self.background_color)?;
-        render_generic_attribute(writer, "color", &self.color)?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "font-size", &self.font_size)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        Ok(())
-    }
-}
-
 impl MjBaseComponentSimplified for MjAccordionElementText {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -237,18 +226,3 @@ impl MjBaseComponentSimplified for MjAccordionElementText {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjAccordionElementTextAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "background-color", &self.background_color)?;
-        render_generic_attribute(writer, "color", &self.color)?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "font-size", &self.font_size)?;
-        render_generic_attribute(writer, "font-weight", &self.font_weight)?;
-        render_generic_attribute(writer, "letter-spacing", &self.letter_spacing)?;
-        render_generic_attribute(writer, "line-height", &self.line_height)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        Ok(())
-    }
-}
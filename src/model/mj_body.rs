@@ -1,18 +1,28 @@
 use crate::model::{
-    render_css_class, render_generic_attribute, Color, CssClass, MjBaseComponent,
-    MjBaseComponentAttributes, MjBaseComponentSimplified, MjBodySectionComponent, SizePx,
+    BodyNode, Color, CssClass, MjBaseComponent, MjBaseComponentAttributes,
+    MjBaseComponentSimplified, SizePx,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjBody {
-    pub content: Vec<Box<dyn MjBodySectionComponent>>,
+    pub content: Vec<BodyNode>,
     pub attributes: MjBodyAttributes,
 }
 
+#[derive(Debug, Clone, PartialEq, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjBodyAttributes {
     pub background_color: Option<Color>,
-    pub width: SizePx,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "600px")]
+    pub width: SizePx,
 }
 
 impl Default for MjBodyAttributes {
@@ -21,15 +31,25 @@ impl Default for MjBodyAttributes {
             background_color: None,
             width: SizePx::new(600),
             css_class: vec![],
+            mj_class: vec![],
         }
     }
 }
 
+impl MjBody {
+    pub(crate) fn render_section(
+        section: &BodyNode,
+        writer: &mut dyn Write,
+        depth: usize,
+    ) -> std::fmt::Result {
+        section.render(writer, depth + 1)
+    }
+}
+
 impl MjBaseComponentSimplified for MjBody {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for section in &self.content {
-            let section = section.to_section();
-            (Box::new(section)).render(writer, depth + 1)?;
+            Self::render_section(section, writer, depth)?;
         }
         Ok(())
     }
@@ -46,13 +66,3 @@ impl MjBaseComponentSimplified for MjBody {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjBodyAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "background-color", &self.background_color)?;
-        render_css_class(writer, &self.css_class)?;
-        write!(writer, " width=\"{}\"", self.width)?;
-
-        Ok(())
-    }
-}
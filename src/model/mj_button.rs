@@ -1,57 +1,91 @@
 use crate::model::{
-    render_css_class, render_font_family, render_generic_attribute, render_padding_opt, Alignment,
-    BaseComponent, BorderProperties, Color, CssClass, FontStyle, Html, MjBaseComponentAttributes,
-    MjBaseComponentSimplified, MjBodyComponent, Padding, PxOrEm, SizePx, TextDecoration,
-    TextTransform, Url, VerticalAlignment,
+    parse_font_weight, Alignment, BorderProperties, Color, CssClass, DeviceVisibility, FontStyle,
+    Html, LineHeight, MjBaseComponentAttributes, MjBaseComponentSimplified, Padding, PxOrEm,
+    PxOrPercent, SizePx, TextDecoration, TextTransform, Url, VerticalAlignment,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjButton {
     pub content: Html,
     pub attributes: MjButtonAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjButtonAttributes {
+    #[mj(default = "center")]
     pub align: Option<Alignment>,
+    #[mj(default = "#414141")]
     pub background_color: Option<Color>,
+    #[mj(default = "none")]
     pub border: Option<BorderProperties>,
     pub border_bottom: Option<BorderProperties>,
     pub border_left: Option<BorderProperties>,
+    #[mj(default = "3px")]
     pub border_radius: Option<SizePx>,
     pub border_right: Option<BorderProperties>,
     pub border_top: Option<BorderProperties>,
+    #[mj(default = "#ffffff")]
     pub color: Option<Color>,
     pub container_background_color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "Ubuntu, Helvetica, Arial, sans-serif", list = "font-family")]
     pub font_family: Vec<String>,
+    #[mj(default = "13px")]
     pub font_size: Option<SizePx>,
     pub font_style: Option<FontStyle>,
+    #[mj(parse = "parse_font_weight")]
     pub font_weight: Option<u32>,
     pub height: Option<SizePx>,
     pub href: Option<Url>,
+    #[mj(default = "10px 25px")]
     pub inner_padding: Option<Padding>,
     pub letter_spacing: Option<PxOrEm>,
-    pub line_height: Option<String>,
+    #[mj(default = "120%")]
+    pub line_height: Option<LineHeight>,
+    #[mj(default = "10px 25px")]
     pub padding: Option<Padding>,
     pub rel: Option<String>,
+    #[mj(default = "_blank")]
     pub target: Option<String>,
+    #[mj(default = "none")]
     pub text_align: Option<String>,
+    #[mj(default = "none")]
     pub text_decoration: Option<TextDecoration>,
+    #[mj(default = "none")]
     pub text_transform: Option<TextTransform>,
     pub title: Option<String>,
     pub vertical_align: Option<VerticalAlignment>,
     pub width: Option<SizePx>,
+    /// Shown on every device when unset.
+    #[mj(skip)]
+    pub visibility: Option<DeviceVisibility>,
+    /// Applied below the breakpoint, through a generated css class.
+    #[mj(skip)]
+    pub mobile: Option<MjButtonMobile>,
 }
 
-impl MjBodyComponent for MjButton {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
+/// Values replacing the attributes of the same name on mobile.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MjButtonMobile {
+    pub align: Option<Alignment>,
+    pub font_size: Option<SizePx>,
+    pub padding: Option<Padding>,
+    /// Such as `100%` for a full width button.
+    pub width: Option<PxOrPercent>,
 }
 
 impl MjBaseComponentSimplified for MjButton {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -67,42 +101,3 @@ impl MjBaseComponentSimplified for MjButton {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjButtonAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "align", &self.align)?;
-        render_generic_attribute(writer, "background-color", &self.background_color)?;
-        render_generic_attribute(writer, "border", &self.border)?;
-        render_generic_attribute(writer, "border-bottom", &self.border_bottom)?;
-        render_generic_attribute(writer, "border-left", &self.border_left)?;
-        render_generic_attribute(writer, "border-right", &self.border_right)?;
-        render_generic_attribute(writer, "border-top", &self.border_top)?;
-        render_generic_attribute(writer, "border-radius", &self.border_radius)?;
-        render_generic_attribute(writer, "color", &self.color)?;
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "font-size", &self.font_size)?;
-        render_generic_attribute(writer, "font-style", &self.font_style)?;
-        render_generic_attribute(writer, "font-weight", &self.font_weight)?;
-        render_generic_attribute(writer, "height", &self.height)?;
-        render_generic_attribute(writer, "href", &self.href)?;
-        render_padding_opt(writer, "inner-padding", &self.inner_padding)?;
-        render_generic_attribute(writer, "letter-spacing", &self.letter_spacing)?;
-        render_generic_attribute(writer, "line-height", &self.line_height)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_generic_attribute(writer, "rel", &self.rel)?;
-        render_generic_attribute(writer, "target", &self.target)?;
-        render_generic_attribute(writer, "text-align", &self.text_align)?;
-        render_generic_attribute(writer, "text-decoration", &self.text_decoration)?;
-        render_generic_attribute(writer, "text-transform", &self.text_transform)?;
-        render_generic_attribute(writer, "title", &self.title)?;
-        render_generic_attribute(writer, "vertical-align", &self.vertical_align)?;
-        render_generic_attribute(writer, "width", &self.width)?;
-        Ok(())
-    }
-}
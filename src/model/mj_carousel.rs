@@ -1,16 +1,20 @@
 use crate::model::{
-    render_css_class, render_generic_attribute, Alignment, BaseComponent, BorderProperties, Color,
-    CssClass, Html, MjBaseComponent, MjBaseComponentAttributes, MjBaseComponentSimplified,
-    MjBodyComponent, SizePx, Url,
+    impl_attribute_value, impl_from_str, schema, Alignment, BorderProperties, Color, CssClass,
+    Html, MjBaseComponent, MjBaseComponentAttributes, MjBaseComponentSimplified, SizePx, Url,
 };
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 use std::fmt::{Display, Formatter};
-use std::io::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjCarousel {
     pub images: Vec<MjCarouselImage>,
     pub attributes: MjCarouselAttributes,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjCarouselImage {
     pub content: Html,
     pub attributes: MjCarouselImageAttributes,
@@ -29,43 +33,61 @@ impl Display for Visibility {
         }
     }
 }
+impl_from_str!(Visibility, "visibility", "visible" => Visible, "hidden" => Hidden);
+impl_attribute_value!(Visibility => schema::VISIBILITY);
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjCarouselAttributes {
+    #[mj(default = "center")]
     pub align: Option<Alignment>,
     pub container_background_color: Option<Color>,
+    #[mj(default = "6px")]
     pub border_radius: Option<SizePx>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "44px")]
     pub icon_width: Option<SizePx>,
+    #[mj(default = "https://i.imgur.com/xTh3hln.png")]
     pub left_icon: Option<Url>,
+    #[mj(default = "https://i.imgur.com/os7o9kz.png")]
     pub right_icon: Option<Url>,
+    #[mj(default = "2px solid transparent")]
     pub tb_border: Option<BorderProperties>,
+    #[mj(default = "6px")]
     pub tb_border_radius: Option<SizePx>,
+    #[mj(default = "#fead0d")]
     pub tb_border_hover_color: Option<Color>,
+    #[mj(default = "#cccccc")]
     pub tb_selected_border_color: Option<Color>,
     pub tb_width: Option<SizePx>,
+    #[mj(default = "visible")]
     pub thumbnails: Option<Visibility>,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjCarouselImageAttributes {
     pub alt: Option<String>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
     pub href: Option<Url>,
     pub rel: Option<String>,
     pub src: Option<Url>,
+    #[mj(default = "_blank")]
     pub target: Option<String>,
-    pub thumbnail_src: Option<String>,
+    pub thumbnail_src: Option<Url>,
     pub title: Option<String>,
 }
 
-impl MjBodyComponent for MjCarousel {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
 impl MjBaseComponentSimplified for MjCarouselImage {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -82,9 +104,9 @@ impl MjBaseComponentSimplified for MjCarouselImage {
     }
 }
 impl MjBaseComponentSimplified for MjCarousel {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for image in &self.images {
-            Box::new(image).render(writer, depth)?;
+            image.render(writer, depth)?;
         }
         Ok(())
     }
@@ -101,43 +123,3 @@ impl MjBaseComponentSimplified for MjCarousel {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjCarouselAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "align", &self.align)?;
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_generic_attribute(writer, "border-radius", &self.border_radius)?;
-        render_css_class(writer, &self.css_class)?;
-        render_generic_attribute(writer, "icon-width", &self.icon_width)?;
-        render_generic_attribute(writer, "left-icon", &self.left_icon)?;
-        render_generic_attribute(writer, "right-icon", &self.right_icon)?;
-        render_generic_attribute(writer, "tb-border", &self.tb_border)?;
-        render_generic_attribute(writer, "tb-border-radius", &self.tb_border_radius)?;
-        render_generic_attribute(writer, "tb-border-hover-color", &self.tb_border_hover_color)?;
-        render_generic_attribute(
-            writer,
-            "tb-selected-border-color",
-            &self.tb_selected_border_color,
-        )?;
-        render_generic_attribute(writer, "tb-width", &self.tb_width)?;
-        render_generic_attribute(writer, "thumbnails", &self.thumbnails)?;
-        Ok(())
-    }
-}
-impl MjBaseComponentAttributes for MjCarouselImageAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "alt", &self.alt)?;
-        render_css_class(writer, &self.css_class)?;
-        render_generic_attribute(writer, "href", &self.href)?;
-        render_generic_attribute(writer, "rel", &self.rel)?;
-        render_generic_attribute(writer, "src", &self.src)?;
-        render_generic_attribute(writer, "target", &self.target)?;
-        render_generic_attribute(writer, "thumbnail-src", &self.thumbnail_src)?;
-        render_generic_attribute(writer, "title", &self.title)?;
-        Ok(())
-    }
-}
@@ -1,17 +1,22 @@
 use crate::model::{
-    render_css_class, render_generic_attribute, render_padding, BorderProperties, Color,
-    ColumnCompatible, CssClass, MjBaseComponentAttributes, MjBaseComponentSimplified,
-    MjBodyColumnComponent, MjBodyComponent, Padding, PxOrPercent, VerticalAlignment,
+    BorderProperties, Color, ColumnNode, CssClass, DeviceVisibility, MjBaseComponent,
+    MjBaseComponentAttributes, MjBaseComponentSimplified, Padding, PaddingValue, PxOrPercent,
+    SizePx, VerticalAlignment,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjColumn {
     // Cannot contain MjColumn or MjSection
-    pub content: Vec<Box<dyn MjBodyComponent>>,
+    pub content: Vec<ColumnNode>,
     pub attributes: MjColumnAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjColumnAttributes {
     pub background_color: Option<Color>,
     pub inner_background_color: Option<Color>,
@@ -23,11 +28,22 @@ pub struct MjColumnAttributes {
     pub border_radius: Option<PxOrPercent>,
 
     pub width: Option<PxOrPercent>, // only percent allowed in group
+    #[mj(default = "top")]
     pub vertical_align: Option<VerticalAlignment>,
+    #[mj(flatten)]
     pub padding: Option<MjColumnPaddingAttributes>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    /// Shown on every device when unset.
+    #[mj(skip)]
+    pub visibility: Option<DeviceVisibility>,
 }
 
+#[derive(Debug, Clone, PartialEq, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjColumnPaddingAttributes {
     pub inner_border: Option<BorderProperties>,
     pub inner_border_bottom: Option<BorderProperties>,
@@ -39,18 +55,30 @@ pub struct MjColumnPaddingAttributes {
     pub padding: Padding,
 }
 
-impl MjBodyColumnComponent for MjColumn {
-    fn to_column(&self) -> ColumnCompatible {
-        ColumnCompatible::Column(self)
+impl Default for MjColumnPaddingAttributes {
+    fn default() -> Self {
+        let zero = PaddingValue::Px(SizePx::new(0));
+        Self {
+            inner_border: None,
+            inner_border_bottom: None,
+            inner_border_left: None,
+            inner_border_right: None,
+            inner_border_top: None,
+            inner_border_radius: None,
+            padding: Padding {
+                top: zero,
+                right: zero,
+                bottom: zero,
+                left: zero,
+            },
+        }
     }
 }
+
 impl MjBaseComponentSimplified for MjColumn {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for component in &self.content {
-            component
-                .to_base_component()
-                .inner
-                .render(writer, depth + 1)?
+            component.render(writer, depth + 1)?
         }
         Ok(())
     }
@@ -67,40 +95,3 @@ impl MjBaseComponentSimplified for MjColumn {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjColumnAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(
-            writer,
-            "inner-background-color",
-            &self.inner_background_color,
-        )?;
-        render_generic_attribute(writer, "border", &self.border)?;
-        render_generic_attribute(writer, "border-bottom", &self.border_bottom)?;
-        render_generic_attribute(writer, "border-left", &self.border_left)?;
-        render_generic_attribute(writer, "border-right", &self.border_right)?;
-        render_generic_attribute(writer, "border-top", &self.border_top)?;
-        render_generic_attribute(writer, "border-radius", &self.border_radius)?;
-        render_generic_attribute(writer, "width", &self.width)?;
-        render_generic_attribute(writer, "vertical-align", &self.vertical_align)?;
-        if let Some(padding) = &self.padding {
-            Box::new(padding).render(writer)?;
-        }
-        render_css_class(writer, &self.css_class)?;
-        Ok(())
-    }
-}
-
-impl MjBaseComponentAttributes for MjColumnPaddingAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "inner-border", &self.inner_border)?;
-        render_generic_attribute(writer, "inner-border-bottom", &self.inner_border_bottom)?;
-        render_generic_attribute(writer, "inner-border-left", &self.inner_border_left)?;
-        render_generic_attribute(writer, "inner-border-right", &self.inner_border_right)?;
-        render_generic_attribute(writer, "inner-border-top", &self.inner_border_top)?;
-        render_generic_attribute(writer, "inner-border-radius", &self.inner_border_radius)?;
-        render_padding(writer, "padding", &self.padding)?;
-
-        Ok(())
-    }
-}
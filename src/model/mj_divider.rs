@@ -1,33 +1,41 @@
 use crate::model::{
-    render_css_class, render_generic_attribute, render_padding_opt, Alignment, BaseComponent,
-    BorderStyle, Color, CssClass, MjBaseComponentAttributes, MjBaseComponentSimplified,
-    MjBodyComponent, Padding, PxOrPercent, SizePx,
+    Alignment, BorderStyle, Color, CssClass, MjBaseComponentAttributes, MjBaseComponentSimplified,
+    Padding, PxOrPercent, SizePx,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjDivider {
     pub attributes: MjDividerAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjDividerAttributes {
+    #[mj(default = "#000000")]
     pub border_color: Option<Color>,
+    #[mj(default = "solid")]
     pub border_style: Option<BorderStyle>,
+    #[mj(default = "4px")]
     pub border_width: Option<SizePx>,
     pub container_background_color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "10px 25px")]
     pub padding: Option<Padding>,
+    #[mj(default = "100%")]
     pub width: Option<PxOrPercent>,
+    #[mj(default = "center")]
     pub align: Option<Alignment>,
 }
 
-impl MjBodyComponent for MjDivider {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
 impl MjBaseComponentSimplified for MjDivider {
-    fn render_content(&self, _writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, _writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         Ok(())
     }
 
@@ -43,21 +51,3 @@ impl MjBaseComponentSimplified for MjDivider {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjDividerAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "border-color", &self.border_color)?;
-        render_generic_attribute(writer, "border-style", &self.border_style)?;
-        render_generic_attribute(writer, "border-width", &self.border_width)?;
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_css_class(writer, &self.css_class)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_generic_attribute(writer, "width", &self.width)?;
-        render_generic_attribute(writer, "align", &self.align)?;
-        Ok(())
-    }
-}
@@ -1,34 +1,39 @@
+use crate::model::mj_column::MjColumn;
 use crate::model::{
-    render_css_class, render_generic_attribute, Color, ColumnCompatible, CssClass, Direction,
-    MjBaseComponent, MjBaseComponentAttributes, MjBaseComponentSimplified, MjBodyColumnComponent,
-    PxOrPercent, VerticalAlignment,
+    Color, CssClass, Direction, MjBaseComponent, MjBaseComponentAttributes,
+    MjBaseComponentSimplified, PxOrPercent, VerticalAlignment,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjGroup {
     // columns with a width in percent
-    pub columns: Vec<Box<dyn MjBodyColumnComponent>>,
+    pub columns: Vec<MjColumn>,
     pub attributes: MjGroupAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjGroupAttributes {
+    #[mj(default = "100%")]
     pub width: Option<PxOrPercent>,
     pub vertical_align: Option<VerticalAlignment>,
     pub background_color: Option<Color>,
+    #[mj(default = "ltr")]
     pub direction: Option<Direction>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
 }
 
-impl MjBodyColumnComponent for MjGroup {
-    fn to_column(&self) -> ColumnCompatible {
-        ColumnCompatible::Group(self)
-    }
-}
 impl MjBaseComponentSimplified for MjGroup {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for column in &self.columns {
-            Box::new(column.to_column()).render(writer, depth + 1)?
+            column.render(writer, depth + 1)?
         }
 
         Ok(())
@@ -46,14 +51,3 @@ impl MjBaseComponentSimplified for MjGroup {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjGroupAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "width", &self.width)?;
-        render_generic_attribute(writer, "vertical-align", &self.vertical_align)?;
-        render_generic_attribute(writer, "background-color", &self.background_color)?;
-        render_generic_attribute(writer, "direction", &self.direction)?;
-        render_css_class(writer, &self.css_class)?;
-        Ok(())
-    }
-}
@@ -17,34 +17,46 @@ use crate::model::mj_table::MjTableAttributes;
 use crate::model::mj_text::MjTextAttributes;
 use crate::model::mj_wrapper::MjWrapperAttributes;
 use crate::model::{
-    build_indentation, Css, MjBaseComponent, MjBaseComponentAttributes, MjBaseComponentSimplified,
-    SizePx, Url,
+    build_indentation, AttributeWriter, Css, MarkupAttributes, MjBaseComponent,
+    MjBaseComponentAttributes, MjBaseComponentSimplified, SizePx, Url,
 };
-use std::collections::HashMap;
-use std::io::Write;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
 
+/// Width below which MJML stacks the columns, when there is no `mj-breakpoint`.
+pub const DEFAULT_BREAKPOINT: SizePx = SizePx::new(480);
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjHead {
     pub mj_attributes: Option<MjAttributes>,
     pub mj_breakpoint: Option<SizePx>,
     pub mj_font: Vec<MjFont>,
     pub mj_html_attributes: Vec<MjSelector>,
     pub mj_preview: Option<String>,
-    pub mj_style: Option<MjStyle>,
+    pub mj_style: Vec<MjStyle>,
     pub mj_title: Option<String>,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjAttributes {
     pub content: Vec<MjAttribute>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjClass {
     pub name: String,
-    pub attributes: HashMap<String, String>,
+    pub attributes: BTreeMap<String, String>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum MjAttribute {
-    MjAll(HashMap<String, String>),
+    MjAll(BTreeMap<String, String>),
     MjClass(MjClass),
     MjAccordion(MjAccordionAttributes),
     MjAccordionElement(MjAccordionElementAttributes),
@@ -56,8 +68,8 @@ pub enum MjAttribute {
     MjColumn(MjColumnAttributes),
     MjDivider(MjDividerAttributes),
     MjGroup(MjGroupAttributes),
-    MjHero(MjHeroAttributes),
-    MjImage(MjImageAttributes),
+    MjHero(PartialAttributes<MjHeroAttributes>),
+    MjImage(PartialAttributes<MjImageAttributes>),
     MjNavbar(MjNavbarAttributes),
     MjNavbarLink(MjNavbarLinkAttributes),
     MjSection(MjSectionAttributes),
@@ -69,28 +81,45 @@ pub enum MjAttribute {
     MjWrapper(MjWrapperAttributes),
 }
 
+/// `mj-attributes` entry of a component with required attributes, which are optional there.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartialAttributes<A> {
+    pub attributes: A,
+    /// Required attributes that are not set, their value in `attributes` is not written.
+    pub unset: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjFont {
-    name: String,
-    href: Url,
+    pub name: String,
+    pub href: Url,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjSelector {
     pub path: String,
     pub mj_html_attributes: Vec<MjHtmlAttribute>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjHtmlAttribute {
     pub name: String,
     pub value: String,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjStyle {
     pub inline: bool,
     pub content: Css,
 }
 
 impl MjBaseComponentSimplified for MjHead {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         let indentation = build_indentation(depth);
         if let Some(mj_attributes) = &self.mj_attributes {
             mj_attributes.render(writer, depth)?;
@@ -121,7 +150,7 @@ impl MjBaseComponentSimplified for MjHead {
                 indentation, preview
             )?;
         }
-        if let Some(mj_style) = &self.mj_style {
+        for mj_style in &self.mj_style {
             mj_style.render(writer, depth)?;
         }
         if let Some(title) = &self.mj_title {
@@ -145,7 +174,7 @@ impl MjBaseComponentSimplified for MjHead {
 }
 
 impl MjBaseComponentSimplified for MjAttributes {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for attribute in &self.content {
             attribute.render(writer, depth)?;
         }
@@ -165,11 +194,11 @@ impl MjBaseComponentSimplified for MjAttributes {
     }
 }
 
-impl MjBaseComponent for MjAttribute {
-    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
-        let (name, attr): (&str, &dyn MjBaseComponentAttributes) = match self {
-            MjAttribute::MjAll(attributes) => return render_mj_all(writer, depth, attributes),
-            MjAttribute::MjClass(class) => return render_mj_class(writer, depth, class),
+impl MjAttribute {
+    /// Tag and default attributes of a component, `None` for `mj-all` and `mj-class`.
+    pub(super) fn component(&self) -> Option<(&'static str, &dyn MjBaseComponentAttributes)> {
+        Some(match self {
+            MjAttribute::MjAll(_) | MjAttribute::MjClass(_) => return None,
             MjAttribute::MjAccordion(attr) => ("mj-accordion", attr),
             MjAttribute::MjAccordionElement(attr) => ("mj-accordion-element", attr),
             MjAttribute::MjAccordionElementTitle(attr) => ("mj-accordion-title", attr),
@@ -180,8 +209,8 @@ impl MjBaseComponent for MjAttribute {
             MjAttribute::MjColumn(attr) => ("mj-column", attr),
             MjAttribute::MjDivider(attr) => ("mj-divider", attr),
             MjAttribute::MjGroup(attr) => ("mj-group", attr),
-            MjAttribute::MjHero(attr) => ("mj-hero", attr),
-            MjAttribute::MjImage(attr) => ("mj-image", attr),
+            MjAttribute::MjHero(attr) => ("mj-hero", &attr.attributes),
+            MjAttribute::MjImage(attr) => ("mj-image", &attr.attributes),
             MjAttribute::MjNavbar(attr) => ("mj-navbar", attr),
             MjAttribute::MjNavbarLink(attr) => ("mj-navbar-link", attr),
             MjAttribute::MjSection(attr) => ("mj-section", attr),
@@ -191,38 +220,76 @@ impl MjBaseComponent for MjAttribute {
             MjAttribute::MjTable(attr) => ("mj-table", attr),
             MjAttribute::MjText(attr) => ("mj-text", attr),
             MjAttribute::MjWrapper(attr) => ("mj-wrapper", attr),
+        })
+    }
+
+    /// Writes the default attributes of a component, without its required attributes left unset.
+    pub(super) fn render_defaults(&self, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        let Some((_, attributes)) = self.component() else {
+            return Ok(());
+        };
+        let unset = match self {
+            MjAttribute::MjHero(hero) => hero.unset.as_slice(),
+            MjAttribute::MjImage(image) => image.unset.as_slice(),
+            _ => &[],
+        };
+        attributes.render(&mut Defaults { writer, unset })
+    }
+}
+
+impl MjBaseComponent for MjAttribute {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
+        let (name, _) = match self {
+            MjAttribute::MjAll(attributes) => return render_mj_all(writer, depth, attributes),
+            MjAttribute::MjClass(class) => return render_mj_class(writer, depth, class),
+            _ => self.component().expect("a component"),
         };
 
         let indentation = build_indentation(depth);
         write!(writer, "{}<{}", indentation, name)?;
-        attr.render(writer)?;
-        write!(writer, "/>")
+        self.render_defaults(&mut MarkupAttributes(writer))?;
+        writeln!(writer, "/>")
+    }
+}
+
+/// Skips the required attributes left unset.
+struct Defaults<'a> {
+    writer: &'a mut dyn AttributeWriter,
+    unset: &'a [String],
+}
+
+impl<'a> AttributeWriter for Defaults<'a> {
+    fn attribute(&mut self, name: &str, value: &dyn Display) -> std::fmt::Result {
+        if self.unset.iter().any(|unset| unset == name) {
+            return Ok(());
+        }
+        self.writer.attribute(name, value)
     }
 }
 
 fn render_mj_all(
     writer: &mut dyn Write,
     depth: usize,
-    attributes: &HashMap<String, String>,
-) -> std::io::Result<()> {
+    attributes: &BTreeMap<String, String>,
+) -> std::fmt::Result {
     let indentation = build_indentation(depth);
     write!(writer, "{}<mj-all", indentation)?;
     for (key, value) in attributes {
         write!(writer, " {}=\"{}\"", key, value)?;
     }
-    write!(writer, "/>")
+    writeln!(writer, "/>")
 }
-fn render_mj_class(writer: &mut dyn Write, depth: usize, class: &MjClass) -> std::io::Result<()> {
+fn render_mj_class(writer: &mut dyn Write, depth: usize, class: &MjClass) -> std::fmt::Result {
     let indentation = build_indentation(depth);
-    write!(writer, "{}<mj-all name=\"{}\"", indentation, class.name)?;
+    write!(writer, "{}<mj-class name=\"{}\"", indentation, class.name)?;
     for (key, value) in &class.attributes {
         write!(writer, " {}=\"{}\"", key, value)?;
     }
-    write!(writer, "/>")
+    writeln!(writer, "/>")
 }
 
 impl MjBaseComponent for MjSelector {
-    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         if self.mj_html_attributes.is_empty() {
             return Ok(());
         }
@@ -246,7 +313,7 @@ impl MjBaseComponent for MjSelector {
 }
 
 impl MjBaseComponent for MjStyle {
-    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         let indentation = build_indentation(depth);
         let inline = if self.inline { "inline=\"inline\"" } else { "" };
 
@@ -257,7 +324,7 @@ impl MjBaseComponent for MjStyle {
 }
 
 impl MjBaseComponent for MjFont {
-    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         let indentation = build_indentation(depth);
         writeln!(
             writer,
@@ -266,3 +333,74 @@ impl MjBaseComponent for MjFont {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::model::element::Element;
+    use crate::model::mjml::Mjml;
+
+    #[test]
+    fn required_defaults() {
+        let mjml: Mjml = r#"<mjml><mj-head><mj-attributes>
+<mj-hero mode="fixed-height" />
+<mj-hero background-height="300px" />
+<mj-image alt="logo" />
+</mj-attributes></mj-head><mj-body></mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        let rendered = mjml.to_string();
+        assert!(rendered.contains("<mj-hero mode=\"fixed-height\"/>"));
+        assert!(rendered.contains("<mj-hero background-height=\"300px\"/>"));
+        assert!(rendered.contains("<mj-image alt=\"logo\"/>"));
+        assert_eq!(rendered.parse::<Mjml>().unwrap(), mjml);
+
+        let element = Element::from(&mjml);
+        let defaults = &element.children[0].children[0].children;
+        assert_eq!(defaults[0].attributes.len(), 1);
+        assert_eq!(defaults[2].attributes.len(), 1);
+
+        // written on purpose, even with the value of an unset attribute
+        let mjml: Mjml = r#"<mjml><mj-head><mj-attributes>
+<mj-hero background-height="0px" />
+<mj-image src="" />
+</mj-attributes></mj-head><mj-body></mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        let rendered = mjml.to_string();
+        assert!(rendered.contains("<mj-hero background-height=\"0\"/>"));
+        assert!(rendered.contains("<mj-image src=\"\"/>"));
+        assert_eq!(rendered.parse::<Mjml>().unwrap(), mjml);
+        let element = Element::from(&mjml);
+        let defaults = &element.children[0].children[0].children;
+        assert_eq!(defaults[0].attributes["background-height"], "0");
+        assert_eq!(defaults[1].attributes["src"], "");
+
+        // components still need them
+        let missing = r#"<mjml><mj-body><mj-section><mj-column>
+<mj-image alt="logo" />
+</mj-column></mj-section></mj-body></mjml>"#;
+        let error = missing.parse::<Mjml>().unwrap_err();
+        assert!(error.to_string().contains("src"), "{}", error);
+    }
+
+    #[test]
+    fn styles() {
+        let mjml: Mjml = r#"<mjml><mj-head>
+<mj-style inline="inline">.a { color: red; }</mj-style>
+<mj-style>.b { color: blue; }</mj-style>
+</mj-head><mj-body></mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        let styles = &mjml.mj_head.as_ref().unwrap().mj_style;
+        assert_eq!(styles.len(), 2);
+        assert!(styles[0].inline);
+        assert_eq!(styles[0].content, ".a { color: red; }");
+        assert!(!styles[1].inline);
+        assert_eq!(styles[1].content, ".b { color: blue; }");
+
+        let rendered = mjml.to_string();
+        let first = rendered.find(".a { color: red; }").unwrap();
+        assert!(rendered[first..].contains(".b { color: blue; }"));
+        assert_eq!(rendered.parse::<Mjml>().unwrap(), mjml);
+    }
+}
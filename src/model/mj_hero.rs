@@ -1,50 +1,83 @@
+use crate::model::background::HeroBackground;
 use crate::model::{
-    render_generic_attribute, render_padding_opt, Alignment, BaseComponent, Color,
-    MjBaseComponentAttributes, MjBaseComponentSimplified, MjBodyComponent, MjBodySectionComponent,
-    Padding, SectionCompatible, SizePx, Url, VerticalAlignment,
+    impl_attribute_value, impl_from_str, schema, ColumnNode, MjBaseComponent,
+    MjBaseComponentAttributes, MjBaseComponentSimplified, Padding, SizePx, VerticalAlignment,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
+use std::fmt::{Display, Formatter};
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjHero {
-    pub content: Vec<Box<dyn MjBodyComponent>>,
+    pub content: Vec<ColumnNode>,
     pub attributes: MjHeroAttributes,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HeroMode {
     FluidHeight,
-    FixedHeight(SizePx),
+    /// Uses `height`.
+    FixedHeight,
 }
+impl Display for HeroMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FluidHeight => write!(f, "fluid-height"),
+            Self::FixedHeight => write!(f, "fixed-height"),
+        }
+    }
+}
+impl_from_str!(
+    HeroMode,
+    "hero mode",
+    "fluid-height" => FluidHeight,
+    "fixed-height" => FixedHeight,
+);
+impl_attribute_value!(HeroMode => schema::HERO_MODE);
 
+#[derive(Debug, Clone, PartialEq, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[mj(no_default)]
 pub struct MjHeroAttributes {
+    #[mj(flatten)]
+    pub background: Option<HeroBackground>,
+    #[mj(required)]
     pub background_height: SizePx,
+    #[mj(required)]
     pub background_width: SizePx,
-    pub background_color: Color,
-    pub background_url: Option<Url>,
-    pub background_position: Option<(Alignment, VerticalAlignment)>,
     pub border_radius: Option<SizePx>,
+    #[mj(default = "fluid-height")]
     pub mode: Option<HeroMode>,
+    #[mj(default = "0px")]
+    pub height: Option<SizePx>,
+    #[mj(default = "0px")]
     pub padding: Option<Padding>,
+    #[mj(default = "top")]
     pub vertical_align: Option<VerticalAlignment>,
 }
 
-impl MjBodyComponent for MjHero {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
-impl MjBodySectionComponent for MjHero {
-    fn to_section(&self) -> SectionCompatible {
-        SectionCompatible::Hero(self)
+impl MjHeroAttributes {
+    /// Zero sized hero, for callers that set the required attributes right after.
+    #[doc(hidden)]
+    pub fn placeholder() -> Self {
+        Self {
+            background: None,
+            background_height: SizePx::new(0),
+            background_width: SizePx::new(0),
+            border_radius: None,
+            mode: None,
+            height: None,
+            padding: None,
+            vertical_align: None,
+        }
     }
 }
+
 impl MjBaseComponentSimplified for MjHero {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for component in &self.content {
-            component
-                .to_base_component()
-                .inner
-                .render(writer, depth + 1)?
+            component.render(writer, depth + 1)?
         }
         Ok(())
     }
@@ -61,33 +94,3 @@ impl MjBaseComponentSimplified for MjHero {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjHeroAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        write!(writer, " background-height=\"{}\"", self.background_height)?;
-        write!(writer, " background-width=\"{}\"", self.background_width)?;
-        write!(writer, " background-color=\"{}\"", self.background_color)?;
-        render_generic_attribute(writer, "background-url", &self.background_url)?;
-        if let Some((align, vertical_align)) = self.background_position {
-            write!(
-                writer,
-                " background-position=\"{} {}\"",
-                align, vertical_align
-            )?;
-        }
-        render_generic_attribute(writer, "border-radius", &self.border_radius)?;
-
-        if let Some(mode) = self.mode {
-            match mode {
-                HeroMode::FluidHeight => write!(writer, " mode=\"fluid-height\"")?,
-                HeroMode::FixedHeight(height) => {
-                    write!(writer, " mode=\"fixed-height\"")?;
-                    write!(writer, " height=\"{}\"", height)?;
-                }
-            }
-        }
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_generic_attribute(writer, "vertical-align", &self.vertical_align)?;
-        Ok(())
-    }
-}
@@ -1,18 +1,25 @@
 use crate::model::{
-    render_css_class, render_generic_attribute, render_padding_opt, Alignment, BaseComponent,
-    BorderProperties, Color, CssClass, MjBaseComponentAttributes, MjBaseComponentSimplified,
-    MjBodyComponent, Padding, SizePx, Url,
+    Alignment, BorderProperties, Color, CssClass, DeviceVisibility, MjBaseComponentAttributes,
+    MjBaseComponentSimplified, Padding, SizePx, Url,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjImage {
     pub attributes: MjImageAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjImageAttributes {
+    #[mj(default = "center")]
     pub align: Option<Alignment>,
+    #[mj(default = "")]
     pub alt: Option<String>,
+    #[mj(default = "none")]
     pub border: Option<BorderProperties>,
     pub border_bottom: Option<BorderProperties>,
     pub border_left: Option<BorderProperties>,
@@ -20,29 +27,33 @@ pub struct MjImageAttributes {
     pub border_right: Option<BorderProperties>,
     pub border_top: Option<BorderProperties>,
     pub container_background_color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
     pub fluid_on_mobile: Option<bool>,
     pub height: Option<SizePx>,
     pub href: Option<Url>,
     pub name: Option<String>,
+    #[mj(default = "10px 25px")]
     pub padding: Option<Padding>,
     pub rel: Option<String>,
     pub sizes: Option<String>, // todo ??
+    #[mj(required)]
     pub src: Url,
     pub srcset: Option<String>, // todo ??
+    #[mj(default = "_blank")]
     pub target: Option<String>,
     pub title: Option<String>,
     pub usemap: Option<String>,
     pub width: Option<SizePx>,
+    /// Shown on every device when unset.
+    #[mj(skip)]
+    pub visibility: Option<DeviceVisibility>,
 }
 
-impl MjBodyComponent for MjImage {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
 impl MjBaseComponentSimplified for MjImage {
-    fn render_content(&self, _writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, _writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         Ok(())
     }
 
@@ -58,36 +69,3 @@ impl MjBaseComponentSimplified for MjImage {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjImageAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "align", &self.align)?;
-        render_generic_attribute(writer, "alt", &self.alt)?;
-        render_generic_attribute(writer, "border", &self.border)?;
-        render_generic_attribute(writer, "border-bottom", &self.border_bottom)?;
-        render_generic_attribute(writer, "border-left", &self.border_left)?;
-        render_generic_attribute(writer, "border-right", &self.border_right)?;
-        render_generic_attribute(writer, "border-top", &self.border_top)?;
-        render_generic_attribute(writer, "border-radius", &self.border_radius)?;
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_css_class(writer, &self.css_class)?;
-        render_generic_attribute(writer, "fluid-on-mobile", &self.fluid_on_mobile)?;
-        render_generic_attribute(writer, "height", &self.height)?;
-        render_generic_attribute(writer, "href", &self.href)?;
-        render_generic_attribute(writer, "name", &self.name)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_generic_attribute(writer, "rel", &self.rel)?;
-        render_generic_attribute(writer, "sizes", &self.sizes)?;
-        write!(writer, " src=\"{}\"", self.src)?;
-        render_generic_attribute(writer, "srcset", &self.srcset)?;
-        render_generic_attribute(writer, "target", &self.target)?;
-        render_generic_attribute(writer, "title", &self.title)?;
-        render_generic_attribute(writer, "usemap", &self.usemap)?;
-        render_generic_attribute(writer, "width", &self.width)?;
-        Ok(())
-    }
-}
@@ -1,76 +1,108 @@
 use crate::model::{
-    render_css_class, render_font_family, render_generic_attribute, render_padding_opt, Alignment,
-    BaseComponent, Color, CssClass, FontStyle, Html, MjBaseComponent, MjBaseComponentAttributes,
-    MjBaseComponentSimplified, MjBodyComponent, Padding, PxOrEm, SizePx, TextDecoration,
+    parse_font_weight, Alignment, Color, CssClass, FontStyle, Html, LineHeight, MjBaseComponent,
+    MjBaseComponentAttributes, MjBaseComponentSimplified, Padding, PxOrEm, SizePx, TextDecoration,
     TextTransform, Url,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjNavbar {
     pub links: Vec<MjNavbarLink>,
     pub attributes: MjNavbarAttributes,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjNavbarLink {
     pub content: Html,
     pub attributes: MjNavbarLinkAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjNavbarAttributes {
+    #[mj(default = "center")]
     pub align: Option<Alignment>,
-    pub base_url: Option<String>,
+    pub base_url: Option<Url>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(flatten, flag = "hamburger")]
     pub hamburger: Option<MjNavbarHamburgerAttributes>,
 }
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjNavbarHamburgerAttributes {
+    #[mj(default = "center")]
     pub ico_align: Option<Alignment>,
+    #[mj(default = "8855")]
     pub ico_close: Option<u32>, // todo
+    #[mj(default = "#000000")]
     pub ico_color: Option<Color>,
+    #[mj(default = "Ubuntu, Helvetica, Arial, sans-serif", list = "font-family")]
     pub ico_font_family: Vec<String>,
+    #[mj(default = "30px")]
     pub ico_font_size: Option<SizePx>,
+    #[mj(default = "30px")]
     pub ico_line_height: Option<SizePx>,
+    #[mj(default = "9776")]
     pub ico_open: Option<u32>, // todo
+    #[mj(default = "10px")]
     pub ico_padding: Option<Padding>,
+    #[mj(default = "none")]
     pub ico_text_decoration: Option<TextDecoration>,
+    #[mj(default = "uppercase")]
     pub ico_text_transform: Option<TextTransform>,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjNavbarLinkAttributes {
+    #[mj(default = "#000000")]
     pub color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "Ubuntu, Helvetica, Arial, sans-serif", list = "font-family")]
     pub font_family: Vec<String>,
+    #[mj(default = "13px")]
     pub font_size: Option<SizePx>,
     pub font_style: Option<FontStyle>,
+    #[mj(parse = "parse_font_weight")]
     pub font_weight: Option<u32>,
     pub href: Option<Url>, // todo
     pub letter_spacing: Option<PxOrEm>,
-    pub line_height: Option<SizePx>,
+    #[mj(default = "22px")]
+    pub line_height: Option<LineHeight>,
+    #[mj(default = "15px 10px")]
     pub padding: Option<Padding>,
     pub rel: Option<String>,
+    #[mj(default = "_blank")]
     pub target: Option<String>,
+    #[mj(default = "none")]
     pub text_decoration: Option<TextDecoration>,
+    #[mj(default = "uppercase")]
     pub text_transform: Option<TextTransform>,
 }
 
-impl MjBodyComponent for MjNavbar {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
 impl MjBaseComponentSimplified for MjNavbar {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for link in &self.links {
-            Box::new(link).render(writer, depth)?;
+            link.render(writer, depth)?;
         }
 
         Ok(())
     }
 
     fn name(&self) -> &'static str {
-        "mj-navbar-link"
+        "mj-navbar"
     }
 
     fn has_content(&self) -> bool {
@@ -82,7 +114,7 @@ impl MjBaseComponentSimplified for MjNavbar {
     }
 }
 impl MjBaseComponentSimplified for MjNavbarLink {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -98,52 +130,3 @@ impl MjBaseComponentSimplified for MjNavbarLink {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjNavbarAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "align", &self.align)?;
-        render_generic_attribute(writer, "base_url", &self.base_url)?;
-        render_css_class(writer, &self.css_class)?;
-        if let Some(hamburger) = &self.hamburger {
-            Box::new(hamburger).render(writer)?;
-        }
-        Ok(())
-    }
-}
-impl MjBaseComponentAttributes for MjNavbarHamburgerAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "ico-align", &self.ico_align)?;
-        render_generic_attribute(writer, "ico-close", &self.ico_close)?;
-        render_generic_attribute(writer, "ico-color", &self.ico_color)?;
-        if !self.ico_font_family.is_empty() {
-            let families = self.ico_font_family.join(", ");
-            write!(writer, " ico-font-family=\"{}\"", families)?;
-        }
-        render_generic_attribute(writer, "ico-font-size", &self.ico_font_size)?;
-        render_generic_attribute(writer, "ico-line-height", &self.ico_line_height)?;
-        render_generic_attribute(writer, "ico-open", &self.ico_open)?;
-        render_padding_opt(writer, "ico-padding", &self.ico_padding)?;
-        render_generic_attribute(writer, "ico-text-decoration", &self.ico_text_decoration)?;
-        render_generic_attribute(writer, "ico-text-transform", &self.ico_text_transform)?;
-        Ok(())
-    }
-}
-impl MjBaseComponentAttributes for MjNavbarLinkAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "color", &self.color)?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "font-size", &self.font_size)?;
-        render_generic_attribute(writer, "font-style", &self.font_style)?;
-        render_generic_attribute(writer, "font-weight", &self.font_weight)?;
-        render_generic_attribute(writer, "href", &self.href)?;
-        render_generic_attribute(writer, "letter-spacing", &self.letter_spacing)?;
-        render_generic_attribute(writer, "line-height", &self.line_height)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_generic_attribute(writer, "rel", &self.rel)?;
-        render_generic_attribute(writer, "target", &self.target)?;
-        render_generic_attribute(writer, "text-decoration", &self.text_decoration)?;
-        render_generic_attribute(writer, "text-transform", &self.text_transform)?;
-        Ok(())
-    }
-}
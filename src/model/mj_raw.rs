@@ -1,20 +1,14 @@
-use crate::model::{
-    BaseComponent, Html, MjBaseComponentAttributes, MjBaseComponentSimplified, MjBodyComponent,
-};
-use std::io::Write;
+use crate::model::{CustomComponent, Html, MjBaseComponentAttributes, MjBaseComponentSimplified};
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjRaw {
     pub content: Html,
 }
 
-impl MjBodyComponent for MjRaw {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
-
 impl MjBaseComponentSimplified for MjRaw {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -30,3 +24,10 @@ impl MjBaseComponentSimplified for MjRaw {
         None
     }
 }
+
+/// Lets raw markup sit where the model has no dedicated variant for it, such as in `mj-body`.
+impl CustomComponent for MjRaw {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
+        self.render_all(writer, depth)
+    }
+}
@@ -1,46 +1,53 @@
+use crate::model::background::Background;
 use crate::model::{
-    render_css_class, render_generic_attribute, render_padding_opt, BaseComponent,
-    BorderProperties, Color, CssClass, Direction, MjBaseComponent, MjBaseComponentAttributes,
-    MjBaseComponentSimplified, MjBodyColumnComponent, MjBodyComponent, MjBodySectionComponent,
-    Padding, SectionCompatible, SizePx, TextAlignment, Url,
+    BorderProperties, CssClass, DeviceVisibility, Direction, MjBaseComponent,
+    MjBaseComponentAttributes, MjBaseComponentSimplified, Padding, SectionNode, SizePx,
+    TextAlignment,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjSection {
     pub attributes: MjSectionAttributes,
-    pub content: Vec<Box<dyn MjBodyColumnComponent>>,
+    pub content: Vec<SectionNode>,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjSectionAttributes {
-    pub background_color: Option<Color>,
-    pub background_position_x: Option<String>, // todo
-    pub background_position_y: Option<String>, // todo
-    pub background_repeat: Option<String>,     // todo
-    pub background_size: Option<String>,       // todo
-    pub background_url: Option<Url>,
+    #[mj(flatten)]
+    pub background: Option<Background>,
+    #[mj(default = "none")]
     pub border: Option<BorderProperties>,
     pub border_bottom: Option<BorderProperties>,
     pub border_left: Option<BorderProperties>,
     pub border_radius: Option<SizePx>,
     pub border_right: Option<BorderProperties>,
     pub border_top: Option<BorderProperties>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "ltr")]
     pub direction: Option<Direction>,
+    #[mj(flag = "full-width")]
     pub full_width: bool,
+    #[mj(default = "20px 0")]
     pub padding: Option<Padding>,
+    #[mj(default = "center")]
     pub text_align: Option<TextAlignment>, // todo
+    /// Shown on every device when unset.
+    #[mj(skip)]
+    pub visibility: Option<DeviceVisibility>,
 }
 
-impl MjBodyComponent for MjSection {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
 impl MjBaseComponentSimplified for MjSection {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for column in &self.content {
-            column.to_column().render(writer, depth + 1)?
+            column.render(writer, depth + 1)?
         }
 
         Ok(())
@@ -58,33 +65,3 @@ impl MjBaseComponentSimplified for MjSection {
         Some(&self.attributes)
     }
 }
-impl MjBodySectionComponent for MjSection {
-    fn to_section(&self) -> SectionCompatible {
-        SectionCompatible::Section(self)
-    }
-}
-
-impl MjBaseComponentAttributes for MjSectionAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "background-color", &self.background_color)?;
-        render_generic_attribute(writer, "background-position-x", &self.background_position_x)?;
-        render_generic_attribute(writer, "background-position-y", &self.background_position_y)?;
-        render_generic_attribute(writer, "background-repeat", &self.background_repeat)?;
-        render_generic_attribute(writer, "background-size", &self.background_size)?;
-        render_generic_attribute(writer, "background-url", &self.background_url)?;
-        render_generic_attribute(writer, "border", &self.border)?;
-        render_generic_attribute(writer, "border-bottom", &self.border_bottom)?;
-        render_generic_attribute(writer, "border-left", &self.border_left)?;
-        render_generic_attribute(writer, "border-right", &self.border_right)?;
-        render_generic_attribute(writer, "border-top", &self.border_top)?;
-        render_generic_attribute(writer, "border-radius", &self.border_radius)?;
-        render_css_class(writer, &self.css_class)?;
-        render_generic_attribute(writer, "direction", &self.direction)?;
-        if self.full_width {
-            write!(writer, " full-width=\"full-width\"")?;
-        }
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_generic_attribute(writer, "text-align", &self.text_align)?;
-        Ok(())
-    }
-}
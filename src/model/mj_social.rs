@@ -1,17 +1,21 @@
 use crate::model::{
-    render_css_class, render_font_family, render_generic_attribute, render_padding_opt, Alignment,
-    BaseComponent, Color, CssClass, FontStyle, Html, MjBaseComponent, MjBaseComponentAttributes,
-    MjBaseComponentSimplified, MjBodyComponent, Padding, PxOrEm, PxOrPercent, SizePx,
-    TextDecoration, Url, VerticalAlignment,
+    impl_attribute_value, impl_from_str, parse_font_weight, schema, Alignment, Color, CssClass,
+    FontStyle, Html, MjBaseComponent, MjBaseComponentAttributes, MjBaseComponentSimplified,
+    Padding, PxOrEm, PxOrPercent, SizePx, TextDecoration, Url, VerticalAlignment,
 };
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 use std::fmt::{Display, Formatter};
-use std::io::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjSocial {
     pub elements: Vec<MjSocialElement>,
     pub attributes: MjSocialAttributes,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjSocialElement {
     pub content: Html,
     pub attributes: MjSocialElementAttributes,
@@ -30,55 +34,89 @@ impl Display for SocialMode {
         }
     }
 }
+impl_from_str!(SocialMode, "social mode", "vertical" => Vertical, "horizontal" => Horizontal);
+impl_attribute_value!(SocialMode => schema::SOCIAL_MODE);
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjSocialAttributes {
+    #[mj(default = "center")]
     pub align: Option<Alignment>,
+    #[mj(default = "3px")]
     pub border_radius: Option<SizePx>,
+    #[mj(default = "#333333")]
     pub color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
     pub container_background_color: Option<Color>,
+    #[mj(default = "Ubuntu, Helvetica, Arial, sans-serif", list = "font-family")]
     pub font_family: Vec<String>,
+    #[mj(default = "13px")]
     pub font_size: Option<PxOrEm>,
     pub font_style: Option<FontStyle>,
+    #[mj(parse = "parse_font_weight")]
     pub font_weight: Option<u32>,
     pub icon_height: Option<PxOrPercent>,
+    #[mj(default = "20px")]
     pub icon_size: Option<PxOrPercent>,
     pub inner_padding: Option<Padding>,
+    #[mj(default = "22px")]
     pub line_height: Option<PxOrPercent>,
+    #[mj(default = "horizontal")]
     pub mode: Option<SocialMode>,
+    #[mj(default = "10px 25px")]
     pub padding: Option<Padding>,
     pub icon_padding: Option<Padding>,
     pub text_padding: Option<Padding>,
+    #[mj(default = "none")]
     pub text_decoration: Option<TextDecoration>,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjSocialElementAttributes {
+    #[mj(default = "center")]
     pub align: Option<Alignment>,
+    #[mj(default = "")]
     pub alt: Option<String>,
     pub background_color: Option<Color>,
+    #[mj(default = "3px")]
     pub border_radius: Option<SizePx>,
+    #[mj(default = "#000")]
     pub color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "Ubuntu, Helvetica, Arial, sans-serif", list = "font-family")]
     pub font_family: Vec<String>,
+    #[mj(default = "13px")]
     pub font_size: Option<PxOrEm>,
     pub font_style: Option<FontStyle>,
+    #[mj(parse = "parse_font_weight")]
     pub font_weight: Option<u32>, // todo
     pub href: Option<Url>,
     pub icon_height: Option<PxOrPercent>,
     pub icon_size: Option<PxOrPercent>,
     pub line_height: Option<PxOrPercent>,
     pub name: Option<String>, //todo
+    #[mj(default = "4px")]
     pub padding: Option<Padding>,
     pub icon_padding: Option<Padding>,
+    #[mj(default = "4px 4px 4px 0")]
     pub text_padding: Option<Padding>,
     pub sizes: Option<String>, // todo
     pub src: Option<Url>,
     pub srcset: Option<String>, // todo
     pub rel: Option<String>,
+    #[mj(default = "_blank")]
     pub target: Option<String>,
     pub title: Option<String>,
+    #[mj(default = "none")]
     pub text_decoration: Option<TextDecoration>,
     pub vertical_align: Option<VerticalAlignment>,
 }
@@ -139,15 +177,10 @@ impl Display for SocialName {
     }
 }
 
-impl MjBodyComponent for MjSocial {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
 impl MjBaseComponentSimplified for MjSocial {
-    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         for element in &self.elements {
-            Box::new(element).render(writer, depth)?;
+            element.render(writer, depth)?;
         }
         Ok(())
     }
@@ -165,7 +198,7 @@ impl MjBaseComponentSimplified for MjSocial {
     }
 }
 impl MjBaseComponentSimplified for MjSocialElement {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -181,63 +214,3 @@ impl MjBaseComponentSimplified for MjSocialElement {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjSocialAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "align", &self.align)?;
-        render_generic_attribute(writer, "border-radius", &self.border_radius)?;
-        render_generic_attribute(writer, "color", &self.color)?;
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "font-size", &self.font_size)?;
-        render_generic_attribute(writer, "font-style", &self.font_style)?;
-        render_generic_attribute(writer, "font-weight", &self.font_weight)?;
-        render_generic_attribute(writer, "icon-height", &self.icon_height)?;
-        render_generic_attribute(writer, "icon-size", &self.icon_size)?;
-        render_padding_opt(writer, "inner-padding", &self.inner_padding)?;
-        render_generic_attribute(writer, "line-height", &self.line_height)?;
-        render_generic_attribute(writer, "mode", &self.mode)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_padding_opt(writer, "icon-padding", &self.icon_padding)?;
-        render_padding_opt(writer, "text-padding", &self.text_padding)?;
-        render_generic_attribute(writer, "text-decoration", &self.text_decoration)?;
-
-        Ok(())
-    }
-}
-impl MjBaseComponentAttributes for MjSocialElementAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "align", &self.align)?;
-        render_generic_attribute(writer, "alt", &self.alt)?;
-        render_generic_attribute(writer, "background-color", &self.background_color)?;
-        render_generic_attribute(writer, "border-radius", &self.border_radius)?;
-        render_generic_attribute(writer, "color", &self.color)?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "font-size", &self.font_size)?;
-        render_generic_attribute(writer, "font-style", &self.font_style)?;
-        render_generic_attribute(writer, "font-weight", &self.font_weight)?;
-        render_generic_attribute(writer, "href", &self.href)?;
-        render_generic_attribute(writer, "icon-height", &self.icon_height)?;
-        render_generic_attribute(writer, "icon-size", &self.icon_size)?;
-        render_generic_attribute(writer, "line-height", &self.line_height)?;
-        render_generic_attribute(writer, "name", &self.name)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_padding_opt(writer, "icon-padding", &self.icon_padding)?;
-        render_padding_opt(writer, "text-padding", &self.text_padding)?;
-        render_generic_attribute(writer, "sizes", &self.sizes)?;
-        render_generic_attribute(writer, "src", &self.src)?;
-        render_generic_attribute(writer, "srcset", &self.srcset)?;
-        render_generic_attribute(writer, "rel", &self.rel)?;
-        render_generic_attribute(writer, "target", &self.target)?;
-        render_generic_attribute(writer, "title", &self.title)?;
-        render_generic_attribute(writer, "text-decoration", &self.text_decoration)?;
-        render_generic_attribute(writer, "vertical-align", &self.vertical_align)?;
-        Ok(())
-    }
-}
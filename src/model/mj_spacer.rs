@@ -1,28 +1,31 @@
 use crate::model::{
-    render_css_class, render_generic_attribute, render_padding_opt, BaseComponent, Color, CssClass,
-    MjBaseComponentAttributes, MjBaseComponentSimplified, MjBodyComponent, Padding, SizePx,
+    Color, CssClass, MjBaseComponentAttributes, MjBaseComponentSimplified, Padding, SizePx,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjSpacer {
     pub attributes: MjSpacerAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjSpacerAttributes {
     pub container_background_color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
     pub padding: Option<Padding>,
+    #[mj(default = "20px")]
     pub height: Option<SizePx>,
 }
 
-impl MjBodyComponent for MjSpacer {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
 impl MjBaseComponentSimplified for MjSpacer {
-    fn render_content(&self, _writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, _writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         Ok(())
     }
 
@@ -38,17 +41,3 @@ impl MjBaseComponentSimplified for MjSpacer {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjSpacerAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_css_class(writer, &self.css_class)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_generic_attribute(writer, "height", &self.height)?;
-        Ok(())
-    }
-}
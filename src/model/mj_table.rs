@@ -1,11 +1,14 @@
 use crate::model::{
-    render_css_class, render_font_family, render_generic_attribute, render_padding_opt, Alignment,
-    BaseComponent, BorderProperties, Color, CssClass, Html, MjBaseComponentAttributes,
-    MjBaseComponentSimplified, MjBodyComponent, Padding, PxOrPercent, SizePx,
+    impl_attribute_value, impl_from_str, schema, Alignment, BorderProperties, Color, CssClass,
+    Html, LineHeight, MjBaseComponentAttributes, MjBaseComponentSimplified, Padding, PxOrPercent,
+    SizePx,
 };
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 use std::fmt::{Display, Formatter};
-use std::io::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjTable {
     // todo: should be restricted to what can be used inside an html table
     pub content: Html,
@@ -29,32 +32,53 @@ impl Display for TableLayout {
         }
     }
 }
+impl_from_str!(
+    TableLayout,
+    "table layout",
+    "auto" => Auto,
+    "fixed" => Fixed,
+    "initial" => Initial,
+    "inherit" => Inherit,
+);
+impl_attribute_value!(TableLayout => schema::TABLE_LAYOUT);
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjTableAttributes {
+    #[mj(default = "left")]
     pub align: Option<Alignment>,
+    #[mj(default = "none")]
     pub border: Option<BorderProperties>,
+    #[mj(default = "0")]
     pub cellpadding: Option<SizePx>,
+    #[mj(default = "0")]
     pub cellspacing: Option<SizePx>,
+    #[mj(default = "#000000")]
     pub color: Option<Color>,
     pub container_background_color: Option<Color>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    #[mj(default = "Ubuntu, Helvetica, Arial, sans-serif", list = "font-family")]
     pub font_family: Vec<String>,
+    #[mj(default = "13px")]
     pub font_size: Option<SizePx>,
-    pub line_height: Option<PxOrPercent>,
+    #[mj(default = "22px")]
+    pub line_height: Option<LineHeight>,
+    #[mj(default = "10px 25px")]
     pub padding: Option<Padding>,
+    #[mj(name = "role", flag = "presentation")]
     pub presentation_role: bool,
+    #[mj(default = "auto")]
     pub table_layout: Option<TableLayout>,
+    #[mj(default = "100%")]
     pub width: Option<PxOrPercent>,
 }
 
-impl MjBodyComponent for MjTable {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
-}
 impl MjBaseComponentSimplified for MjTable {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -70,29 +94,3 @@ impl MjBaseComponentSimplified for MjTable {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjTableAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "align", &self.align)?;
-        render_generic_attribute(writer, "border", &self.border)?;
-        render_generic_attribute(writer, "cellpadding", &self.cellpadding)?;
-        render_generic_attribute(writer, "cellspacing", &self.cellspacing)?;
-        render_generic_attribute(writer, "color", &self.color)?;
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_css_class(writer, &self.css_class)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "font-size", &self.font_size)?;
-        render_generic_attribute(writer, "line-height", &self.line_height)?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        if self.presentation_role {
-            write!(writer, " role=\"presentation\"")?;
-        }
-        render_generic_attribute(writer, "table-layout", &self.table_layout)?;
-        render_generic_attribute(writer, "width", &self.width)?;
-        Ok(())
-    }
-}
@@ -1,41 +1,66 @@
 use crate::model::{
-    render_css_class, render_font_family, render_generic_attribute, render_padding_opt,
-    BaseComponent, Color, CssClass, FontStyle, Html, MjBaseComponentAttributes,
-    MjBaseComponentSimplified, MjBodyComponent, Padding, PxOrEm, SizePx, TextAlignment,
+    parse_font_weight, Color, CssClass, DeviceVisibility, FontStyle, Html, LineHeight,
+    MjBaseComponentAttributes, MjBaseComponentSimplified, Padding, PxOrEm, SizePx, TextAlignment,
     TextDecoration, TextTransform,
 };
-use std::io::Write;
+use mjml_builder_derive::Attributes;
+use std::fmt::Write;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjText {
     pub content: Html,
     pub attributes: MjTextAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjTextAttributes {
+    #[mj(default = "#000000")]
     pub color: Option<Color>,
+    #[mj(default = "Ubuntu, Helvetica, Arial, sans-serif", list = "font-family")]
     pub font_family: Vec<String>,
+    #[mj(default = "13px")]
     pub font_size: Option<SizePx>,
     pub font_style: Option<FontStyle>,
+    #[mj(parse = "parse_font_weight")]
     pub font_weight: Option<u32>, // todo
-    pub line_height: Option<SizePx>,
+    pub line_height: Option<LineHeight>,
     pub letter_spacing: Option<PxOrEm>,
     pub height: Option<SizePx>,
     pub text_decoration: Option<TextDecoration>,
     pub text_transform: Option<TextTransform>,
+    #[mj(default = "left")]
     pub align: Option<TextAlignment>,
     pub container_background_color: Option<Color>,
+    #[mj(default = "10px 25px")]
     pub padding: Option<Padding>,
+    #[mj(list = "css-class")]
     pub css_class: Vec<CssClass>,
+    #[mj(list = "css-class")]
+    pub mj_class: Vec<CssClass>,
+    /// Shown on every device when unset.
+    #[mj(skip)]
+    pub visibility: Option<DeviceVisibility>,
+    /// Applied below the breakpoint, through a generated css class.
+    #[mj(skip)]
+    pub mobile: Option<MjTextMobile>,
 }
 
-impl MjBodyComponent for MjText {
-    fn to_base_component(&self) -> BaseComponent {
-        BaseComponent { inner: self }
-    }
+/// Values replacing the attributes of the same name on mobile.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MjTextMobile {
+    pub align: Option<TextAlignment>,
+    pub font_size: Option<SizePx>,
+    pub line_height: Option<LineHeight>,
+    pub padding: Option<Padding>,
 }
+
 impl MjBaseComponentSimplified for MjText {
-    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::io::Result<()> {
+    fn render_content(&self, writer: &mut dyn Write, _depth: usize) -> std::fmt::Result {
         writeln!(writer, "{}", self.content)
     }
 
@@ -51,27 +76,3 @@ impl MjBaseComponentSimplified for MjText {
         Some(&self.attributes)
     }
 }
-
-impl MjBaseComponentAttributes for MjTextAttributes {
-    fn render(&self, writer: &mut dyn Write) -> std::io::Result<()> {
-        render_generic_attribute(writer, "color", &self.color)?;
-        render_font_family(writer, &self.font_family)?;
-        render_generic_attribute(writer, "font-size", &self.font_size)?;
-        render_generic_attribute(writer, "font-style", &self.font_style)?;
-        render_generic_attribute(writer, "font-weight", &self.font_weight)?;
-        render_generic_attribute(writer, "line-height", &self.line_height)?;
-        render_generic_attribute(writer, "letter-spacing", &self.letter_spacing)?;
-        render_generic_attribute(writer, "height", &self.height)?;
-        render_generic_attribute(writer, "text-decoration", &self.text_decoration)?;
-        render_generic_attribute(writer, "text-transform", &self.text_transform)?;
-        render_generic_attribute(writer, "align", &self.align)?;
-        render_generic_attribute(
-            writer,
-            "container-background-color",
-            &self.container_background_color,
-        )?;
-        render_padding_opt(writer, "padding", &self.padding)?;
-        render_css_class(writer, &self.css_class)?;
-        Ok(())
-    }
-}
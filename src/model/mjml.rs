@@ -1,8 +1,15 @@
 use crate::model::mj_body::MjBody;
 use crate::model::mj_head::MjHead;
-use crate::model::{build_indentation, Html, MjBaseComponent};
-use std::io::Write;
+use crate::model::{
+    build_indentation, impl_attribute_value, impl_from_str, schema, Html, IoWriter,
+    MarkupAttributes, MjBaseComponent, MjBaseComponentAttributes, MjBaseComponentSimplified,
+};
+use mjml_builder_derive::Attributes;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Write};
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mjml {
     pub mj_raw_file_start: Option<MjRawFileStart>,
     pub mj_body: MjBody,
@@ -11,49 +18,155 @@ pub struct Mjml {
     pub attributes: MjmlAttributes,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, PartialEq, Default, Attributes)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MjmlAttributes {
     pub owa: Option<Owa>,
+    #[mj(default = "und")]
     pub lang: Option<String>,
+    #[mj(name = "dir", default = "auto")]
     pub dit: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Owa {
     Desktop,
 }
+impl Display for Owa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Desktop => write!(f, "desktop"),
+        }
+    }
+}
+impl_from_str!(Owa, "owa", "desktop" => Desktop);
+impl_attribute_value!(Owa => schema::OWA);
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MjRawFileStart {
     pub content: Html,
 }
 
+/// Default size of the chunks produced by [`RenderChunks`].
+pub const DEFAULT_CHUNK_SIZE: usize = 16 * 1024;
+
+const BODY_DEPTH: usize = 1;
+
 impl Mjml {
-    pub fn render(self, writer: &mut Box<&mut dyn std::io::Write>) -> std::io::Result<()> {
-        writeln!(writer, "<mjml>")?;
-        if let Some(mj_raw_file_start) = self.mj_raw_file_start {
-            Box::new(mj_raw_file_start).render(writer, 1)?;
-        }
-        if let Some(mj_head) = self.mj_head {
-            Box::new(mj_head).render(writer, 1)?;
+    pub fn render(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let mut adapter = IoWriter::new(writer);
+        if self.render_fmt(&mut adapter).is_err() {
+            return Err(adapter.into_error());
         }
-        Box::new(self.mj_body).render(writer, 1)?;
-        writeln!(writer, "</mjml>")?;
 
         writer.flush()?;
 
         Ok(())
     }
 
-    pub fn render_to_string(self) -> std::io::Result<String> {
-        let mut buf: Vec<u8> = Vec::new();
+    /// Writes the document, with its mobile overrides applied.
+    pub fn render_fmt(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        let mjml = self.responsive();
+        mjml.render_prologue(writer)?;
+        for section in &mjml.mj_body.content {
+            MjBody::render_section(section, writer, BODY_DEPTH + 1)?;
+        }
+        mjml.render_epilogue(writer)
+    }
+
+    pub fn render_to_string(&self) -> std::io::Result<String> {
+        let mut buf = String::new();
+
+        self.render_fmt(&mut buf).map_err(std::io::Error::other)?;
+
+        Ok(buf)
+    }
 
-        self.render(&mut Box::new(&mut buf))?;
+    /// Renders the document piece by piece, splitting between the sections of the body. A
+    /// `chunk_size` of 0 gives one section per chunk.
+    pub fn render_chunks(&self, chunk_size: usize) -> RenderChunks<'_> {
+        RenderChunks {
+            mjml: self.responsive(),
+            chunk_size: chunk_size.max(1),
+            stage: ChunkStage::Prologue,
+            buffer: String::new(),
+        }
+    }
 
-        Ok(String::from_utf8_lossy(&buf).to_string())
+    fn render_prologue(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        write!(writer, "<mjml")?;
+        self.attributes.render(&mut MarkupAttributes(writer))?;
+        writeln!(writer, ">")?;
+        if let Some(mj_raw_file_start) = &self.mj_raw_file_start {
+            mj_raw_file_start.render(writer, 1)?;
+        }
+        if let Some(mj_head) = &self.mj_head {
+            mj_head.render(writer, 1)?;
+        }
+        self.mj_body.render_start(writer, BODY_DEPTH)
+    }
+
+    fn render_epilogue(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        self.mj_body.render_end(writer, BODY_DEPTH)?;
+        writeln!(writer, "</mjml>")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkStage {
+    Prologue,
+    Section(usize),
+    Epilogue,
+    Done,
+}
+
+/// Output of [`Mjml::render_chunks`].
+///
+/// Each chunk holds at least `chunk_size` bytes, except the last one. A single section is never
+/// split, so a chunk can be bigger than `chunk_size`.
+pub struct RenderChunks<'a> {
+    mjml: Cow<'a, Mjml>,
+    chunk_size: usize,
+    stage: ChunkStage,
+    buffer: String,
+}
+
+impl<'a> RenderChunks<'a> {
+    pub fn next_chunk(&mut self) -> Result<Option<&str>, std::fmt::Error> {
+        self.buffer.clear();
+        while self.stage != ChunkStage::Done && self.buffer.len() < self.chunk_size {
+            self.stage = match self.stage {
+                ChunkStage::Prologue => {
+                    self.mjml.render_prologue(&mut self.buffer)?;
+                    ChunkStage::Section(0)
+                }
+                ChunkStage::Section(index) => match self.mjml.mj_body.content.get(index) {
+                    Some(section) => {
+                        MjBody::render_section(section, &mut self.buffer, BODY_DEPTH + 1)?;
+                        ChunkStage::Section(index + 1)
+                    }
+                    None => ChunkStage::Epilogue,
+                },
+                ChunkStage::Epilogue => {
+                    self.mjml.render_epilogue(&mut self.buffer)?;
+                    ChunkStage::Done
+                }
+                ChunkStage::Done => ChunkStage::Done,
+            };
+        }
+
+        if self.buffer.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(&self.buffer))
+        }
     }
 }
 
 impl MjBaseComponent for MjRawFileStart {
-    fn render(&self, writer: &mut dyn std::io::Write, depth: usize) -> std::io::Result<()> {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         let indentation = build_indentation(depth);
         writeln!(writer, "{}<mj-raw position=\"file-start\">", indentation)?;
         writeln!(writer, "{}", self.content)?;
@@ -61,3 +174,9 @@ impl MjBaseComponent for MjRawFileStart {
         Ok(())
     }
 }
+
+impl Display for Mjml {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.render_fmt(f)
+    }
+}
@@ -1,3 +1,13 @@
+pub mod background;
+mod canonical;
+mod codegen;
+pub mod color;
+pub mod element;
+pub mod layout;
+pub mod length;
+#[doc(hidden)]
+pub mod macro_support;
+pub mod markup;
 pub mod mj_accordion;
 pub mod mj_body;
 pub mod mj_button;
@@ -17,96 +27,330 @@ pub mod mj_table;
 pub mod mj_text;
 pub mod mj_wrapper;
 pub mod mjml;
+mod responsive;
+pub mod schema;
+mod text;
+pub mod theme;
+#[cfg(feature = "json")]
+pub mod tokens;
 pub mod utils;
 
+use crate::model::mj_accordion::{
+    MjAccordion, MjAccordionElement, MjAccordionElementText, MjAccordionElementTitle,
+};
+use crate::model::mj_body::MjBody;
+use crate::model::mj_button::MjButton;
+use crate::model::mj_carousel::{MjCarousel, MjCarouselImage};
 use crate::model::mj_column::MjColumn;
+use crate::model::mj_divider::MjDivider;
 use crate::model::mj_group::MjGroup;
+use crate::model::mj_head::{MjAttributes, MjHead};
 use crate::model::mj_hero::MjHero;
+use crate::model::mj_image::MjImage;
+use crate::model::mj_navbar::{MjNavbar, MjNavbarLink};
+use crate::model::mj_raw::MjRaw;
 use crate::model::mj_section::MjSection;
+use crate::model::mj_social::{MjSocial, MjSocialElement};
+use crate::model::mj_spacer::MjSpacer;
+use crate::model::mj_table::MjTable;
+use crate::model::mj_text::MjText;
 use crate::model::mj_wrapper::MjWrapper;
-use std::io::Write;
+use crate::model::mjml::MjRawFileStart;
+pub use color::Color;
+use dyn_clone::DynClone;
+pub use length::{Length, LineHeight, PxOrEm, PxOrPercent, SizePx};
+use std::any::Any;
+use std::fmt::Write;
+use std::fmt::{Debug, Display, Formatter};
 pub use utils::*;
 
 trait MjBaseComponent {
-    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()>;
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result;
 }
 
 trait MjBaseComponentSimplified {
-    fn render_content(&self, writer: &mut dyn std::io::Write, depth: usize) -> std::io::Result<()>;
+    fn render_content(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result;
     fn name(&self) -> &'static str;
     fn has_content(&self) -> bool;
     fn attributes(&self) -> Option<&dyn MjBaseComponentAttributes>;
 
-    fn render_all(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render_all(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
+        self.render_start(writer, depth)?;
+        if self.has_content() {
+            self.render_content(writer, depth + 1)?;
+        }
+        self.render_end(writer, depth)
+    }
+
+    fn render_start(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         let indentation = build_indentation(depth);
-        let name = self.name();
-        write!(writer, "{}<{}", indentation, name)?;
+        write!(writer, "{}<{}", indentation, self.name())?;
         if let Some(attributes) = self.attributes() {
-            attributes.render(writer)?;
+            attributes.render(&mut MarkupAttributes(writer))?;
         }
         if self.has_content() {
-            writeln!(writer, ">")?;
-            self.render_content(writer, depth + 1)?;
-            writeln!(writer, "{}</{}>", indentation, name)?;
+            writeln!(writer, ">")
         } else {
-            writeln!(writer, "/>")?;
+            writeln!(writer, "/>")
+        }
+    }
+
+    fn render_end(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
+        if self.has_content() {
+            let indentation = build_indentation(depth);
+            writeln!(writer, "{}</{}>", indentation, self.name())?;
         }
         Ok(())
     }
 }
 
 impl<T: MjBaseComponentSimplified> MjBaseComponent for T {
-    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         self.render_all(writer, depth)
     }
 }
 
 trait MjBaseComponentAttributes {
-    fn render(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()>;
+    fn render(&self, writer: &mut dyn AttributeWriter) -> std::fmt::Result;
+    /// Sets an attribute from its markup value, returns `false` when there is no such attribute.
+    fn set(&mut self, name: &str, value: &str) -> Result<bool, ParseValueError>;
 }
 
-pub trait MjBodyComponent {
-    fn to_base_component(&self) -> BaseComponent;
+/// Object-safe equality, used to compare boxed custom components.
+pub trait DynEq: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn dyn_eq(&self, other: &dyn Any) -> bool;
 }
 
-pub struct BaseComponent<'a> {
-    inner: &'a dyn MjBaseComponent,
+impl<T: Any + PartialEq> DynEq for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn dyn_eq(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<T>() == Some(self)
+    }
 }
 
-pub enum SectionCompatible<'a> {
-    Section(&'a MjSection),
-    Wrapper(&'a MjWrapper),
-    Hero(&'a MjHero),
+/// Escape hatch for components that are not part of the model.
+pub trait CustomComponent: DynClone + DynEq + Debug + Send + Sync {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result;
+}
+dyn_clone::clone_trait_object!(CustomComponent);
+impl PartialEq for dyn CustomComponent {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other.as_any())
+    }
 }
 
-impl<'a> MjBaseComponent for SectionCompatible<'a> {
-    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
-        match *self {
-            SectionCompatible::Section(s) => Box::new(s).render(writer, depth),
-            SectionCompatible::Wrapper(w) => Box::new(w).render(writer, depth),
-            SectionCompatible::Hero(h) => Box::new(h).render(writer, depth),
+/// Custom components are serialized as their markup and read back as [`MjRaw`].
+#[cfg(feature = "serde")]
+mod custom_markup {
+    use crate::model::mj_raw::MjRaw;
+    use crate::model::CustomComponent;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    // serde passes a reference to the field itself
+    #[allow(clippy::borrowed_box)]
+    pub fn serialize<S: Serializer>(
+        custom: &Box<dyn CustomComponent>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut markup = String::new();
+        custom
+            .render(&mut markup, 0)
+            .map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(markup.trim_end())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Box<dyn CustomComponent>, D::Error> {
+        let markup = String::deserialize(deserializer)?;
+        let content = match markup.trim().strip_prefix("<mj-raw>") {
+            Some(rest) => rest
+                .strip_suffix("</mj-raw>")
+                .unwrap_or(rest)
+                .trim()
+                .to_string(),
+            None => markup,
+        };
+        Ok(Box::new(MjRaw { content }))
+    }
+}
+
+/// Children of `mj-body` and `mj-wrapper`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum BodyNode {
+    Section(MjSection),
+    Wrapper(MjWrapper),
+    Hero(MjHero),
+    #[cfg_attr(feature = "serde", serde(with = "custom_markup"))]
+    Custom(Box<dyn CustomComponent>),
+}
+
+impl MjBaseComponent for BodyNode {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
+        match self {
+            Self::Section(section) => section.render(writer, depth),
+            Self::Wrapper(wrapper) => wrapper.render(writer, depth),
+            Self::Hero(hero) => hero.render(writer, depth),
+            Self::Custom(custom) => custom.render(writer, depth),
         }
     }
 }
 
-pub trait MjBodySectionComponent {
-    fn to_section(&self) -> SectionCompatible;
+/// Children of `mj-section`.
+// columns are by far the most common child, boxing them would defeat the purpose of the enum
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum SectionNode {
+    Column(MjColumn),
+    Group(MjGroup),
+    #[cfg_attr(feature = "serde", serde(with = "custom_markup"))]
+    Custom(Box<dyn CustomComponent>),
+}
+
+impl MjBaseComponent for SectionNode {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
+        match self {
+            Self::Column(column) => column.render(writer, depth),
+            Self::Group(group) => group.render(writer, depth),
+            Self::Custom(custom) => custom.render(writer, depth),
+        }
+    }
 }
 
-pub enum ColumnCompatible<'a> {
-    Column(&'a MjColumn),
-    Group(&'a MjGroup),
+/// Children of `mj-column` and `mj-hero`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ColumnNode {
+    Accordion(MjAccordion),
+    Button(MjButton),
+    Carousel(MjCarousel),
+    Divider(MjDivider),
+    Image(MjImage),
+    Navbar(MjNavbar),
+    Raw(MjRaw),
+    Social(MjSocial),
+    Spacer(MjSpacer),
+    Table(MjTable),
+    Text(MjText),
+    #[cfg_attr(feature = "serde", serde(with = "custom_markup"))]
+    Custom(Box<dyn CustomComponent>),
 }
 
-impl<'a> MjBaseComponent for ColumnCompatible<'a> {
-    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::io::Result<()> {
+impl MjBaseComponent for ColumnNode {
+    fn render(&self, writer: &mut dyn Write, depth: usize) -> std::fmt::Result {
         match self {
-            ColumnCompatible::Column(c) => c.render(writer, depth),
-            ColumnCompatible::Group(g) => g.render(writer, depth),
+            Self::Accordion(accordion) => accordion.render(writer, depth),
+            Self::Button(button) => button.render(writer, depth),
+            Self::Carousel(carousel) => carousel.render(writer, depth),
+            Self::Divider(divider) => divider.render(writer, depth),
+            Self::Image(image) => image.render(writer, depth),
+            Self::Navbar(navbar) => navbar.render(writer, depth),
+            Self::Raw(raw) => raw.render_all(writer, depth),
+            Self::Social(social) => social.render(writer, depth),
+            Self::Spacer(spacer) => spacer.render(writer, depth),
+            Self::Table(table) => table.render(writer, depth),
+            Self::Text(text) => text.render(writer, depth),
+            Self::Custom(custom) => custom.render(writer, depth),
         }
     }
 }
 
-pub trait MjBodyColumnComponent {
-    fn to_column(&self) -> ColumnCompatible;
+macro_rules! impl_from_component {
+    ($node:ident, $($variant:ident($component:ty)),+) => {
+        $(
+            impl From<$component> for $node {
+                fn from(component: $component) -> Self {
+                    Self::$variant(component)
+                }
+            }
+        )+
+    };
 }
+
+impl_from_component!(
+    BodyNode,
+    Section(MjSection),
+    Wrapper(MjWrapper),
+    Hero(MjHero)
+);
+impl_from_component!(SectionNode, Column(MjColumn), Group(MjGroup));
+impl_from_component!(
+    ColumnNode,
+    Accordion(MjAccordion),
+    Button(MjButton),
+    Carousel(MjCarousel),
+    Divider(MjDivider),
+    Image(MjImage),
+    Navbar(MjNavbar),
+    Raw(MjRaw),
+    Social(MjSocial),
+    Spacer(MjSpacer),
+    Table(MjTable),
+    Text(MjText)
+);
+
+/// `mj-raw` outside of columns is kept as a custom component.
+impl From<MjRaw> for BodyNode {
+    fn from(raw: MjRaw) -> Self {
+        Self::Custom(Box::new(raw))
+    }
+}
+
+impl From<MjRaw> for SectionNode {
+    fn from(raw: MjRaw) -> Self {
+        Self::Custom(Box::new(raw))
+    }
+}
+
+macro_rules! impl_display {
+    ($($component:ty),+) => {
+        $(
+            impl Display for $component {
+                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    MjBaseComponent::render(self, f, 0)
+                }
+            }
+        )+
+    };
+}
+
+impl_display!(
+    BodyNode,
+    SectionNode,
+    ColumnNode,
+    MjAccordion,
+    MjAccordionElement,
+    MjAccordionElementText,
+    MjAccordionElementTitle,
+    MjAttributes,
+    MjBody,
+    MjButton,
+    MjCarousel,
+    MjCarouselImage,
+    MjColumn,
+    MjDivider,
+    MjGroup,
+    MjHead,
+    MjHero,
+    MjImage,
+    MjNavbar,
+    MjNavbarLink,
+    MjRaw,
+    MjRawFileStart,
+    MjSection,
+    MjSocial,
+    MjSocialElement,
+    MjSpacer,
+    MjTable,
+    MjText,
+    MjWrapper
+);
@@ -0,0 +1,673 @@
+//! Mobile overrides and device visibility of components, applied as media queries in `mj-style`.
+
+use crate::model::mj_button::MjButtonAttributes;
+use crate::model::mj_column::{MjColumn, MjColumnAttributes};
+use crate::model::mj_head::{MjHead, MjStyle, DEFAULT_BREAKPOINT};
+use crate::model::mj_image::MjImageAttributes;
+use crate::model::mj_section::MjSectionAttributes;
+use crate::model::mj_text::MjTextAttributes;
+use crate::model::mjml::Mjml;
+use crate::model::{
+    Alignment, BodyNode, ColumnNode, CssClass, DeviceVisibility, SectionNode, SizePx,
+};
+use std::borrow::Cow;
+
+/// Prefix of the classes generated for the mobile overrides.
+const MOBILE_CLASS: &str = "mjb-mobile-";
+/// Class hiding a component on mobile.
+const DESKTOP_ONLY_CLASS: &str = "mjb-desktop-only";
+/// Prefix of the classes hiding a component on desktop, followed by its display on mobile.
+const MOBILE_ONLY_CLASS: &str = "mjb-mobile-only-";
+
+impl Mjml {
+    /// Whether a component has mobile overrides or a device visibility to apply.
+    pub fn has_mobile_overrides(&self) -> bool {
+        let mut found = false;
+        visit(self, &mut |component| found |= component.is_responsive());
+        found
+    }
+
+    /// Moves the mobile overrides and the device visibilities into the document: components get
+    /// css classes, unique for the overrides, and the `mj-style` gets the rules applying them.
+    ///
+    /// The rules are appended to the last `mj-style` of the head that is not inline, as MJML
+    /// drops the media queries of inline styles, or to a new one.
+    pub fn apply_mobile_overrides(&mut self) {
+        let existing = self
+            .mj_head
+            .as_ref()
+            .map(|head| {
+                let styles = head.mj_style.iter().map(|style| style.content.as_str());
+                styles.collect::<Vec<_>>().join("\n")
+            })
+            .unwrap_or_default();
+        let is_new = |class: &str| !existing.contains(&format!(".{} ", class));
+        let mut desktop_rules = vec![];
+        let mut mobile_rules = vec![];
+        let mut visibility_classes = vec![];
+        let mut index = 1;
+        visit_mut(self, &mut |component| {
+            if let Some(visibility) = component.take_visibility() {
+                let class = match visibility {
+                    DeviceVisibility::DesktopOnly => DESKTOP_ONLY_CLASS.to_string(),
+                    DeviceVisibility::MobileOnly => {
+                        format!("{}{}", MOBILE_ONLY_CLASS, component.display())
+                    }
+                };
+                if is_new(&class) && !visibility_classes.contains(&class) {
+                    let (desktop, mobile) =
+                        visibility_rules(&class, visibility, component.display());
+                    desktop_rules.extend(desktop);
+                    mobile_rules.extend(mobile);
+                    visibility_classes.push(class.clone());
+                }
+                if !component.css_class().contains(&class) {
+                    component.css_class().push(class);
+                }
+            }
+
+            while !is_new(&format!("{}{}", MOBILE_CLASS, index)) {
+                index += 1;
+            }
+            let class = format!("{}{}", MOBILE_CLASS, index);
+            let rules = component.take_rules(&class);
+            if !rules.is_empty() {
+                mobile_rules.extend(rules);
+                component.css_class().push(class);
+                index += 1;
+            }
+        });
+        if desktop_rules.is_empty() && mobile_rules.is_empty() {
+            return;
+        }
+
+        let head = self.mj_head.get_or_insert_with(empty_head);
+        let breakpoint = head.mj_breakpoint.unwrap_or(DEFAULT_BREAKPOINT);
+        if head.mj_style.iter().all(|style| style.inline) {
+            head.mj_style.push(MjStyle {
+                inline: false,
+                content: String::new(),
+            });
+        }
+        let style = head
+            .mj_style
+            .iter_mut()
+            .rev()
+            .find(|style| !style.inline)
+            .expect("a style that is not inline");
+        for rule in desktop_rules {
+            if !style.content.is_empty() {
+                style.content.push('\n');
+            }
+            style.content.push_str(&rule);
+        }
+        if !mobile_rules.is_empty() {
+            if !style.content.is_empty() {
+                style.content.push('\n');
+            }
+            style
+                .content
+                .push_str(&media_query(breakpoint, &mobile_rules));
+        }
+    }
+
+    /// The document as rendered, with its mobile overrides applied.
+    pub(crate) fn responsive(&self) -> Cow<'_, Mjml> {
+        if !self.has_mobile_overrides() {
+            return Cow::Borrowed(self);
+        }
+        let mut responsive = self.clone();
+        responsive.apply_mobile_overrides();
+        Cow::Owned(responsive)
+    }
+}
+
+/// Wraps the rules in the media query MJML uses for mobile layouts: its desktop styles apply
+/// from the breakpoint, so mobile ends one pixel below it.
+fn media_query(breakpoint: SizePx, rules: &[String]) -> String {
+    let max_width = breakpoint.checked_sub(SizePx::new(1)).unwrap_or(breakpoint);
+    let mut css = format!("@media only screen and (max-width:{}) {{\n", max_width);
+    for rule in rules {
+        css.push_str("  ");
+        css.push_str(rule);
+        css.push('\n');
+    }
+    css.push('}');
+    css
+}
+
+/// Desktop and mobile rules of a visibility class.
+///
+/// Hidden elements also get `max-height: 0` and `overflow: hidden` for the clients ignoring
+/// `display: none`. Outlook does not read media queries, the components shown on mobile only are
+/// hidden from it by `mso-hide`, including the `-outlook` tables MJML renders for sections and
+/// columns.
+fn visibility_rules(
+    class: &str,
+    visibility: DeviceVisibility,
+    display: &str,
+) -> (Vec<String>, Vec<String>) {
+    let hidden = "display: none !important; max-height: 0 !important; overflow: hidden !important;";
+    match visibility {
+        DeviceVisibility::DesktopOnly => (vec![], vec![format!(".{} {{ {} }}", class, hidden)]),
+        DeviceVisibility::MobileOnly => (
+            vec![format!(
+                ".{class}, .{class}-outlook {{ {} mso-hide: all !important; }}",
+                hidden
+            )],
+            vec![format!(
+                ".{} {{ display: {} !important; max-height: none !important; overflow: visible !important; }}",
+                class, display
+            )],
+        ),
+    }
+}
+
+fn empty_head() -> MjHead {
+    MjHead {
+        mj_attributes: None,
+        mj_breakpoint: None,
+        mj_font: vec![],
+        mj_html_attributes: vec![],
+        mj_preview: None,
+        mj_style: vec![],
+        mj_title: None,
+    }
+}
+
+/// Attributes of a component that can hold mobile overrides or a device visibility.
+trait Responsive {
+    fn is_responsive(&self) -> bool;
+
+    fn take_visibility(&mut self) -> Option<DeviceVisibility>;
+
+    /// Removes the overrides, returning the css rules applying them to the component of `class`.
+    fn take_rules(&mut self, _class: &str) -> Vec<String> {
+        vec![]
+    }
+
+    /// Css display of the element holding the css class, restored when shown on mobile.
+    fn display(&self) -> &'static str {
+        // components in a column are wrapped in a table cell
+        "table-cell"
+    }
+
+    fn css_class(&mut self) -> &mut Vec<CssClass>;
+}
+
+/// Css rules of one component, every declaration is `!important` to beat the inline styles.
+struct Rules<'a> {
+    class: &'a str,
+    rules: Vec<String>,
+}
+
+impl<'a> Rules<'a> {
+    fn new(class: &'a str) -> Self {
+        Self {
+            class,
+            rules: vec![],
+        }
+    }
+
+    /// Adds a rule for the descendants of the component matching one of `selectors`, an empty
+    /// selector being the component itself. Unset declarations are left out.
+    fn add(&mut self, selectors: &[&str], declarations: &[(&str, Option<String>)]) {
+        let declarations = declarations
+            .iter()
+            .filter_map(|(name, value)| Some(format!("{}: {} !important;", name, value.as_ref()?)))
+            .collect::<Vec<_>>();
+        if declarations.is_empty() {
+            return;
+        }
+        let selectors = selectors
+            .iter()
+            .map(|selector| match selector {
+                &"" => format!(".{}", self.class),
+                selector => format!(".{} {}", self.class, selector),
+            })
+            .collect::<Vec<_>>();
+        self.rules.push(format!(
+            "{} {{ {} }}",
+            selectors.join(", "),
+            declarations.join(" ")
+        ));
+    }
+}
+
+impl Responsive for MjSectionAttributes {
+    fn is_responsive(&self) -> bool {
+        self.visibility.is_some()
+    }
+
+    fn take_visibility(&mut self) -> Option<DeviceVisibility> {
+        self.visibility.take()
+    }
+
+    fn display(&self) -> &'static str {
+        // full width sections set their class on a table
+        if self.full_width {
+            "table"
+        } else {
+            "block"
+        }
+    }
+
+    fn css_class(&mut self) -> &mut Vec<CssClass> {
+        &mut self.css_class
+    }
+}
+
+impl Responsive for MjColumnAttributes {
+    fn is_responsive(&self) -> bool {
+        self.visibility.is_some()
+    }
+
+    fn take_visibility(&mut self) -> Option<DeviceVisibility> {
+        self.visibility.take()
+    }
+
+    fn display(&self) -> &'static str {
+        "inline-block"
+    }
+
+    fn css_class(&mut self) -> &mut Vec<CssClass> {
+        &mut self.css_class
+    }
+}
+
+impl Responsive for MjImageAttributes {
+    fn is_responsive(&self) -> bool {
+        self.visibility.is_some()
+    }
+
+    fn take_visibility(&mut self) -> Option<DeviceVisibility> {
+        self.visibility.take()
+    }
+
+    fn css_class(&mut self) -> &mut Vec<CssClass> {
+        &mut self.css_class
+    }
+}
+
+impl Responsive for MjTextAttributes {
+    fn is_responsive(&self) -> bool {
+        self.mobile.is_some() || self.visibility.is_some()
+    }
+
+    fn take_visibility(&mut self) -> Option<DeviceVisibility> {
+        self.visibility.take()
+    }
+
+    fn take_rules(&mut self, class: &str) -> Vec<String> {
+        let Some(mobile) = self.mobile.take() else {
+            return vec![];
+        };
+        let mut rules = Rules::new(class);
+        // the class is set on the cell holding the padding, the text is in a `div`
+        rules.add(&[""], &[("padding", mobile.padding.map(|v| v.to_string()))]);
+        rules.add(
+            &["div"],
+            &[
+                ("font-size", mobile.font_size.map(|v| v.to_string())),
+                ("line-height", mobile.line_height.map(|v| v.to_string())),
+                ("text-align", mobile.align.map(|v| v.to_string())),
+            ],
+        );
+        rules.rules
+    }
+
+    fn css_class(&mut self) -> &mut Vec<CssClass> {
+        &mut self.css_class
+    }
+}
+
+impl Responsive for MjButtonAttributes {
+    fn is_responsive(&self) -> bool {
+        self.mobile.is_some() || self.visibility.is_some()
+    }
+
+    fn take_visibility(&mut self) -> Option<DeviceVisibility> {
+        self.visibility.take()
+    }
+
+    fn take_rules(&mut self, class: &str) -> Vec<String> {
+        let Some(mobile) = self.mobile.take() else {
+            return vec![];
+        };
+        let (margin_left, margin_right) = match mobile.align {
+            Some(Alignment::Left) => (Some("0"), Some("auto")),
+            Some(Alignment::Center) => (Some("auto"), Some("auto")),
+            Some(Alignment::Right) => (Some("auto"), Some("0")),
+            None => (None, None),
+        };
+        let mut rules = Rules::new(class);
+        rules.add(&[""], &[("padding", mobile.padding.map(|v| v.to_string()))]);
+        rules.add(
+            &["table"],
+            &[
+                ("width", mobile.width.map(|v| v.to_string())),
+                ("margin-left", margin_left.map(String::from)),
+                ("margin-right", margin_right.map(String::from)),
+            ],
+        );
+        // the link, or the paragraph without `href`, fills the resized button
+        let fill = mobile.width.map(|_| "block".to_string());
+        rules.add(
+            &["a", "p"],
+            &[
+                ("font-size", mobile.font_size.map(|v| v.to_string())),
+                ("display", fill.clone()),
+                ("width", fill.map(|_| "auto".to_string())),
+            ],
+        );
+        rules.rules
+    }
+
+    fn css_class(&mut self) -> &mut Vec<CssClass> {
+        &mut self.css_class
+    }
+}
+
+fn visit(mjml: &Mjml, f: &mut dyn FnMut(&dyn Responsive)) {
+    fn visit_column(column: &MjColumn, f: &mut dyn FnMut(&dyn Responsive)) {
+        f(&column.attributes);
+        column.content.iter().for_each(|node| visit_node(node, f));
+    }
+    fn visit_node(node: &ColumnNode, f: &mut dyn FnMut(&dyn Responsive)) {
+        match node {
+            ColumnNode::Button(button) => f(&button.attributes),
+            ColumnNode::Image(image) => f(&image.attributes),
+            ColumnNode::Text(text) => f(&text.attributes),
+            _ => {}
+        }
+    }
+    fn visit_body(node: &BodyNode, f: &mut dyn FnMut(&dyn Responsive)) {
+        match node {
+            BodyNode::Section(section) => {
+                f(&section.attributes);
+                for node in &section.content {
+                    match node {
+                        SectionNode::Column(column) => visit_column(column, f),
+                        SectionNode::Group(group) => group
+                            .columns
+                            .iter()
+                            .for_each(|column| visit_column(column, f)),
+                        SectionNode::Custom(_) => {}
+                    }
+                }
+            }
+            BodyNode::Wrapper(wrapper) => {
+                wrapper.content.iter().for_each(|node| visit_body(node, f))
+            }
+            BodyNode::Hero(hero) => hero.content.iter().for_each(|node| visit_node(node, f)),
+            BodyNode::Custom(_) => {}
+        }
+    }
+    mjml.mj_body
+        .content
+        .iter()
+        .for_each(|node| visit_body(node, f));
+}
+
+fn visit_mut(mjml: &mut Mjml, f: &mut dyn FnMut(&mut dyn Responsive)) {
+    fn visit_column(column: &mut MjColumn, f: &mut dyn FnMut(&mut dyn Responsive)) {
+        f(&mut column.attributes);
+        column
+            .content
+            .iter_mut()
+            .for_each(|node| visit_node(node, f));
+    }
+    fn visit_node(node: &mut ColumnNode, f: &mut dyn FnMut(&mut dyn Responsive)) {
+        match node {
+            ColumnNode::Button(button) => f(&mut button.attributes),
+            ColumnNode::Image(image) => f(&mut image.attributes),
+            ColumnNode::Text(text) => f(&mut text.attributes),
+            _ => {}
+        }
+    }
+    fn visit_body(node: &mut BodyNode, f: &mut dyn FnMut(&mut dyn Responsive)) {
+        match node {
+            BodyNode::Section(section) => {
+                f(&mut section.attributes);
+                for node in &mut section.content {
+                    match node {
+                        SectionNode::Column(column) => visit_column(column, f),
+                        SectionNode::Group(group) => group
+                            .columns
+                            .iter_mut()
+                            .for_each(|column| visit_column(column, f)),
+                        SectionNode::Custom(_) => {}
+                    }
+                }
+            }
+            BodyNode::Wrapper(wrapper) => wrapper
+                .content
+                .iter_mut()
+                .for_each(|node| visit_body(node, f)),
+            BodyNode::Hero(hero) => hero.content.iter_mut().for_each(|node| visit_node(node, f)),
+            BodyNode::Custom(_) => {}
+        }
+    }
+    mjml.mj_body
+        .content
+        .iter_mut()
+        .for_each(|node| visit_body(node, f));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::mj_button::{MjButton, MjButtonMobile};
+    use crate::model::mj_text::{MjText, MjTextAttributes, MjTextMobile};
+    use crate::model::mjml::Mjml;
+    use crate::model::{
+        Alignment, BodyNode, ColumnNode, DeviceVisibility, PxOrPercent, SectionNode, SizePx,
+        TextAlignment,
+    };
+
+    fn document() -> Mjml {
+        r#"<mjml><mj-head><mj-style>.title { color: red; }</mj-style></mj-head><mj-body><mj-section><mj-column>
+<mj-text css-class="title">Hello</mj-text>
+<mj-button href="https://example.com">Go</mj-button>
+<mj-text>Bye</mj-text>
+</mj-column></mj-section></mj-body></mjml>"#
+            .parse()
+            .unwrap()
+    }
+
+    fn column(mjml: &mut Mjml) -> &mut Vec<ColumnNode> {
+        let BodyNode::Section(section) = &mut mjml.mj_body.content[0] else {
+            panic!("expected a section");
+        };
+        let SectionNode::Column(column) = &mut section.content[0] else {
+            panic!("expected a column");
+        };
+        &mut column.content
+    }
+
+    #[test]
+    fn mobile_overrides() {
+        let mut mjml = document();
+        assert!(!mjml.has_mobile_overrides());
+        assert_eq!(mjml.to_string(), document().to_string());
+
+        let content = column(&mut mjml);
+        let ColumnNode::Text(MjText { attributes, .. }) = &mut content[0] else {
+            panic!("expected a text");
+        };
+        attributes.mobile = Some(MjTextMobile {
+            align: Some(TextAlignment::Center),
+            font_size: Some(SizePx::new(14)),
+            padding: Some("5px 10px".parse().unwrap()),
+            ..Default::default()
+        });
+        let ColumnNode::Button(MjButton { attributes, .. }) = &mut content[1] else {
+            panic!("expected a button");
+        };
+        attributes.mobile = Some(MjButtonMobile {
+            align: Some(Alignment::Left),
+            width: Some(PxOrPercent::percent(100.0)),
+            ..Default::default()
+        });
+        assert!(mjml.has_mobile_overrides());
+
+        let rendered = mjml.to_string();
+        assert!(rendered.contains("<mj-text css-class=\"title mjb-mobile-1\">"));
+        assert!(rendered.contains("<mj-button css-class=\"mjb-mobile-2\""));
+        assert!(rendered.contains("<mj-text>\nBye"));
+        assert!(rendered.contains(
+            ".title { color: red; }
+@media only screen and (max-width:479px) {
+  .mjb-mobile-1 { padding: 5px 10px !important; }
+  .mjb-mobile-1 div { font-size: 14px !important; text-align: center !important; }
+  .mjb-mobile-2 table { width: 100% !important; margin-left: 0 !important; margin-right: auto !important; }
+  .mjb-mobile-2 a, .mjb-mobile-2 p { display: block !important; width: auto !important; }
+}"
+        ));
+        // rendering leaves the model untouched
+        assert!(mjml.has_mobile_overrides());
+
+        // a rendered document keeps its classes when new overrides are added
+        let mut rendered = rendered.parse::<Mjml>().unwrap();
+        let ColumnNode::Text(MjText { attributes, .. }) = &mut column(&mut rendered)[2] else {
+            panic!("expected a text");
+        };
+        attributes.mobile = Some(MjTextMobile {
+            line_height: Some("1.2".parse().unwrap()),
+            ..Default::default()
+        });
+        rendered.apply_mobile_overrides();
+        assert!(!rendered.has_mobile_overrides());
+        let style = &rendered.mj_head.unwrap().mj_style[0].content;
+        assert!(style.ends_with(
+            "@media only screen and (max-width:479px) {
+  .mjb-mobile-3 div { line-height: 1.2 !important; }
+}"
+        ));
+    }
+
+    #[test]
+    fn mobile_overrides_breakpoint() {
+        let mut mjml: Mjml = r#"<mjml><mj-head><mj-breakpoint width="600px" /></mj-head><mj-body><mj-section><mj-column>
+<mj-text>Hello</mj-text>
+</mj-column></mj-section></mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        column(&mut mjml)[0] = ColumnNode::Text(MjText {
+            content: "Hello".to_string(),
+            attributes: MjTextAttributes {
+                mobile: Some(MjTextMobile {
+                    font_size: Some(SizePx::new(12)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        });
+        let mut chunks = mjml.render_chunks(usize::MAX);
+        let rendered = chunks.next_chunk().unwrap().unwrap();
+        assert!(rendered.contains("(max-width:599px)"));
+        assert!(rendered.contains("<mj-text css-class=\"mjb-mobile-1\">"));
+
+        let source = mjml.to_rust("template");
+        assert!(source.contains("mobile: Some(MjTextMobile {"));
+
+        let mut plain = mjml.clone();
+        plain.mj_head = None;
+        plain.apply_mobile_overrides();
+        assert!(plain.mj_head.unwrap().mj_style[0]
+            .content
+            .contains("(max-width:479px)"));
+
+        // media queries of inline styles are dropped by MJML
+        let mut inline: Mjml = r#"<mjml><mj-head><mj-style inline="inline">.a { color: red; }</mj-style></mj-head><mj-body><mj-section><mj-column>
+<mj-text>Hello</mj-text>
+</mj-column></mj-section></mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        column(&mut inline)[0] = column(&mut mjml)[0].clone();
+        let rendered = inline.to_string();
+        assert!(rendered.contains(
+            "<mj-style inline=\"inline\">
+.a { color: red; }
+"
+        ));
+        let rendered: Mjml = rendered.parse().unwrap();
+        let styles = rendered.mj_head.unwrap().mj_style;
+        assert_eq!(styles.len(), 2);
+        assert_eq!(styles[0].content, ".a { color: red; }");
+        assert!(!styles[1].inline);
+        assert!(styles[1]
+            .content
+            .contains(".mjb-mobile-1 div { font-size: 12px"));
+    }
+
+    #[test]
+    fn device_visibility() {
+        let mut mjml: Mjml = r#"<mjml><mj-body>
+<mj-section><mj-column><mj-image src="https://example.com/a.png" /><mj-text>Hello</mj-text></mj-column></mj-section>
+<mj-section full-width="full-width"><mj-column><mj-text>Bye</mj-text></mj-column></mj-section>
+</mj-body></mjml>"#
+            .parse()
+            .unwrap();
+        let BodyNode::Section(section) = &mut mjml.mj_body.content[0] else {
+            panic!("expected a section");
+        };
+        section.attributes.visibility = Some(DeviceVisibility::DesktopOnly);
+        let SectionNode::Column(column) = &mut section.content[0] else {
+            panic!("expected a column");
+        };
+        column.attributes.visibility = Some(DeviceVisibility::MobileOnly);
+        let ColumnNode::Image(image) = &mut column.content[0] else {
+            panic!("expected an image");
+        };
+        image.attributes.visibility = Some(DeviceVisibility::MobileOnly);
+        let ColumnNode::Text(text) = &mut column.content[1] else {
+            panic!("expected a text");
+        };
+        text.attributes.visibility = Some(DeviceVisibility::DesktopOnly);
+        text.attributes.mobile = Some(MjTextMobile {
+            font_size: Some(SizePx::new(12)),
+            ..Default::default()
+        });
+        let BodyNode::Section(section) = &mut mjml.mj_body.content[1] else {
+            panic!("expected a section");
+        };
+        section.attributes.visibility = Some(DeviceVisibility::MobileOnly);
+        assert!(mjml.has_mobile_overrides());
+
+        let rendered = mjml.to_string();
+        assert!(rendered.contains("<mj-section css-class=\"mjb-desktop-only\">"));
+        assert!(rendered.contains("<mj-column css-class=\"mjb-mobile-only-inline-block\">"));
+        assert!(rendered.contains("css-class=\"mjb-mobile-only-table-cell\""));
+        assert!(rendered.contains("<mj-text css-class=\"mjb-desktop-only mjb-mobile-1\">"));
+        assert!(rendered.contains("css-class=\"mjb-mobile-only-table\" full-width=\"full-width\""));
+        assert!(rendered.contains(
+            "
+.mjb-mobile-only-inline-block, .mjb-mobile-only-inline-block-outlook { display: none !important; max-height: 0 !important; overflow: hidden !important; mso-hide: all !important; }
+.mjb-mobile-only-table-cell, .mjb-mobile-only-table-cell-outlook { display: none !important; max-height: 0 !important; overflow: hidden !important; mso-hide: all !important; }
+.mjb-mobile-only-table, .mjb-mobile-only-table-outlook { display: none !important; max-height: 0 !important; overflow: hidden !important; mso-hide: all !important; }
+@media only screen and (max-width:479px) {
+  .mjb-desktop-only { display: none !important; max-height: 0 !important; overflow: hidden !important; }
+  .mjb-mobile-only-inline-block { display: inline-block !important; max-height: none !important; overflow: visible !important; }
+  .mjb-mobile-only-table-cell { display: table-cell !important; max-height: none !important; overflow: visible !important; }
+  .mjb-mobile-1 div { font-size: 12px !important; }
+  .mjb-mobile-only-table { display: table !important; max-height: none !important; overflow: visible !important; }
+}
+"
+        ));
+
+        // the rules of a rendered document are not repeated
+        let mut rendered = rendered.parse::<Mjml>().unwrap();
+        let BodyNode::Section(section) = &mut rendered.mj_body.content[1] else {
+            panic!("expected a section");
+        };
+        section.attributes.css_class.clear();
+        section.attributes.visibility = Some(DeviceVisibility::DesktopOnly);
+        let style = rendered.mj_head.as_ref().unwrap().mj_style.clone();
+        rendered.apply_mobile_overrides();
+        assert_eq!(rendered.mj_head.unwrap().mj_style, style);
+        let BodyNode::Section(section) = &rendered.mj_body.content[1] else {
+            panic!("expected a section");
+        };
+        assert_eq!(section.attributes.css_class, ["mjb-desktop-only"]);
+    }
+}
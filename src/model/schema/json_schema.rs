@@ -0,0 +1,185 @@
+use crate::model::schema::{components, ComponentSchema, ContentKind, ValueKind, ENUMS};
+use serde_json::{json, Map, Value};
+
+const SIZE: &str = "(0|[0-9]+(\\.[0-9]+)?px)";
+
+/// JSON Schema (draft 2020-12) of the MJML JSON nodes described by the registry.
+pub fn json_schema() -> Value {
+    let mut defs = Map::new();
+    defs.insert(
+        "Color".into(),
+        json!({
+            "type": "string",
+            "pattern": "^(#[0-9a-fA-F]{3,4}|#[0-9a-fA-F]{6}|#[0-9a-fA-F]{8}|[a-zA-Z]+|rgba?\\(.*\\))$"
+        }),
+    );
+    defs.insert("SizePx".into(), pattern(&format!("^{}$", SIZE)));
+    defs.insert(
+        "PxOrEm".into(),
+        pattern("^(0|-?[0-9]+(\\.[0-9]+)?(px|em))$"),
+    );
+    defs.insert(
+        "PxOrPercent".into(),
+        pattern("^(0|[0-9]+(\\.[0-9]+)?(px|%))$"),
+    );
+    defs.insert("LineHeight".into(), pattern("^[0-9]+(\\.[0-9]+)?(px|%)?$"));
+    defs.insert(
+        "Length".into(),
+        pattern("^-?[0-9]+(\\.[0-9]+)?(px|em|rem|%)?$"),
+    );
+    defs.insert(
+        "Padding".into(),
+        pattern(&format!("^{0}( {0}){{0,3}}$", SIZE)),
+    );
+    defs.insert("Border".into(), json!({ "type": "string" }));
+    defs.insert("Integer".into(), pattern("^-?[0-9]+$"));
+    for kind in ENUMS {
+        if let ValueKind::Enum { name, values } = kind {
+            defs.insert(name.to_string(), json!({ "enum": values }));
+        }
+    }
+
+    for component in components() {
+        let name = component.type_name();
+        if let Some(attributes) = attributes_schema(component) {
+            defs.insert(format!("{}Attributes", name), attributes);
+        }
+        defs.insert(name, component_schema(component));
+    }
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "MJML JSON",
+        "$ref": "#/$defs/Mjml",
+        "$defs": defs,
+    })
+}
+
+fn pattern(pattern: &str) -> Value {
+    json!({ "type": "string", "pattern": pattern })
+}
+
+fn attributes_schema(component: &ComponentSchema) -> Option<Value> {
+    match component.tag {
+        "mj-all" => Some(json!({
+            "type": "object",
+            "additionalProperties": { "type": "string" },
+        })),
+        "mj-class" => Some(json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"],
+            "additionalProperties": { "type": "string" },
+        })),
+        _ if component.attributes.is_empty() => None,
+        _ => {
+            let properties: Map<String, Value> = component
+                .attributes
+                .iter()
+                .map(|attribute| (attribute.name.to_string(), value_schema(attribute.kind)))
+                .collect();
+            let required: Vec<_> = component
+                .attributes
+                .iter()
+                .filter(|attribute| attribute.required)
+                .map(|attribute| attribute.name)
+                .collect();
+            Some(json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            }))
+        }
+    }
+}
+
+fn component_schema(component: &ComponentSchema) -> Value {
+    let name = component.type_name();
+    let mut properties = Map::new();
+    let mut required = vec!["tagName"];
+    properties.insert("tagName".into(), json!({ "const": component.tag }));
+
+    let has_attributes =
+        !component.attributes.is_empty() || matches!(component.tag, "mj-all" | "mj-class");
+    if has_attributes {
+        properties.insert(
+            "attributes".into(),
+            reference(&format!("{}Attributes", name)),
+        );
+        if component.tag == "mj-class"
+            || component
+                .attributes
+                .iter()
+                .any(|attribute| attribute.required)
+        {
+            required.push("attributes");
+        }
+    }
+
+    match component.content {
+        ContentKind::Empty => {}
+        ContentKind::Html => {
+            properties.insert("content".into(), json!({ "type": "string" }));
+        }
+        ContentKind::Children => {
+            let mut children: Vec<_> = component
+                .allowed_children()
+                .map(|child| reference(&child.type_name()))
+                .collect();
+            if component.tag == "mj-attributes" {
+                children.extend(
+                    components()
+                        .iter()
+                        .filter(|component| component.has_attribute_defaults())
+                        .map(|component| {
+                            json!({
+                                "type": "object",
+                                "properties": {
+                                    "tagName": { "const": component.tag },
+                                    "attributes": reference(&format!("{}Attributes", component.type_name())),
+                                },
+                                "required": ["tagName"],
+                                "additionalProperties": false,
+                            })
+                        }),
+                );
+            }
+            properties.insert(
+                "children".into(),
+                json!({ "type": "array", "items": { "oneOf": children } }),
+            );
+        }
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{}", name) })
+}
+
+fn value_schema(kind: ValueKind) -> Value {
+    match kind {
+        ValueKind::Color => reference("Color"),
+        ValueKind::SizePx => reference("SizePx"),
+        ValueKind::PxOrEm => reference("PxOrEm"),
+        ValueKind::PxOrPercent => reference("PxOrPercent"),
+        ValueKind::LineHeight => reference("LineHeight"),
+        ValueKind::Length => reference("Length"),
+        ValueKind::Padding => reference("Padding"),
+        ValueKind::Border => reference("Border"),
+        ValueKind::Integer => reference("Integer"),
+        ValueKind::Enum { name, .. } => reference(name),
+        ValueKind::Boolean => json!({ "enum": ["true", "false"] }),
+        ValueKind::Url => json!({ "type": "string", "format": "uri-reference" }),
+        ValueKind::String | ValueKind::CssClass | ValueKind::FontFamily => {
+            json!({ "type": "string" })
+        }
+    }
+}
@@ -0,0 +1,609 @@
+//! Static description of the components of the model, for tools that need to introspect it.
+
+#[cfg(feature = "json")]
+mod json_schema;
+mod typescript;
+
+use crate::model::mj_accordion::{
+    MjAccordionAttributes, MjAccordionElementAttributes, MjAccordionElementTextAttributes,
+    MjAccordionElementTitleAttributes,
+};
+use crate::model::mj_body::MjBodyAttributes;
+use crate::model::mj_button::MjButtonAttributes;
+use crate::model::mj_carousel::{MjCarouselAttributes, MjCarouselImageAttributes};
+use crate::model::mj_column::MjColumnAttributes;
+use crate::model::mj_divider::MjDividerAttributes;
+use crate::model::mj_group::MjGroupAttributes;
+use crate::model::mj_hero::MjHeroAttributes;
+use crate::model::mj_image::MjImageAttributes;
+use crate::model::mj_navbar::{MjNavbarAttributes, MjNavbarLinkAttributes};
+use crate::model::mj_section::MjSectionAttributes;
+use crate::model::mj_social::{MjSocialAttributes, MjSocialElementAttributes};
+use crate::model::mj_spacer::MjSpacerAttributes;
+use crate::model::mj_table::MjTableAttributes;
+use crate::model::mj_text::MjTextAttributes;
+use crate::model::mj_wrapper::MjWrapperAttributes;
+use crate::model::mjml::MjmlAttributes;
+
+#[cfg(feature = "json")]
+pub use json_schema::json_schema;
+pub use typescript::typescript_definitions;
+
+/// Kind of value accepted by an attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Color,
+    SizePx,
+    PxOrEm,
+    PxOrPercent,
+    LineHeight,
+    Length,
+    Padding,
+    Border,
+    Integer,
+    Boolean,
+    String,
+    Url,
+    CssClass,
+    FontFamily,
+    Enum {
+        name: &'static str,
+        values: &'static [&'static str],
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttributeSchema {
+    pub name: &'static str,
+    pub kind: ValueKind,
+    /// Value used by MJML when the attribute is not set.
+    pub default: Option<&'static str>,
+    pub required: bool,
+}
+
+/// Attributes of a component, implemented by `#[derive(Attributes)]`.
+pub trait AttributesSchema {
+    const ATTRIBUTES: &'static [AttributeSchema];
+}
+
+/// Joins the attributes of a struct and of a nested struct, `N` is the sum of both lengths.
+pub(crate) const fn concat<const N: usize>(
+    first: &[AttributeSchema],
+    second: &[AttributeSchema],
+) -> [AttributeSchema; N] {
+    let mut out = [attribute("", ValueKind::String, None); N];
+    let mut i = 0;
+    while i < N {
+        out[i] = if i < first.len() {
+            first[i]
+        } else {
+            second[i - first.len()]
+        };
+        i += 1;
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    /// Self-closing tag.
+    Empty,
+    /// Raw html or text.
+    Html,
+    /// Other components only.
+    Children,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentSchema {
+    pub tag: &'static str,
+    pub attributes: &'static [AttributeSchema],
+    pub allowed_parents: &'static [&'static str],
+    pub content: ContentKind,
+}
+
+impl ComponentSchema {
+    pub fn attribute(&self, name: &str) -> Option<&'static AttributeSchema> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    pub fn allowed_children(&self) -> impl Iterator<Item = &'static ComponentSchema> + '_ {
+        components()
+            .iter()
+            .filter(|component| component.allowed_parents.contains(&self.tag))
+    }
+
+    /// Name of the exported type, `mj-navbar-link` becomes `MjNavbarLink`.
+    pub fn type_name(&self) -> String {
+        self.tag
+            .split('-')
+            .map(|part| {
+                let mut chars = part.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Whether default values for this component can be set in `mj-attributes`.
+    pub fn has_attribute_defaults(&self) -> bool {
+        !self.attributes.is_empty() && !matches!(self.tag, "mjml" | "mj-body") && !self.in_head()
+    }
+
+    fn in_head(&self) -> bool {
+        self.allowed_parents.iter().any(|parent| {
+            *parent == "mj-head" || component(parent).is_some_and(|parent| parent.in_head())
+        })
+    }
+}
+
+/// All the components of the model, in document order.
+pub fn components() -> &'static [ComponentSchema] {
+    COMPONENTS
+}
+
+pub fn component(tag: &str) -> Option<&'static ComponentSchema> {
+    COMPONENTS.iter().find(|component| component.tag == tag)
+}
+
+pub const ALIGNMENT: ValueKind = ValueKind::Enum {
+    name: "Alignment",
+    values: &["left", "center", "right"],
+};
+pub const TEXT_ALIGNMENT: ValueKind = ValueKind::Enum {
+    name: "TextAlignment",
+    values: &["left", "center", "right", "justify"],
+};
+pub const VERTICAL_ALIGNMENT: ValueKind = ValueKind::Enum {
+    name: "VerticalAlignment",
+    values: &["top", "center", "bottom"],
+};
+pub const BORDER_STYLE: ValueKind = ValueKind::Enum {
+    name: "BorderStyle",
+    values: &[
+        "dashed", "dotted", "double", "groove", "inset", "outset", "ridge", "solid",
+    ],
+};
+pub const FONT_STYLE: ValueKind = ValueKind::Enum {
+    name: "FontStyle",
+    values: &["normal", "italic", "oblique"],
+};
+pub const TEXT_DECORATION: ValueKind = ValueKind::Enum {
+    name: "TextDecoration",
+    values: &["underline", "overline", "line-through", "none"],
+};
+pub const TEXT_TRANSFORM: ValueKind = ValueKind::Enum {
+    name: "TextTransform",
+    values: &["capitalize", "uppercase", "lowercase", "none"],
+};
+pub const DIRECTION: ValueKind = ValueKind::Enum {
+    name: "Direction",
+    values: &["ltr", "rtl"],
+};
+pub const ICON_POSITION: ValueKind = ValueKind::Enum {
+    name: "IconPosition",
+    values: &["left", "right"],
+};
+pub const TABLE_LAYOUT: ValueKind = ValueKind::Enum {
+    name: "TableLayout",
+    values: &["auto", "fixed", "initial", "inherit"],
+};
+pub const SOCIAL_MODE: ValueKind = ValueKind::Enum {
+    name: "SocialMode",
+    values: &["vertical", "horizontal"],
+};
+pub const VISIBILITY: ValueKind = ValueKind::Enum {
+    name: "Visibility",
+    values: &["visible", "hidden"],
+};
+pub const HERO_MODE: ValueKind = ValueKind::Enum {
+    name: "HeroMode",
+    values: &["fluid-height", "fixed-height"],
+};
+pub const BACKGROUND_REPEAT: ValueKind = ValueKind::Enum {
+    name: "BackgroundRepeat",
+    values: &["repeat", "no-repeat"],
+};
+pub const OWA: ValueKind = ValueKind::Enum {
+    name: "Owa",
+    values: &["desktop"],
+};
+
+/// Every enum used by the attributes.
+pub const ENUMS: &[ValueKind] = &[
+    ALIGNMENT,
+    TEXT_ALIGNMENT,
+    VERTICAL_ALIGNMENT,
+    BORDER_STYLE,
+    FONT_STYLE,
+    TEXT_DECORATION,
+    TEXT_TRANSFORM,
+    DIRECTION,
+    ICON_POSITION,
+    TABLE_LAYOUT,
+    SOCIAL_MODE,
+    VISIBILITY,
+    HERO_MODE,
+    BACKGROUND_REPEAT,
+    OWA,
+];
+
+pub(crate) const fn attribute(
+    name: &'static str,
+    kind: ValueKind,
+    default: Option<&'static str>,
+) -> AttributeSchema {
+    AttributeSchema {
+        name,
+        kind,
+        default,
+        required: false,
+    }
+}
+
+const fn required(name: &'static str, kind: ValueKind) -> AttributeSchema {
+    AttributeSchema {
+        name,
+        kind,
+        default: None,
+        required: true,
+    }
+}
+
+const BODY_CHILDREN_PARENTS: &[&str] = &["mj-body", "mj-wrapper"];
+const COLUMN_CHILDREN_PARENTS: &[&str] = &["mj-column", "mj-hero"];
+
+const COMPONENTS: &[ComponentSchema] = &[
+    ComponentSchema {
+        tag: "mjml",
+        attributes: MjmlAttributes::ATTRIBUTES,
+        allowed_parents: &[],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-head",
+        attributes: &[],
+        allowed_parents: &["mjml"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-attributes",
+        attributes: &[],
+        allowed_parents: &["mj-head"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-all",
+        attributes: &[],
+        allowed_parents: &["mj-attributes"],
+        content: ContentKind::Empty,
+    },
+    ComponentSchema {
+        tag: "mj-class",
+        attributes: &[required("name", ValueKind::String)],
+        allowed_parents: &["mj-attributes"],
+        content: ContentKind::Empty,
+    },
+    ComponentSchema {
+        tag: "mj-breakpoint",
+        attributes: &[attribute("width", ValueKind::SizePx, Some("480px"))],
+        allowed_parents: &["mj-head"],
+        content: ContentKind::Empty,
+    },
+    ComponentSchema {
+        tag: "mj-font",
+        attributes: &[
+            required("name", ValueKind::String),
+            required("href", ValueKind::Url),
+        ],
+        allowed_parents: &["mj-head"],
+        content: ContentKind::Empty,
+    },
+    ComponentSchema {
+        tag: "mj-html-attributes",
+        attributes: &[],
+        allowed_parents: &["mj-head"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-selector",
+        attributes: &[required("path", ValueKind::String)],
+        allowed_parents: &["mj-html-attributes"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-html-attribute",
+        attributes: &[required("name", ValueKind::String)],
+        allowed_parents: &["mj-selector"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-preview",
+        attributes: &[],
+        allowed_parents: &["mj-head"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-style",
+        attributes: &[attribute("inline", ValueKind::Boolean, None)],
+        allowed_parents: &["mj-head"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-title",
+        attributes: &[],
+        allowed_parents: &["mj-head"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-body",
+        attributes: MjBodyAttributes::ATTRIBUTES,
+        allowed_parents: &["mjml"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-wrapper",
+        attributes: MjWrapperAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-body"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-section",
+        attributes: MjSectionAttributes::ATTRIBUTES,
+        allowed_parents: BODY_CHILDREN_PARENTS,
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-hero",
+        attributes: MjHeroAttributes::ATTRIBUTES,
+        allowed_parents: BODY_CHILDREN_PARENTS,
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-group",
+        attributes: MjGroupAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-section"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-column",
+        attributes: MjColumnAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-section", "mj-group"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-accordion",
+        attributes: MjAccordionAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-accordion-element",
+        attributes: MjAccordionElementAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-accordion"],
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-accordion-title",
+        attributes: MjAccordionElementTitleAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-accordion-element"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-accordion-text",
+        attributes: MjAccordionElementTextAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-accordion-element"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-button",
+        attributes: MjButtonAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-carousel",
+        attributes: MjCarouselAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-carousel-image",
+        attributes: MjCarouselImageAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-carousel"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-divider",
+        attributes: MjDividerAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Empty,
+    },
+    ComponentSchema {
+        tag: "mj-image",
+        attributes: MjImageAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Empty,
+    },
+    ComponentSchema {
+        tag: "mj-navbar",
+        attributes: MjNavbarAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-navbar-link",
+        attributes: MjNavbarLinkAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-navbar"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-raw",
+        attributes: &[],
+        allowed_parents: &[
+            "mjml",
+            "mj-body",
+            "mj-wrapper",
+            "mj-section",
+            "mj-column",
+            "mj-hero",
+        ],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-social",
+        attributes: MjSocialAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Children,
+    },
+    ComponentSchema {
+        tag: "mj-social-element",
+        attributes: MjSocialElementAttributes::ATTRIBUTES,
+        allowed_parents: &["mj-social"],
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-spacer",
+        attributes: MjSpacerAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Empty,
+    },
+    ComponentSchema {
+        tag: "mj-table",
+        attributes: MjTableAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Html,
+    },
+    ComponentSchema {
+        tag: "mj-text",
+        attributes: MjTextAttributes::ATTRIBUTES,
+        allowed_parents: COLUMN_CHILDREN_PARENTS,
+        content: ContentKind::Html,
+    },
+];
+
+#[cfg(test)]
+mod test {
+    use crate::model::schema::{
+        component, components, ContentKind, ValueKind, ALIGNMENT, BORDER_STYLE, FONT_STYLE,
+        TEXT_ALIGNMENT, TEXT_DECORATION, TEXT_TRANSFORM,
+    };
+    use crate::model::utils::{
+        Alignment, BorderStyle, FontStyle, TextAlignment, TextDecoration, TextTransform,
+    };
+    use std::collections::HashSet;
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    #[test]
+    fn tags_are_unique_and_parents_exist() {
+        let tags: HashSet<_> = components().iter().map(|component| component.tag).collect();
+        assert_eq!(tags.len(), components().len());
+        for component in components() {
+            for parent in component.allowed_parents {
+                assert!(tags.contains(parent), "unknown parent {}", parent);
+            }
+        }
+    }
+
+    #[test]
+    fn enum_defaults_are_valid() {
+        for component in components() {
+            for attribute in component.attributes {
+                if let (ValueKind::Enum { values, .. }, Some(default)) =
+                    (attribute.kind, attribute.default)
+                {
+                    assert!(
+                        values.contains(&default),
+                        "{} {}",
+                        component.tag,
+                        attribute.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn enum_values_parse() {
+        fn round_trip<T: FromStr + Display>(kind: ValueKind) {
+            let ValueKind::Enum { name, values } = kind else {
+                panic!("expected an enum");
+            };
+            for value in values {
+                let parsed = value
+                    .parse::<T>()
+                    .unwrap_or_else(|_| panic!("{} {}", name, value));
+                assert_eq!(parsed.to_string(), *value);
+            }
+        }
+        round_trip::<Alignment>(ALIGNMENT);
+        round_trip::<BorderStyle>(BORDER_STYLE);
+        round_trip::<FontStyle>(FONT_STYLE);
+        round_trip::<TextAlignment>(TEXT_ALIGNMENT);
+        round_trip::<TextDecoration>(TEXT_DECORATION);
+        round_trip::<TextTransform>(TEXT_TRANSFORM);
+        assert_eq!("lineThrough".parse(), Ok(TextDecoration::LineThrough));
+    }
+
+    #[test]
+    fn lookup() {
+        let text = component("mj-text").unwrap();
+        assert_eq!(text.content, ContentKind::Html);
+        assert_eq!(text.attribute("font-size").unwrap().kind, ValueKind::SizePx);
+        assert_eq!(text.attribute("color").unwrap().default, Some("#000000"));
+
+        let column_children: Vec<_> = component("mj-column")
+            .unwrap()
+            .allowed_children()
+            .map(|component| component.tag)
+            .collect();
+        assert!(column_children.contains(&"mj-text"));
+        assert!(!column_children.contains(&"mj-section"));
+        for parent in [
+            "mj-body",
+            "mj-wrapper",
+            "mj-section",
+            "mj-column",
+            "mj-hero",
+        ] {
+            assert!(component(parent)
+                .unwrap()
+                .allowed_children()
+                .any(|child| child.tag == "mj-raw"));
+        }
+    }
+
+    #[test]
+    fn typescript() {
+        let definitions = super::typescript_definitions();
+        assert!(definitions.contains("export interface MjText {"));
+        assert!(definitions.contains("  \"font-size\"?: SizePx;"));
+        assert!(definitions.contains("  \"src\": string;"));
+        assert!(definitions.contains("export type Alignment = "));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_schema() {
+        let schema = super::json_schema();
+        let text = &schema["$defs"]["MjText"];
+        assert_eq!(text["properties"]["tagName"]["const"], "mj-text");
+        assert_eq!(
+            schema["$defs"]["MjTextAttributes"]["properties"]["font-size"]["$ref"],
+            "#/$defs/SizePx"
+        );
+        for reference in schema["$defs"]["MjColumn"]["properties"]["children"]["items"]["oneOf"]
+            .as_array()
+            .unwrap()
+        {
+            let name = reference["$ref"].as_str().unwrap();
+            let name = name.trim_start_matches("#/$defs/");
+            assert!(schema["$defs"].get(name).is_some(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::model::schema::{components, ComponentSchema, ContentKind, ValueKind, ENUMS};
+use std::fmt::Write;
+
+const VALUE_TYPES: &str = r#"export type Color = string;
+export type SizePx = `${number}px` | "0";
+export type PxOrEm = `${number}px` | `${number}em` | "0";
+export type PxOrPercent = `${number}px` | `${number}%` | "0";
+export type LineHeight = `${number}px` | `${number}%` | `${number}`;
+export type Length = `${number}${"px" | "em" | "rem" | "%" | ""}`;
+export type Padding = string;
+export type Border = string;
+export type Integer = `${number}`;
+"#;
+
+/// TypeScript declarations (`.d.ts`) of the MJML JSON nodes described by the registry.
+pub fn typescript_definitions() -> String {
+    let mut out = String::new();
+    // writing into a String cannot fail
+    render(&mut out).unwrap();
+    out
+}
+
+fn render(out: &mut String) -> std::fmt::Result {
+    writeln!(out, "// Generated by mjml-builder, do not edit.")?;
+    writeln!(out)?;
+    out.push_str(VALUE_TYPES);
+    for kind in ENUMS {
+        if let ValueKind::Enum { name, values } = kind {
+            let values: Vec<_> = values.iter().map(|value| format!("{:?}", value)).collect();
+            writeln!(out, "export type {} = {};", name, values.join(" | "))?;
+        }
+    }
+
+    for component in components() {
+        writeln!(out)?;
+        render_component(out, component)?;
+    }
+
+    Ok(())
+}
+
+fn render_component(out: &mut String, component: &ComponentSchema) -> std::fmt::Result {
+    let name = component.type_name();
+    let attributes = match component.tag {
+        "mj-all" => Some(("Record<string, string>".to_string(), false)),
+        "mj-class" => Some((
+            "{ name: string } & Record<string, string>".to_string(),
+            true,
+        )),
+        _ if component.attributes.is_empty() => None,
+        _ => {
+            writeln!(out, "export interface {}Attributes {{", name)?;
+            for attribute in component.attributes {
+                let optional = if attribute.required { "" } else { "?" };
+                writeln!(
+                    out,
+                    "  {:?}{}: {};",
+                    attribute.name,
+                    optional,
+                    value_type(attribute.kind)
+                )?;
+            }
+            writeln!(out, "}}")?;
+            let required = component.attributes.iter().any(|attr| attr.required);
+            Some((format!("{}Attributes", name), required))
+        }
+    };
+
+    writeln!(out, "export interface {} {{", name)?;
+    writeln!(out, "  tagName: {:?};", component.tag)?;
+    if let Some((attributes, required)) = attributes {
+        let optional = if required { "" } else { "?" };
+        writeln!(out, "  attributes{}: {};", optional, attributes)?;
+    }
+    match component.content {
+        ContentKind::Empty => {}
+        ContentKind::Html => writeln!(out, "  content?: string;")?,
+        ContentKind::Children => {
+            let mut children: Vec<_> = component
+                .allowed_children()
+                .map(|child| child.type_name())
+                .collect();
+            if component.tag == "mj-attributes" {
+                children.extend(
+                    components()
+                        .iter()
+                        .filter(|component| component.has_attribute_defaults())
+                        .map(|component| {
+                            format!(
+                                "{{ tagName: {:?}; attributes?: {}Attributes }}",
+                                component.tag,
+                                component.type_name()
+                            )
+                        }),
+                );
+            }
+            writeln!(out, "  children?: Array<{}>;", children.join(" | "))?;
+        }
+    }
+    writeln!(out, "}}")
+}
+
+fn value_type(kind: ValueKind) -> &'static str {
+    match kind {
+        ValueKind::Color => "Color",
+        ValueKind::SizePx => "SizePx",
+        ValueKind::PxOrEm => "PxOrEm",
+        ValueKind::PxOrPercent => "PxOrPercent",
+        ValueKind::LineHeight => "LineHeight",
+        ValueKind::Length => "Length",
+        ValueKind::Padding => "Padding",
+        ValueKind::Border => "Border",
+        ValueKind::Integer => "Integer",
+        ValueKind::Enum { name, .. } => name,
+        ValueKind::Boolean => "\"true\" | \"false\"",
+        ValueKind::String | ValueKind::Url | ValueKind::CssClass | ValueKind::FontFamily => {
+            "string"
+        }
+    }
+}
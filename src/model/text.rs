@@ -0,0 +1,240 @@
+//! Plain-text version of a document, for the text part of multipart emails.
+
+use crate::model::mj_body::MjBody;
+use crate::model::mjml::Mjml;
+use crate::model::{BodyNode, ColumnNode, SectionNode};
+
+const DIVIDER: &str = "----";
+
+impl Mjml {
+    /// Text of the body, one paragraph per block, with links written after their label.
+    ///
+    /// Markup is stripped from the content, `<br>` and block tags become line breaks. Images only
+    /// show their `alt` text, custom components and raw markup are left out.
+    pub fn to_plain_text(&self) -> String {
+        let mut blocks = Vec::new();
+        body_text(&self.mj_body, &mut blocks);
+        let mut text = blocks.join("\n\n");
+        text.push('\n');
+        text
+    }
+}
+
+fn body_text(body: &MjBody, blocks: &mut Vec<String>) {
+    for node in &body.content {
+        body_node_text(node, blocks);
+    }
+}
+
+fn body_node_text(node: &BodyNode, blocks: &mut Vec<String>) {
+    match node {
+        BodyNode::Section(section) => {
+            for node in &section.content {
+                match node {
+                    SectionNode::Column(column) => columns_text(&column.content, blocks),
+                    SectionNode::Group(group) => {
+                        for column in &group.columns {
+                            columns_text(&column.content, blocks);
+                        }
+                    }
+                    SectionNode::Custom(_) => {}
+                }
+            }
+        }
+        BodyNode::Wrapper(wrapper) => {
+            for node in &wrapper.content {
+                body_node_text(node, blocks);
+            }
+        }
+        BodyNode::Hero(hero) => columns_text(&hero.content, blocks),
+        BodyNode::Custom(_) => {}
+    }
+}
+
+fn columns_text(nodes: &[ColumnNode], blocks: &mut Vec<String>) {
+    for node in nodes {
+        let block = match node {
+            ColumnNode::Accordion(accordion) => accordion
+                .elements
+                .iter()
+                .map(|element| {
+                    format!(
+                        "{}\n{}",
+                        html_to_text(&element.title.content),
+                        html_to_text(&element.text.content)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            ColumnNode::Button(button) => {
+                link_text(&html_to_text(&button.content), &button.attributes.href)
+            }
+            ColumnNode::Carousel(carousel) => carousel
+                .images
+                .iter()
+                .filter_map(|image| image.attributes.alt.clone())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ColumnNode::Divider(_) => DIVIDER.to_string(),
+            ColumnNode::Image(image) => image.attributes.alt.clone().unwrap_or_default(),
+            ColumnNode::Navbar(navbar) => navbar
+                .links
+                .iter()
+                .map(|link| {
+                    let href = link.attributes.href.as_ref().map(|href| {
+                        format!(
+                            "{}{}",
+                            navbar.attributes.base_url.as_deref().unwrap_or_default(),
+                            href
+                        )
+                    });
+                    link_text(&html_to_text(&link.content), &href)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ColumnNode::Social(social) => social
+                .elements
+                .iter()
+                .map(|element| link_text(&html_to_text(&element.content), &element.attributes.href))
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ColumnNode::Table(table) => html_to_text(&table.content),
+            ColumnNode::Text(text) => html_to_text(&text.content),
+            ColumnNode::Raw(_) | ColumnNode::Spacer(_) | ColumnNode::Custom(_) => continue,
+        };
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+    }
+}
+
+fn link_text(label: &str, href: &Option<String>) -> String {
+    match href {
+        Some(href) if label.is_empty() => href.clone(),
+        Some(href) if label != href => format!("{} ({})", label, href),
+        _ => label.to_string(),
+    }
+}
+
+/// Strips the tags of `html`, keeping the line breaks they imply and the target of links.
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut link: Option<(String, usize)> = None;
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match name.as_str() {
+            "br" | "p" | "div" | "li" | "tr" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                text.push('\n')
+            }
+            "a" if !closing => link = href(tag).map(|href| (href, text.len())),
+            "a" => {
+                if let Some((href, label_start)) = link.take() {
+                    let label = text[label_start..].trim().to_string();
+                    text.truncate(label_start);
+                    text.push_str(&link_text(&decode_entities(&label), &Some(href)));
+                }
+            }
+            _ => {}
+        }
+    }
+    text.push_str(rest);
+    normalize(&decode_entities(&text))
+}
+
+fn href(tag: &str) -> Option<String> {
+    let position = tag.find("href=")?;
+    let value = &tag[position + "href=".len()..];
+    let value = match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+        _ => value.split(|c: char| c.is_whitespace()).next()?,
+    };
+    Some(value.to_string())
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Collapses the spaces of each line and the empty lines, the way html would show them.
+fn normalize(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::mjml::Mjml;
+
+    #[test]
+    fn plain_text() {
+        let mjml: Mjml = r#"<mjml>
+  <mj-body>
+    <mj-section>
+      <mj-column>
+        <mj-image src="https://example.com/logo.png" alt="Example" />
+        <mj-text>
+          <h1>Hello   Ada</h1>
+          <p>Your order is <b>on its way</b>.<br/>Track it <a href="https://example.com/track">here</a>.</p>
+        </mj-text>
+        <mj-divider />
+        <mj-button href="https://example.com/account">My account</mj-button>
+      </mj-column>
+    </mj-section>
+    <mj-section>
+      <mj-column>
+        <mj-text>Fish &amp; chips</mj-text>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>"#
+            .parse()
+            .unwrap();
+        assert_eq!(
+            mjml.to_plain_text(),
+            "Example
+
+Hello Ada
+
+Your order is on its way.
+Track it here (https://example.com/track).
+
+----
+
+My account (https://example.com/account)
+
+Fish & chips
+"
+        );
+    }
+}
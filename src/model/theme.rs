@@ -0,0 +1,183 @@
+//! Brand values compiled into `mj-attributes`, so that switching brand means swapping the theme.
+
+use crate::model::mj_button::MjButtonAttributes;
+use crate::model::mj_head::{MjAttribute, MjAttributes, MjClass};
+use crate::model::mj_section::MjSectionAttributes;
+use crate::model::mj_text::MjTextAttributes;
+use crate::model::{Color, Padding, SizePx};
+use std::collections::BTreeMap;
+
+/// `mj-class` giving text the primary color.
+pub const PRIMARY_CLASS: &str = "primary";
+/// `mj-class` for secondary text, in the secondary color and the small font size.
+pub const MUTED_CLASS: &str = "muted";
+/// `mj-class` for titles, in the heading font.
+pub const HEADING_CLASS: &str = "heading";
+
+const HEADING_FONT_WEIGHT: u32 = 700;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Theme {
+    /// Background of the buttons and color of the `primary` class.
+    pub primary_color: Color,
+    /// Color of the `muted` class.
+    pub secondary_color: Color,
+    pub text_color: Color,
+    /// Text of the buttons, drawn over the primary color.
+    pub button_text_color: Color,
+    pub font_family: Vec<String>,
+    pub heading_font_family: Vec<String>,
+    pub font_size: SizePx,
+    pub small_font_size: SizePx,
+    pub heading_font_size: SizePx,
+    pub button_radius: SizePx,
+    pub section_padding: Padding,
+}
+
+impl Theme {
+    pub fn to_mj_attributes(&self) -> MjAttributes {
+        let class = |name: &str, attributes: &[(&str, String)]| {
+            MjAttribute::MjClass(MjClass {
+                name: name.to_string(),
+                attributes: attributes
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.clone()))
+                    .collect(),
+            })
+        };
+
+        MjAttributes {
+            content: vec![
+                MjAttribute::MjAll(BTreeMap::from([(
+                    "font-family".to_string(),
+                    self.font_family.join(", "),
+                )])),
+                MjAttribute::MjText(MjTextAttributes {
+                    color: Some(self.text_color),
+                    font_size: Some(self.font_size),
+                    ..Default::default()
+                }),
+                MjAttribute::MjButton(MjButtonAttributes {
+                    background_color: Some(self.primary_color),
+                    color: Some(self.button_text_color),
+                    border_radius: Some(self.button_radius),
+                    font_size: Some(self.font_size),
+                    ..Default::default()
+                }),
+                MjAttribute::MjSection(MjSectionAttributes {
+                    padding: Some(self.section_padding),
+                    ..Default::default()
+                }),
+                class(PRIMARY_CLASS, &[("color", self.primary_color.to_string())]),
+                class(
+                    MUTED_CLASS,
+                    &[
+                        ("color", self.secondary_color.to_string()),
+                        ("font-size", self.small_font_size.to_string()),
+                    ],
+                ),
+                class(
+                    HEADING_CLASS,
+                    &[
+                        ("font-family", self.heading_font_family.join(", ")),
+                        ("font-size", self.heading_font_size.to_string()),
+                        ("font-weight", HEADING_FONT_WEIGHT.to_string()),
+                    ],
+                ),
+            ],
+        }
+    }
+}
+
+impl From<&Theme> for MjAttributes {
+    fn from(theme: &Theme) -> Self {
+        theme.to_mj_attributes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::mj_head::{MjAttribute, MjHead};
+    use crate::model::mj_text::{MjText, MjTextAttributes};
+    use crate::model::theme::{Theme, HEADING_CLASS};
+    use crate::model::{Color, Padding, PaddingValue, SizePx};
+
+    fn theme(primary_color: Color) -> Theme {
+        Theme {
+            primary_color,
+            secondary_color: Color::rgb(0x88, 0x88, 0x88),
+            text_color: Color::rgb(0x22, 0x22, 0x22),
+            button_text_color: Color::WHITE,
+            font_family: vec![
+                "Inter".to_string(),
+                "Arial".to_string(),
+                "sans-serif".to_string(),
+            ],
+            heading_font_family: vec!["Georgia".to_string(), "serif".to_string()],
+            font_size: SizePx::new(16),
+            small_font_size: SizePx::new(12),
+            heading_font_size: SizePx::new(28),
+            button_radius: SizePx::new(6),
+            section_padding: Padding {
+                top: PaddingValue::Px(SizePx::new(24)),
+                right: PaddingValue::Px(SizePx::new(0)),
+                bottom: PaddingValue::Px(SizePx::new(24)),
+                left: PaddingValue::Px(SizePx::new(0)),
+            },
+        }
+    }
+
+    #[test]
+    fn theme_attributes() {
+        let attributes = theme(Color::rgb(0x00, 0x66, 0xFF)).to_mj_attributes();
+        let rendered = attributes.to_string();
+        assert!(rendered.contains("<mj-all font-family=\"Inter, Arial, sans-serif\"/>"));
+        assert!(rendered.contains("<mj-text color=\"#222222\" font-size=\"16px\"/>"));
+        assert!(rendered.contains(
+            "<mj-button background-color=\"#0066FF\" border-radius=\"6px\" color=\"#FFFFFF\" font-size=\"16px\"/>"
+        ));
+        assert!(rendered.contains("<mj-class name=\"primary\" color=\"#0066FF\"/>"));
+        assert!(
+            rendered.contains("<mj-class name=\"muted\" color=\"#888888\" font-size=\"12px\"/>")
+        );
+        assert!(rendered.contains(
+            "<mj-class name=\"heading\" font-family=\"Georgia, serif\" font-size=\"28px\" font-weight=\"700\"/>"
+        ));
+
+        // switching brand only changes the colors derived from it
+        let other = theme(Color::rgb(0xCC, 0x00, 0x33)).to_mj_attributes();
+        let changed = attributes
+            .content
+            .iter()
+            .zip(&other.content)
+            .filter(|(a, b)| a != b)
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            changed[..],
+            [(MjAttribute::MjButton(_), _), (MjAttribute::MjClass(_), _)]
+        ));
+
+        let head = MjHead {
+            mj_attributes: Some((&theme(Color::BLACK)).into()),
+            mj_breakpoint: None,
+            mj_font: vec![],
+            mj_html_attributes: vec![],
+            mj_preview: None,
+            mj_style: vec![],
+            mj_title: None,
+        };
+        assert!(head.to_string().contains("<mj-attributes>"));
+
+        let heading = MjText {
+            content: "Welcome".to_string(),
+            attributes: MjTextAttributes {
+                mj_class: vec![HEADING_CLASS.to_string()],
+                ..Default::default()
+            },
+        };
+        assert!(heading
+            .to_string()
+            .starts_with("<mj-text mj-class=\"heading\">"));
+    }
+}
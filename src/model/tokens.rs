@@ -0,0 +1,533 @@
+//! Import of design tokens in the W3C Design Tokens format.
+//!
+//! Tokens are read from nested groups, with `$type` inherited from the enclosing groups and
+//! `{group.token}` aliases resolved. Only the `color`, `dimension`, `fontFamily` and `fontWeight`
+//! types are read, the tokens that cannot be represented by the model are reported in
+//! [`DesignTokens::unsupported`].
+
+use crate::model::mj_head::{MjAttribute, MjAttributes, MjClass};
+use crate::model::theme::Theme;
+use crate::model::{parse_color, Color, Length, Padding, PaddingValue, SizePx};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Aliases deeper than this are taken as a cycle.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenValue {
+    Color(Color),
+    Dimension(Length),
+    FontFamily(Vec<String>),
+    FontWeight(u32),
+}
+
+/// A token that has no equivalent in the model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedToken {
+    /// Names of the groups and of the token, separated by dots.
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenError {
+    Malformed(String),
+    Missing(String),
+    WrongType {
+        path: String,
+        expected: &'static str,
+    },
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed(reason) => write!(f, "malformed tokens: {}", reason),
+            Self::Missing(path) => write!(f, "no token {}", path),
+            Self::WrongType { path, expected } => {
+                write!(f, "token {} is not a {} token", path, expected)
+            }
+        }
+    }
+}
+
+impl Error for TokenError {}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DesignTokens {
+    pub tokens: BTreeMap<String, TokenValue>,
+    pub unsupported: Vec<UnsupportedToken>,
+}
+
+/// Paths of the tokens filling each field of a [`Theme`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeTokens {
+    pub primary_color: String,
+    pub secondary_color: String,
+    pub text_color: String,
+    pub button_text_color: String,
+    pub font_family: String,
+    pub heading_font_family: String,
+    pub font_size: String,
+    pub small_font_size: String,
+    pub heading_font_size: String,
+    pub button_radius: String,
+    /// Vertical padding of the sections.
+    pub section_spacing: String,
+}
+
+impl Default for ThemeTokens {
+    fn default() -> Self {
+        Self {
+            primary_color: "color.primary".to_string(),
+            secondary_color: "color.secondary".to_string(),
+            text_color: "color.text".to_string(),
+            button_text_color: "color.button-text".to_string(),
+            font_family: "font.family.body".to_string(),
+            heading_font_family: "font.family.heading".to_string(),
+            font_size: "font.size.body".to_string(),
+            small_font_size: "font.size.small".to_string(),
+            heading_font_size: "font.size.heading".to_string(),
+            button_radius: "radius.button".to_string(),
+            section_spacing: "spacing.section".to_string(),
+        }
+    }
+}
+
+impl DesignTokens {
+    pub fn from_json(value: &Value) -> Result<Self, TokenError> {
+        let root = value
+            .as_object()
+            .ok_or_else(|| TokenError::Malformed("the root is not an object".to_string()))?;
+        let mut raw = BTreeMap::new();
+        collect(root, "", None, &mut raw);
+
+        let mut tokens = Self::default();
+        for (path, token) in &raw {
+            match resolve(&raw, path, token, 0).and_then(|(kind, value)| read(kind, value)) {
+                Ok(value) => {
+                    tokens.tokens.insert(path.clone(), value);
+                }
+                Err(reason) => tokens.unsupported.push(UnsupportedToken {
+                    path: path.clone(),
+                    reason,
+                }),
+            }
+        }
+        Ok(tokens)
+    }
+
+    pub fn get(&self, path: &str) -> Option<&TokenValue> {
+        self.tokens.get(path)
+    }
+
+    pub fn color(&self, path: &str) -> Result<Color, TokenError> {
+        match self.find(path)? {
+            TokenValue::Color(color) => Ok(*color),
+            _ => Err(wrong_type(path, "color")),
+        }
+    }
+
+    pub fn dimension(&self, path: &str) -> Result<Length, TokenError> {
+        match self.find(path)? {
+            TokenValue::Dimension(dimension) => Ok(*dimension),
+            _ => Err(wrong_type(path, "dimension")),
+        }
+    }
+
+    /// A dimension token in pixels.
+    pub fn size_px(&self, path: &str) -> Result<SizePx, TokenError> {
+        match self.find(path)? {
+            TokenValue::Dimension(dimension) => dimension
+                .convert()
+                .ok_or_else(|| wrong_type(path, "px dimension")),
+            _ => Err(wrong_type(path, "px dimension")),
+        }
+    }
+
+    pub fn font_family(&self, path: &str) -> Result<Vec<String>, TokenError> {
+        match self.find(path)? {
+            TokenValue::FontFamily(family) => Ok(family.clone()),
+            _ => Err(wrong_type(path, "fontFamily")),
+        }
+    }
+
+    pub fn font_weight(&self, path: &str) -> Result<u32, TokenError> {
+        match self.find(path)? {
+            TokenValue::FontWeight(weight) => Ok(*weight),
+            _ => Err(wrong_type(path, "fontWeight")),
+        }
+    }
+
+    fn find(&self, path: &str) -> Result<&TokenValue, TokenError> {
+        self.get(path)
+            .ok_or_else(|| TokenError::Missing(path.to_string()))
+    }
+
+    pub fn theme(&self, paths: &ThemeTokens) -> Result<Theme, TokenError> {
+        let spacing = PaddingValue::Px(self.size_px(&paths.section_spacing)?);
+        let none = PaddingValue::Px(SizePx::new(0));
+        Ok(Theme {
+            primary_color: self.color(&paths.primary_color)?,
+            secondary_color: self.color(&paths.secondary_color)?,
+            text_color: self.color(&paths.text_color)?,
+            button_text_color: self.color(&paths.button_text_color)?,
+            font_family: self.font_family(&paths.font_family)?,
+            heading_font_family: self.font_family(&paths.heading_font_family)?,
+            font_size: self.size_px(&paths.font_size)?,
+            small_font_size: self.size_px(&paths.small_font_size)?,
+            heading_font_size: self.size_px(&paths.heading_font_size)?,
+            button_radius: self.size_px(&paths.button_radius)?,
+            section_padding: Padding {
+                top: spacing,
+                right: none,
+                bottom: spacing,
+                left: none,
+            },
+        })
+    }
+
+    /// One `mj-class` per color, font family and font weight token, named after its path with
+    /// dashes, such as `color-primary` setting `color`.
+    ///
+    /// Dimensions are left out as their use, font size or spacing, is not known.
+    pub fn mj_classes(&self) -> Vec<MjClass> {
+        self.tokens
+            .iter()
+            .filter_map(|(path, value)| {
+                let (name, value) = match value {
+                    TokenValue::Color(color) => ("color", color.to_string()),
+                    TokenValue::FontFamily(family) => ("font-family", family.join(", ")),
+                    TokenValue::FontWeight(weight) => ("font-weight", weight.to_string()),
+                    TokenValue::Dimension(_) => return None,
+                };
+                Some(MjClass {
+                    name: path.replace('.', "-"),
+                    attributes: BTreeMap::from([(name.to_string(), value)]),
+                })
+            })
+            .collect()
+    }
+
+    /// The attributes of the theme read with `paths`, followed by [`Self::mj_classes`].
+    pub fn to_mj_attributes(&self, paths: &ThemeTokens) -> Result<MjAttributes, TokenError> {
+        let mut attributes = self.theme(paths)?.to_mj_attributes();
+        attributes
+            .content
+            .extend(self.mj_classes().into_iter().map(MjAttribute::MjClass));
+        Ok(attributes)
+    }
+}
+
+fn wrong_type(path: &str, expected: &'static str) -> TokenError {
+    TokenError::WrongType {
+        path: path.to_string(),
+        expected,
+    }
+}
+
+struct RawToken<'a> {
+    kind: Option<&'a str>,
+    value: &'a Value,
+}
+
+fn collect<'a>(
+    group: &'a Map<String, Value>,
+    prefix: &str,
+    inherited: Option<&'a str>,
+    raw: &mut BTreeMap<String, RawToken<'a>>,
+) {
+    let kind = group.get("$type").and_then(Value::as_str).or(inherited);
+    for (name, child) in group {
+        if name.starts_with('$') {
+            continue;
+        }
+        let Some(child) = child.as_object() else {
+            continue;
+        };
+        let path = match prefix {
+            "" => name.clone(),
+            _ => format!("{}.{}", prefix, name),
+        };
+        match child.get("$value") {
+            Some(value) => {
+                let kind = child.get("$type").and_then(Value::as_str).or(kind);
+                raw.insert(path, RawToken { kind, value });
+            }
+            None => collect(child, &path, kind, raw),
+        }
+    }
+}
+
+/// Follows the aliases, the type of a token defaults to the one of the token it refers to.
+fn resolve<'a>(
+    raw: &'a BTreeMap<String, RawToken<'a>>,
+    path: &str,
+    token: &'a RawToken<'a>,
+    depth: usize,
+) -> Result<(&'a str, &'a Value), String> {
+    let alias = token
+        .value
+        .as_str()
+        .and_then(|value| value.strip_prefix('{')?.strip_suffix('}'));
+    match alias {
+        Some(target) => {
+            if depth == MAX_ALIAS_DEPTH {
+                return Err(format!("alias of {} is circular", path));
+            }
+            let aliased = raw
+                .get(target)
+                .ok_or_else(|| format!("alias to the missing token {}", target))?;
+            let (kind, value) = resolve(raw, target, aliased, depth + 1)?;
+            Ok((token.kind.unwrap_or(kind), value))
+        }
+        None => token
+            .kind
+            .map(|kind| (kind, token.value))
+            .ok_or_else(|| "no $type".to_string()),
+    }
+}
+
+fn read(kind: &str, value: &Value) -> Result<TokenValue, String> {
+    match kind {
+        "color" => read_color(value).map(TokenValue::Color),
+        "dimension" => read_dimension(value).map(TokenValue::Dimension),
+        "fontFamily" => read_font_family(value).map(TokenValue::FontFamily),
+        "fontWeight" => read_font_weight(value).map(TokenValue::FontWeight),
+        _ => Err(format!("type {} has no equivalent", kind)),
+    }
+}
+
+fn read_color(value: &Value) -> Result<Color, String> {
+    let invalid = || format!("invalid color {}", value);
+    match value {
+        Value::String(color) => parse_color(color).map_err(|err| err.to_string()),
+        Value::Object(color) => {
+            if let Some(hex) = color.get("hex").and_then(Value::as_str) {
+                return parse_color(hex).map_err(|err| err.to_string());
+            }
+            match color.get("colorSpace").and_then(Value::as_str) {
+                Some("srgb") => {}
+                Some(space) => return Err(format!("color space {} has no equivalent", space)),
+                None => return Err(invalid()),
+            }
+            let channel = |value: &Value| {
+                value
+                    .as_f64()
+                    .filter(|value| (0.0..=1.0).contains(value))
+                    .map(|value| (value * 255.0).round() as u8)
+            };
+            let components = color
+                .get("components")
+                .and_then(Value::as_array)
+                .and_then(|components| components.iter().map(channel).collect::<Option<Vec<_>>>())
+                .ok_or_else(invalid)?;
+            let alpha = match color.get("alpha") {
+                Some(alpha) => channel(alpha).ok_or_else(invalid)?,
+                None => u8::MAX,
+            };
+            match components[..] {
+                [r, g, b] => Ok(Color::rgba(r, g, b, alpha)),
+                _ => Err(invalid()),
+            }
+        }
+        _ => Err(invalid()),
+    }
+}
+
+fn read_dimension(value: &Value) -> Result<Length, String> {
+    let (amount, unit) = match value {
+        Value::String(dimension) => {
+            let split = dimension
+                .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+                .unwrap_or(dimension.len());
+            let amount = dimension[..split]
+                .parse::<f64>()
+                .map_err(|_| format!("invalid dimension {}", value))?;
+            (amount, &dimension[split..])
+        }
+        Value::Object(dimension) => match (
+            dimension.get("value").and_then(Value::as_f64),
+            dimension.get("unit").and_then(Value::as_str),
+        ) {
+            (Some(amount), Some(unit)) => (amount, unit),
+            _ => return Err(format!("invalid dimension {}", value)),
+        },
+        _ => return Err(format!("invalid dimension {}", value)),
+    };
+    let amount = amount as f32;
+    match unit {
+        "px" => Ok(Length::px(amount)),
+        "em" => Ok(Length::em(amount)),
+        "rem" => Ok(Length::rem(amount)),
+        _ => Err(format!("unit {} has no equivalent", unit)),
+    }
+}
+
+fn read_font_family(value: &Value) -> Result<Vec<String>, String> {
+    match value {
+        Value::String(family) => Ok(vec![family.clone()]),
+        Value::Array(families) => families
+            .iter()
+            .map(|family| family.as_str().map(str::to_string))
+            .collect::<Option<_>>()
+            .ok_or_else(|| format!("invalid font family {}", value)),
+        _ => Err(format!("invalid font family {}", value)),
+    }
+}
+
+fn read_font_weight(value: &Value) -> Result<u32, String> {
+    let weight = match value {
+        Value::Number(weight) => weight
+            .as_u64()
+            .and_then(|weight| u32::try_from(weight).ok()),
+        Value::String(keyword) => match keyword.as_str() {
+            "thin" | "hairline" => Some(100),
+            "extra-light" | "ultra-light" => Some(200),
+            "light" => Some(300),
+            "normal" | "regular" | "book" => Some(400),
+            "medium" => Some(500),
+            "semi-bold" | "demi-bold" => Some(600),
+            "bold" => Some(700),
+            "extra-bold" | "ultra-bold" => Some(800),
+            "black" | "heavy" => Some(900),
+            "extra-black" | "ultra-black" => Some(950),
+            _ => None,
+        },
+        _ => None,
+    };
+    weight
+        .filter(|weight| (1..=1000).contains(weight))
+        .ok_or_else(|| format!("invalid font weight {}", value))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::tokens::{
+        DesignTokens, ThemeTokens, TokenError, TokenValue, UnsupportedToken,
+    };
+    use crate::model::{Color, Length, SizePx};
+    use serde_json::json;
+
+    fn sample() -> serde_json::Value {
+        json!({
+            "color": {
+                "$type": "color",
+                "primary": { "$value": "#0066ff" },
+                "secondary": { "$value": { "colorSpace": "srgb", "components": [0.5, 0.5, 0.5] } },
+                "text": { "$value": "#222222" },
+                "button-text": { "$value": "{color.white}" },
+                "white": { "$value": "#ffffff" },
+                "overlay": { "$value": { "colorSpace": "oklch", "components": [0.5, 0.1, 200] } }
+            },
+            "font": {
+                "family": {
+                    "$type": "fontFamily",
+                    "body": { "$value": ["Inter", "Arial", "sans-serif"] },
+                    "heading": { "$value": "Georgia" }
+                },
+                "size": {
+                    "$type": "dimension",
+                    "body": { "$value": { "value": 16, "unit": "px" } },
+                    "small": { "$value": "12px" },
+                    "heading": { "$value": "28px" },
+                    "caption": { "$value": "0.75rem" },
+                    "half": { "$value": "12.5px" }
+                },
+                "weight": {
+                    "bold": { "$type": "fontWeight", "$value": "bold" }
+                }
+            },
+            "radius": { "button": { "$type": "dimension", "$value": "6px" } },
+            "spacing": {
+                "$type": "dimension",
+                "section": { "$value": "24px" },
+                "loop": { "$value": "{spacing.loop}" }
+            },
+            "motion": { "fast": { "$type": "duration", "$value": "100ms" } }
+        })
+    }
+
+    #[test]
+    fn read_tokens() {
+        let tokens = DesignTokens::from_json(&sample()).unwrap();
+        assert_eq!(
+            tokens.color("color.primary"),
+            Ok(Color::rgb(0x00, 0x66, 0xFF))
+        );
+        assert_eq!(
+            tokens.color("color.secondary"),
+            Ok(Color::rgb(128, 128, 128))
+        );
+        assert_eq!(tokens.color("color.button-text"), Ok(Color::WHITE));
+        assert_eq!(tokens.size_px("font.size.body"), Ok(SizePx::new(16)));
+        assert_eq!(tokens.font_weight("font.weight.bold"), Ok(700));
+        assert_eq!(
+            tokens.get("font.family.heading"),
+            Some(&TokenValue::FontFamily(vec!["Georgia".to_string()]))
+        );
+        assert_eq!(
+            tokens.dimension("color.primary"),
+            Err(TokenError::WrongType {
+                path: "color.primary".to_string(),
+                expected: "dimension"
+            })
+        );
+
+        let unsupported = |path: &str, reason: &str| UnsupportedToken {
+            path: path.to_string(),
+            reason: reason.to_string(),
+        };
+        assert_eq!(
+            tokens.unsupported,
+            vec![
+                unsupported("color.overlay", "color space oklch has no equivalent"),
+                unsupported("motion.fast", "type duration has no equivalent"),
+                unsupported("spacing.loop", "alias of spacing.loop is circular"),
+            ]
+        );
+        assert_eq!(tokens.size_px("font.size.half"), Ok(SizePx::px(12.5)));
+        assert_eq!(tokens.dimension("font.size.caption"), Ok(Length::rem(0.75)));
+        assert_eq!(
+            tokens.size_px("font.size.caption"),
+            Err(TokenError::WrongType {
+                path: "font.size.caption".to_string(),
+                expected: "px dimension"
+            })
+        );
+        assert_eq!(
+            DesignTokens::from_json(&json!({ "a": { "$type": "dimension", "$value": "1.5em" } }))
+                .unwrap()
+                .dimension("a"),
+            Ok(Length::em(1.5))
+        );
+    }
+
+    #[test]
+    fn tokens_to_attributes() {
+        let tokens = DesignTokens::from_json(&sample()).unwrap();
+        let theme = tokens.theme(&ThemeTokens::default()).unwrap();
+        assert_eq!(theme.font_family, vec!["Inter", "Arial", "sans-serif"]);
+        assert_eq!(theme.heading_font_size, SizePx::new(28));
+
+        let rendered = tokens
+            .to_mj_attributes(&ThemeTokens::default())
+            .unwrap()
+            .to_string();
+        assert!(rendered.contains("<mj-class name=\"color-primary\" color=\"#0066FF\"/>"));
+        assert!(rendered.contains("<mj-class name=\"font-weight-bold\" font-weight=\"700\"/>"));
+        assert!(!rendered.contains("font-size-body"));
+
+        let paths = ThemeTokens {
+            primary_color: "color.brand".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            tokens.theme(&paths),
+            Err(TokenError::Missing("color.brand".to_string()))
+        );
+    }
+}
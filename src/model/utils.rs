@@ -1,5 +1,217 @@
+use crate::model::schema::{self, ValueKind};
+use crate::model::{Color, SizePx};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write;
 use std::fmt::{Display, Formatter};
-use std::io::Write;
+use std::str::FromStr;
+
+/// Forwards formatted output to an [`std::io::Write`], keeping the actual io error.
+pub(crate) struct IoWriter<'a> {
+    inner: &'a mut dyn std::io::Write,
+    error: Option<std::io::Error>,
+}
+
+impl<'a> IoWriter<'a> {
+    pub(crate) fn new(inner: &'a mut dyn std::io::Write) -> Self {
+        Self { inner, error: None }
+    }
+
+    pub(crate) fn into_error(self) -> std::io::Error {
+        self.error
+            .unwrap_or_else(|| std::io::Error::other("formatter error"))
+    }
+}
+
+impl<'a> Write for IoWriter<'a> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            std::fmt::Error
+        })
+    }
+}
+
+/// Receives the attributes of a component, one at a time.
+pub(crate) trait AttributeWriter {
+    fn attribute(&mut self, name: &str, value: &dyn Display) -> std::fmt::Result;
+}
+
+/// Writes attributes the way they appear in a tag, as ` name="value"`.
+pub(crate) struct MarkupAttributes<'a>(pub(crate) &'a mut dyn Write);
+
+impl<'a> AttributeWriter for MarkupAttributes<'a> {
+    fn attribute(&mut self, name: &str, value: &dyn Display) -> std::fmt::Result {
+        write!(self.0, " {}=\"{}\"", name, value)
+    }
+}
+
+impl AttributeWriter for BTreeMap<String, String> {
+    fn attribute(&mut self, name: &str, value: &dyn Display) -> std::fmt::Result {
+        self.insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+/// A value that could not be read from its markup form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseValueError {
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl ParseValueError {
+    pub(crate) fn new(kind: &'static str, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+impl Display for ParseValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid {}: {:?}", self.kind, self.value)
+    }
+}
+
+impl Error for ParseValueError {}
+
+/// Implements [`FromStr`] for a fieldless enum from the values used in markup.
+macro_rules! impl_from_str {
+    ($type:ty, $kind:literal, $($value:literal => $variant:ident),+ $(,)?) => {
+        impl std::str::FromStr for $type {
+            type Err = $crate::model::ParseValueError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($value => Ok(Self::$variant),)+
+                    _ => Err($crate::model::ParseValueError::new($kind, s)),
+                }
+            }
+        }
+        $crate::model::impl_serde_str!($type);
+    };
+}
+pub(crate) use impl_from_str;
+
+/// Implements the serde traits from [`FromStr`] and [`Display`], so values read as in markup.
+macro_rules! impl_serde_str {
+    ($($type:ty),+ $(,)?) => {
+        $(
+            #[cfg(feature = "serde")]
+            impl serde::Serialize for $type {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            #[cfg(feature = "serde")]
+            impl<'de> serde::Deserialize<'de> for $type {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = String::deserialize(deserializer)?;
+                    value.parse().map_err(serde::de::Error::custom)
+                }
+            }
+        )+
+    };
+}
+pub(crate) use impl_serde_str;
+
+/// A value held by a single attribute, read and written by the derived attributes structs.
+pub(crate) trait AttributeValue: Sized {
+    const KIND: ValueKind;
+
+    fn parse_value(value: &str) -> Result<Self, ParseValueError>;
+    fn render_value(&self, name: &str, writer: &mut dyn AttributeWriter) -> std::fmt::Result;
+}
+
+/// Implements [`AttributeValue`] from [`FromStr`] and [`Display`].
+macro_rules! impl_attribute_value {
+    ($($type:ty => $kind:expr),+ $(,)?) => {
+        $(
+            impl $crate::model::AttributeValue for $type {
+                const KIND: $crate::model::schema::ValueKind = $kind;
+
+                fn parse_value(value: &str) -> Result<Self, $crate::model::ParseValueError> {
+                    value.parse()
+                }
+
+                fn render_value(
+                    &self,
+                    name: &str,
+                    writer: &mut dyn $crate::model::AttributeWriter,
+                ) -> std::fmt::Result {
+                    writer.attribute(name, self)
+                }
+            }
+        )+
+    };
+}
+pub(crate) use impl_attribute_value;
+
+impl<T: AttributeValue> AttributeValue for Option<T> {
+    const KIND: ValueKind = T::KIND;
+
+    fn parse_value(value: &str) -> Result<Self, ParseValueError> {
+        T::parse_value(value).map(Some)
+    }
+
+    fn render_value(&self, name: &str, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        match self {
+            Some(value) => value.render_value(name, writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl AttributeValue for String {
+    const KIND: ValueKind = ValueKind::String;
+
+    fn parse_value(value: &str) -> Result<Self, ParseValueError> {
+        Ok(value.to_string())
+    }
+
+    fn render_value(&self, name: &str, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        writer.attribute(name, self)
+    }
+}
+
+impl AttributeValue for u32 {
+    const KIND: ValueKind = ValueKind::Integer;
+
+    fn parse_value(value: &str) -> Result<Self, ParseValueError> {
+        parse_number(value)
+    }
+
+    fn render_value(&self, name: &str, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        writer.attribute(name, self)
+    }
+}
+
+impl AttributeValue for bool {
+    const KIND: ValueKind = ValueKind::Boolean;
+
+    fn parse_value(value: &str) -> Result<Self, ParseValueError> {
+        parse_bool(value)
+    }
+
+    fn render_value(&self, name: &str, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        writer.attribute(name, self)
+    }
+}
+
+impl AttributeValue for Color {
+    const KIND: ValueKind = ValueKind::Color;
+
+    fn parse_value(value: &str) -> Result<Self, ParseValueError> {
+        parse_color(value)
+    }
+
+    fn render_value(&self, name: &str, writer: &mut dyn AttributeWriter) -> std::fmt::Result {
+        writer.attribute(name, self)
+    }
+}
 
 pub(crate) fn build_indentation(depth: usize) -> String {
     "\t".repeat(depth)
@@ -20,6 +232,8 @@ impl Display for Alignment {
         }
     }
 }
+impl_from_str!(Alignment, "alignment", "left" => Left, "center" => Center, "right" => Right);
+impl_attribute_value!(Alignment => schema::ALIGNMENT);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TextAlignment {
@@ -38,6 +252,36 @@ impl Display for TextAlignment {
         }
     }
 }
+impl_from_str!(
+    TextAlignment,
+    "text alignment",
+    "left" => Left,
+    "center" => Center,
+    "right" => Right,
+    "justify" => Justify,
+);
+impl_attribute_value!(TextAlignment => schema::TEXT_ALIGNMENT);
+
+/// Devices a component is shown on, applied through generated css classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceVisibility {
+    DesktopOnly,
+    MobileOnly,
+}
+impl Display for DeviceVisibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DesktopOnly => write!(f, "desktop-only"),
+            Self::MobileOnly => write!(f, "mobile-only"),
+        }
+    }
+}
+impl_from_str!(
+    DeviceVisibility,
+    "device visibility",
+    "desktop-only" => DesktopOnly,
+    "mobile-only" => MobileOnly,
+);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VerticalAlignment {
@@ -54,66 +298,75 @@ impl Display for VerticalAlignment {
         }
     }
 }
+impl_from_str!(
+    VerticalAlignment,
+    "vertical alignment",
+    "top" => Top,
+    "center" => Center,
+    "middle" => Center,
+    "bottom" => Bottom,
+);
+impl_attribute_value!(VerticalAlignment => schema::VERTICAL_ALIGNMENT);
 
-pub type Color = hex_color::HexColor;
 pub type Html = String; // todo
 pub type CssClass = String;
 pub type Css = String;
 pub type Url = String;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SizePx(u32);
-impl SizePx {
-    pub fn new(size: u32) -> Self {
-        Self(size)
-    }
-}
-impl Display for SizePx {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.0 != 0 {
-            write!(f, "{}px", self.0)
-        } else {
-            write!(f, "0")
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum PxOrEm {
-    Px(SizePx),
-    Em(f32),
-}
-impl Display for PxOrEm {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Px(px) => write!(f, "{}", px),
-            Self::Em(em) => write!(f, "{}em", em),
-        }
-    }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Padding {
+    pub top: PaddingValue,
+    pub right: PaddingValue,
+    pub bottom: PaddingValue,
+    pub left: PaddingValue,
 }
+impl FromStr for Padding {
+    type Err = ParseValueError;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum PxOrPercent {
-    Px(SizePx),
-    Percent(f32),
+    /// Reads the css shorthand, from one to four values.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<PaddingValue>, _>>()?;
+        let (top, right, bottom, left) = match values[..] {
+            [all] => (all, all, all, all),
+            [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+            [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+            [top, right, bottom, left] => (top, right, bottom, left),
+            _ => return Err(ParseValueError::new("padding", s)),
+        };
+        Ok(Self {
+            top,
+            right,
+            bottom,
+            left,
+        })
+    }
 }
-impl Display for PxOrPercent {
+impl Display for Padding {
+    /// Writes the shortest css shorthand.
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Px(px) => write!(f, "{}", px),
-            Self::Percent(percent) => write!(f, "{}%", percent),
+        let Self {
+            top,
+            right,
+            bottom,
+            left,
+        } = self;
+        if left != right {
+            write!(f, "{} {} {} {}", top, right, bottom, left)
+        } else if top != bottom {
+            write!(f, "{} {} {}", top, right, bottom)
+        } else if top != right {
+            write!(f, "{} {}", top, right)
+        } else {
+            write!(f, "{}", top)
         }
     }
 }
 
-pub struct Padding {
-    pub top: PaddingValue,
-    pub right: PaddingValue,
-    pub bottom: PaddingValue,
-    pub left: PaddingValue,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PaddingValue {
     Px(SizePx),
     Inherit,
@@ -126,11 +379,29 @@ impl Display for PaddingValue {
         }
     }
 }
+impl FromStr for PaddingValue {
+    type Err = ParseValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "inherit" => Ok(Self::Inherit),
+            _ => s
+                .parse()
+                .map(Self::Px)
+                .map_err(|_| ParseValueError::new("padding", s)),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BorderStyle {
     Dashed,
     Dotted,
+    Double,
+    Groove,
+    Inset,
+    Outset,
+    Ridge,
     Solid,
 }
 impl Display for BorderStyle {
@@ -138,11 +409,31 @@ impl Display for BorderStyle {
         match self {
             BorderStyle::Dashed => write!(f, "dashed"),
             BorderStyle::Dotted => write!(f, "dotted"),
+            BorderStyle::Double => write!(f, "double"),
+            BorderStyle::Groove => write!(f, "groove"),
+            BorderStyle::Inset => write!(f, "inset"),
+            BorderStyle::Outset => write!(f, "outset"),
+            BorderStyle::Ridge => write!(f, "ridge"),
             BorderStyle::Solid => write!(f, "solid"),
         }
     }
 }
+impl_from_str!(
+    BorderStyle,
+    "border style",
+    "dashed" => Dashed,
+    "dotted" => Dotted,
+    "double" => Double,
+    "groove" => Groove,
+    "inset" => Inset,
+    "outset" => Outset,
+    "ridge" => Ridge,
+    "solid" => Solid,
+);
+impl_attribute_value!(BorderStyle => schema::BORDER_STYLE);
 
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BorderProperties {
     pub width: Option<SizePx>,
     pub style: Option<BorderStyle>,
@@ -150,22 +441,51 @@ pub struct BorderProperties {
 }
 
 impl Display for BorderProperties {
+    /// Writes the css shorthand, as `1px solid #CCCCCC`.
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if self.width.is_none() && self.style.is_none() && self.color.is_none() {
+        let parts = [
+            self.width.map(|width| width.to_string()),
+            self.style.map(|style| style.to_string()),
+            self.color.map(|color| color.to_string()),
+        ];
+        let parts = parts.into_iter().flatten().collect::<Vec<_>>();
+        if parts.is_empty() {
             return write!(f, "none");
         }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+impl FromStr for BorderProperties {
+    type Err = ParseValueError;
 
-        if let Some(style) = self.style {
-            write!(f, "{}", style)?;
-        }
-        if let Some(width) = self.width {
-            write!(f, "{}", width)?;
+    /// Reads the css shorthand, its parts can be in any order and each one at most once.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseValueError::new("border", s);
+        let mut border = Self {
+            width: None,
+            style: None,
+            color: None,
+        };
+        let mut none = false;
+        for part in split_css_values(s) {
+            let duplicate = if part == "none" {
+                std::mem::replace(&mut none, true)
+            } else if let Ok(style) = part.parse() {
+                border.style.replace(style).is_some()
+            } else if let Ok(width) = part.parse() {
+                border.width.replace(width).is_some()
+            } else {
+                let color = parse_color(part).map_err(|_| invalid())?;
+                border.color.replace(color).is_some()
+            };
+            if duplicate || (none && border.style.is_some()) {
+                return Err(invalid());
+            }
         }
-        if let Some(color) = &self.color {
-            write!(f, "{}", color)?;
+        if !none && border == Self::default() {
+            return Err(invalid());
         }
-
-        Ok(())
+        Ok(border)
     }
 }
 
@@ -184,6 +504,14 @@ impl Display for FontStyle {
         }
     }
 }
+impl_from_str!(
+    FontStyle,
+    "font style",
+    "normal" => Normal,
+    "italic" => Italic,
+    "oblique" => Oblique,
+);
+impl_attribute_value!(FontStyle => schema::FONT_STYLE);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TextDecoration {
@@ -197,11 +525,22 @@ impl Display for TextDecoration {
         match self {
             Self::Underline => write!(f, "underline"),
             Self::Overline => write!(f, "overline"),
-            Self::LineThrough => write!(f, "lineThrough"),
+            Self::LineThrough => write!(f, "line-through"),
             Self::None => write!(f, "none"),
         }
     }
 }
+impl_from_str!(
+    TextDecoration,
+    "text decoration",
+    "underline" => Underline,
+    "overline" => Overline,
+    "line-through" => LineThrough,
+    // written by earlier versions
+    "lineThrough" => LineThrough,
+    "none" => None,
+);
+impl_attribute_value!(TextDecoration => schema::TEXT_DECORATION);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TextTransform {
@@ -220,6 +559,15 @@ impl Display for TextTransform {
         }
     }
 }
+impl_from_str!(
+    TextTransform,
+    "text transform",
+    "capitalize" => Capitalize,
+    "uppercase" => Uppercase,
+    "lowercase" => Lowercase,
+    "none" => None,
+);
+impl_attribute_value!(TextTransform => schema::TEXT_TRANSFORM);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -234,60 +582,220 @@ impl Display for Direction {
         }
     }
 }
+impl_from_str!(Direction, "direction", "ltr" => Ltr, "rtl" => Rtl);
+impl_serde_str!(PaddingValue);
+impl_attribute_value!(
+    Padding => ValueKind::Padding,
+    BorderProperties => ValueKind::Border,
+    Direction => schema::DIRECTION,
+);
+
+pub(crate) fn parse_color(value: &str) -> Result<Color, ParseValueError> {
+    value.parse()
+}
+
+pub(crate) fn parse_number(value: &str) -> Result<u32, ParseValueError> {
+    value
+        .parse()
+        .map_err(|_| ParseValueError::new("number", value))
+}
+
+pub(crate) fn parse_font_weight(value: &str) -> Result<u32, ParseValueError> {
+    match value {
+        "normal" => Ok(400),
+        "bold" => Ok(700),
+        _ => value
+            .parse()
+            .map_err(|_| ParseValueError::new("font weight", value)),
+    }
+}
+
+pub(crate) fn parse_bool(value: &str) -> Result<bool, ParseValueError> {
+    value
+        .parse()
+        .map_err(|_| ParseValueError::new("boolean", value))
+}
+
+pub(crate) fn parse_css_class(value: &str) -> Vec<CssClass> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|class| !class.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub(crate) fn parse_font_family(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|family| !family.is_empty())
+        .map(str::to_string)
+        .collect()
+}
 
 pub(crate) fn render_css_class(
-    writer: &mut dyn Write,
+    writer: &mut dyn AttributeWriter,
+    name: &str,
     css_class: &[CssClass],
-) -> std::io::Result<()> {
+) -> std::fmt::Result {
     if !css_class.is_empty() {
-        let classes = css_class.join(", ");
-        write!(writer, " css-class=\"{}\"", classes)?;
+        let classes = css_class.join(" ");
+        writer.attribute(name, &classes)?;
     }
 
     Ok(())
 }
 pub(crate) fn render_font_family(
-    writer: &mut dyn Write,
+    writer: &mut dyn AttributeWriter,
+    name: &str,
     font_family: &[String],
-) -> std::io::Result<()> {
+) -> std::fmt::Result {
     if !font_family.is_empty() {
         let families = font_family.join(", ");
-        write!(writer, " font-family=\"{}\"", families)?;
+        writer.attribute(name, &families)?;
     }
 
     Ok(())
 }
 
-pub(crate) fn render_generic_attribute<T: Display>(
-    writer: &mut dyn Write,
-    name: &str,
-    value: &Option<T>,
-) -> std::io::Result<()> {
-    if let Some(value) = value {
-        write!(writer, " {}=\"{}\"", name, value)?;
+/// Values of a css shorthand, the spaces inside parentheses do not separate them.
+pub(crate) fn split_css_values(value: &str) -> Vec<&str> {
+    let mut values = vec![];
+    let mut depth = 0usize;
+    let mut start = None;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if c.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    values.push(&value[start..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
     }
-    Ok(())
+    if let Some(start) = start {
+        values.push(&value[start..]);
+    }
+    values
 }
 
-pub(crate) fn render_padding_opt(
-    writer: &mut dyn Write,
-    prefix: &str,
-    padding: &Option<Padding>,
-) -> std::io::Result<()> {
-    if let Some(padding) = padding {
-        render_padding(writer, prefix, padding)?;
+#[cfg(test)]
+mod test {
+    use crate::model::mj_button::{MjButton, MjButtonAttributes};
+    use crate::model::{BorderProperties, BorderStyle, Color, Padding, PaddingValue, SizePx};
+
+    fn padding(top: u32, right: u32, bottom: u32, left: u32) -> Padding {
+        let px = |size| PaddingValue::Px(SizePx::new(size));
+        Padding {
+            top: px(top),
+            right: px(right),
+            bottom: px(bottom),
+            left: px(left),
+        }
     }
-    Ok(())
-}
 
-pub(crate) fn render_padding(
-    writer: &mut dyn Write,
-    prefix: &str,
-    padding: &Padding,
-) -> std::io::Result<()> {
-    write!(writer, " {}-bottom=\"{}\"", prefix, padding.bottom)?;
-    write!(writer, " {}-top=\"{}\"", prefix, padding.top)?;
-    write!(writer, " {}-left=\"{}\"", prefix, padding.left)?;
-    write!(writer, " {}-right=\"{}\"", prefix, padding.right)?;
-    Ok(())
+    #[test]
+    fn padding_shorthand() {
+        for (value, expected, written) in [
+            ("10px", padding(10, 10, 10, 10), "10px"),
+            ("10px 20px", padding(10, 20, 10, 20), "10px 20px"),
+            ("10px 20px 30px", padding(10, 20, 30, 20), "10px 20px 30px"),
+            (
+                "10px 20px 30px 40px",
+                padding(10, 20, 30, 40),
+                "10px 20px 30px 40px",
+            ),
+            ("10px 20px 10px 20px", padding(10, 20, 10, 20), "10px 20px"),
+            ("10px 20px 10px", padding(10, 20, 10, 20), "10px 20px"),
+            ("0px 0", padding(0, 0, 0, 0), "0"),
+            (" 4px  8px ", padding(4, 8, 4, 8), "4px 8px"),
+        ] {
+            let padding = value.parse::<Padding>().unwrap();
+            assert_eq!(padding, expected, "{}", value);
+            assert_eq!(padding.to_string(), written);
+        }
+        for value in ["", "1px 2px 3px 4px 5px", "1em", "10px,20px"] {
+            assert!(value.parse::<Padding>().is_err(), "{}", value);
+        }
+        assert_eq!(
+            "inherit 10px".parse::<Padding>().unwrap().to_string(),
+            "inherit 10px"
+        );
+    }
+
+    #[test]
+    fn border_shorthand() {
+        let border = |width: Option<u32>, style, color| BorderProperties {
+            width: width.map(SizePx::new),
+            style,
+            color,
+        };
+        let red = Some(Color::rgb(255, 0, 0));
+        for (value, expected, written) in [
+            (
+                "1px solid #ccc",
+                border(
+                    Some(1),
+                    Some(BorderStyle::Solid),
+                    Some(Color::rgb(204, 204, 204)),
+                ),
+                "1px solid #CCCCCC",
+            ),
+            (
+                "solid 2px red",
+                border(Some(2), Some(BorderStyle::Solid), red),
+                "2px solid #FF0000",
+            ),
+            (
+                "1px dashed rgb(255, 0, 0)",
+                border(Some(1), Some(BorderStyle::Dashed), red),
+                "1px dashed #FF0000",
+            ),
+            (
+                "2px solid transparent",
+                border(Some(2), Some(BorderStyle::Solid), Some(Color::TRANSPARENT)),
+                "2px solid transparent",
+            ),
+            (
+                "double",
+                border(None, Some(BorderStyle::Double), None),
+                "double",
+            ),
+            ("none", border(None, None, None), "none"),
+            ("0 none", border(Some(0), None, None), "0"),
+        ] {
+            let parsed = value.parse::<BorderProperties>().unwrap();
+            assert_eq!(parsed, expected, "{}", value);
+            assert_eq!(parsed.to_string(), written);
+            assert_eq!(written.parse::<BorderProperties>().unwrap(), expected);
+        }
+        for value in [
+            "",
+            "1px 2px solid",
+            "solid dashed",
+            "1px solid nocolor",
+            "none solid",
+        ] {
+            assert!(value.parse::<BorderProperties>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn render_shorthands() {
+        let button = MjButton {
+            content: "Buy".to_string(),
+            attributes: MjButtonAttributes {
+                border: Some("1px solid #ccc".parse().unwrap()),
+                padding: Some("10px 25px".parse().unwrap()),
+                ..Default::default()
+            },
+        };
+        assert!(button
+            .to_string()
+            .starts_with("<mj-button border=\"1px solid #CCCCCC\" padding=\"10px 25px\">"));
+    }
 }
@@ -0,0 +1,544 @@
+use crate::model::mjml::Mjml;
+use std::borrow::{Borrow, Cow};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::ops::Range;
+
+const PLACEHOLDER_START: &str = "{{";
+const PLACEHOLDER_END: &str = "}}";
+const RAW_START: &str = "{{{";
+const RAW_END: &str = "}}}";
+const LITERAL_START: &str = "\\{{";
+
+/// A template rendered once into static segments and placeholder slots.
+///
+/// Placeholders are written as `{{ name }}` anywhere in the content or the attributes of the
+/// model. Their values are escaped for the markup around them: `&`, `<` and `>` in content, quotes
+/// too inside a tag. `{{{ name }}}` writes the value as it is, for trusted markup, and `\{{` writes
+/// a literal `{{`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledTemplate {
+    statics: String,
+    segments: Vec<Segment>,
+    slots: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Static(Range<usize>),
+    Slot(usize, Escape),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    Text,
+    Attribute,
+    Raw,
+}
+
+/// Where the parser stands in the markup, to escape the placeholders found there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Markup {
+    Text,
+    Tag,
+    Value(char),
+}
+
+impl Markup {
+    fn after(mut self, text: &str) -> Self {
+        for c in text.chars() {
+            self = match (self, c) {
+                (Self::Text, '<') => Self::Tag,
+                (Self::Tag, '>') => Self::Text,
+                (Self::Tag, '"' | '\'') => Self::Value(c),
+                (Self::Value(quote), c) if c == quote => Self::Tag,
+                (state, _) => state,
+            };
+        }
+        self
+    }
+
+    fn escape(self) -> Escape {
+        match self {
+            Self::Text => Escape::Text,
+            Self::Tag | Self::Value(_) => Escape::Attribute,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TemplateError {
+    Io(std::io::Error),
+    UnclosedPlaceholder { offset: usize },
+    InvalidPlaceholder(String),
+    MissingValue(String),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::UnclosedPlaceholder { offset } => {
+                write!(f, "unclosed placeholder at offset {}", offset)
+            }
+            Self::InvalidPlaceholder(name) => write!(f, "invalid placeholder name \"{}\"", name),
+            Self::MissingValue(name) => write!(f, "no value for placeholder \"{}\"", name),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for TemplateError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Values used to fill the placeholders of a [`CompiledTemplate`].
+pub trait TemplateContext {
+    fn value(&self, name: &str) -> Option<&str>;
+}
+
+impl<K, V, S> TemplateContext for HashMap<K, V, S>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+    S: std::hash::BuildHasher,
+{
+    fn value(&self, name: &str) -> Option<&str> {
+        self.get(name).map(AsRef::as_ref)
+    }
+}
+
+impl<K, V> TemplateContext for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: AsRef<str>,
+{
+    fn value(&self, name: &str) -> Option<&str> {
+        self.get(name).map(AsRef::as_ref)
+    }
+}
+
+impl<K, V> TemplateContext for [(K, V)]
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn value(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(key, _)| key.as_ref() == name)
+            .map(|(_, value)| value.as_ref())
+    }
+}
+
+impl<K, V, const N: usize> TemplateContext for [(K, V); N]
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn value(&self, name: &str) -> Option<&str> {
+        self.as_slice().value(name)
+    }
+}
+
+impl<T: TemplateContext + ?Sized> TemplateContext for &T {
+    fn value(&self, name: &str) -> Option<&str> {
+        (**self).value(name)
+    }
+}
+
+impl Mjml {
+    pub fn compile(&self) -> Result<CompiledTemplate, TemplateError> {
+        CompiledTemplate::parse(self.render_to_string()?)
+    }
+}
+
+impl CompiledTemplate {
+    pub fn parse(source: String) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut slots: Vec<String> = Vec::new();
+        let mut statics = String::with_capacity(source.len());
+
+        let mut rest = source.as_str();
+        let mut offset = 0;
+        let mut markup = Markup::Text;
+        while let Some(start) = rest.find(PLACEHOLDER_START) {
+            if rest[..start].ends_with('\\') {
+                let literal = start - 1;
+                push_static(&mut statics, &mut segments, &rest[..literal]);
+                push_static(&mut statics, &mut segments, PLACEHOLDER_START);
+                markup = markup.after(&rest[..literal]);
+                let consumed = literal + LITERAL_START.len();
+                rest = &rest[consumed..];
+                offset += consumed;
+                continue;
+            }
+            push_static(&mut statics, &mut segments, &rest[..start]);
+            markup = markup.after(&rest[..start]);
+
+            let raw = rest[start..].starts_with(RAW_START);
+            let (start_len, end_marker) = match raw {
+                true => (RAW_START.len(), RAW_END),
+                false => (PLACEHOLDER_START.len(), PLACEHOLDER_END),
+            };
+            let after_start = &rest[start + start_len..];
+            let Some(end) = after_start.find(end_marker) else {
+                return Err(TemplateError::UnclosedPlaceholder {
+                    offset: offset + start,
+                });
+            };
+            let name = after_start[..end].trim();
+            if !is_valid_placeholder(name) {
+                return Err(TemplateError::InvalidPlaceholder(name.to_string()));
+            }
+            let slot = match slots.iter().position(|slot| slot == name) {
+                Some(slot) => slot,
+                None => {
+                    slots.push(name.to_string());
+                    slots.len() - 1
+                }
+            };
+            let escape = match raw {
+                true => Escape::Raw,
+                false => markup.escape(),
+            };
+            segments.push(Segment::Slot(slot, escape));
+
+            let consumed = start + start_len + end + end_marker.len();
+            rest = &rest[consumed..];
+            offset += consumed;
+        }
+        push_static(&mut statics, &mut segments, rest);
+        statics.shrink_to_fit();
+
+        Ok(Self {
+            statics,
+            segments,
+            slots,
+        })
+    }
+
+    /// Names of the placeholders, in order of first appearance.
+    pub fn placeholders(&self) -> &[String] {
+        &self.slots
+    }
+
+    /// Exact length of the output for the given context.
+    pub fn rendered_len<C: TemplateContext + ?Sized>(
+        &self,
+        context: &C,
+    ) -> Result<usize, TemplateError> {
+        self.segments.iter().try_fold(0, |len, segment| {
+            Ok(len
+                + match segment {
+                    Segment::Static(range) => range.len(),
+                    Segment::Slot(..) => self.segment(context, segment)?.len(),
+                })
+        })
+    }
+
+    /// Appends the rendered template to `out`, growing it at most once.
+    pub fn render_into<C: TemplateContext + ?Sized>(
+        &self,
+        context: &C,
+        out: &mut Vec<u8>,
+    ) -> Result<(), TemplateError> {
+        out.reserve(self.rendered_len(context)?);
+        for segment in &self.segments {
+            out.extend_from_slice(self.segment(context, segment)?.as_bytes());
+        }
+        Ok(())
+    }
+
+    pub fn render_into_string<C: TemplateContext + ?Sized>(
+        &self,
+        context: &C,
+        out: &mut String,
+    ) -> Result<(), TemplateError> {
+        out.reserve(self.rendered_len(context)?);
+        for segment in &self.segments {
+            out.push_str(&self.segment(context, segment)?);
+        }
+        Ok(())
+    }
+
+    pub fn render_to_string<C: TemplateContext + ?Sized>(
+        &self,
+        context: &C,
+    ) -> Result<String, TemplateError> {
+        let mut out = String::new();
+        self.render_into_string(context, &mut out)?;
+        Ok(out)
+    }
+
+    pub fn render<C: TemplateContext + ?Sized>(
+        &self,
+        context: &C,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<(), TemplateError> {
+        self.rendered_len(context)?;
+        for segment in &self.segments {
+            writer.write_all(self.segment(context, segment)?.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn segment<'a, C: TemplateContext + ?Sized>(
+        &'a self,
+        context: &'a C,
+        segment: &Segment,
+    ) -> Result<Cow<'a, str>, TemplateError> {
+        match segment {
+            Segment::Static(range) => Ok(Cow::Borrowed(&self.statics[range.clone()])),
+            Segment::Slot(slot, escape) => {
+                Ok(escape_value(self.slot_value(context, *slot)?, *escape))
+            }
+        }
+    }
+
+    fn slot_value<'a, C: TemplateContext + ?Sized>(
+        &self,
+        context: &'a C,
+        slot: usize,
+    ) -> Result<&'a str, TemplateError> {
+        let name = &self.slots[slot];
+        context
+            .value(name)
+            .ok_or_else(|| TemplateError::MissingValue(name.clone()))
+    }
+}
+
+/// Renders one email per context across the rayon thread pool.
+///
+/// Results are returned in the order of `contexts`; a failing context does not abort the others.
+#[cfg(feature = "rayon")]
+pub fn render_batch<I>(
+    template: &CompiledTemplate,
+    contexts: I,
+) -> Vec<Result<String, TemplateError>>
+where
+    I: IntoIterator,
+    I::Item: TemplateContext + Send,
+{
+    use rayon::prelude::*;
+
+    contexts
+        .into_iter()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|context| template.render_to_string(&context))
+        .collect()
+}
+
+fn push_static(statics: &mut String, segments: &mut Vec<Segment>, content: &str) {
+    if content.is_empty() {
+        return;
+    }
+    let start = statics.len();
+    statics.push_str(content);
+    segments.push(Segment::Static(start..statics.len()));
+}
+
+fn escape_value(value: &str, escape: Escape) -> Cow<'_, str> {
+    let special: &[char] = match escape {
+        Escape::Raw => return Cow::Borrowed(value),
+        Escape::Text => &['&', '<', '>'],
+        Escape::Attribute => &['&', '<', '>', '"', '\''],
+    };
+    if !value.contains(special) {
+        return Cow::Borrowed(value);
+    }
+    let mut escaped = String::with_capacity(value.len() + 16);
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if escape == Escape::Attribute => escaped.push_str("&quot;"),
+            '\'' if escape == Escape::Attribute => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+fn is_valid_placeholder(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::mj_body::MjBody;
+    use crate::model::mj_column::MjColumn;
+    use crate::model::mj_section::MjSection;
+    use crate::model::mj_text::MjText;
+    use crate::model::mjml::Mjml;
+    use crate::model::{BodyNode, ColumnNode, SectionNode};
+    use crate::template::{CompiledTemplate, TemplateError};
+    use std::collections::HashMap;
+
+    fn greeting() -> Mjml {
+        Mjml {
+            mj_raw_file_start: None,
+            mj_body: MjBody {
+                content: vec![BodyNode::Section(MjSection {
+                    attributes: Default::default(),
+                    content: vec![SectionNode::Column(MjColumn {
+                        content: vec![ColumnNode::Text(MjText {
+                            content: "Hello {{ name }}, welcome to {{company}}!".to_string(),
+                            attributes: Default::default(),
+                        })],
+                        attributes: Default::default(),
+                    })],
+                })],
+                attributes: Default::default(),
+            },
+            mj_head: None,
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn render_matches_direct_rendering() {
+        let template = greeting().compile().unwrap();
+        assert_eq!(template.placeholders(), ["name", "company"]);
+
+        let rendered = template
+            .render_to_string(&[("name", "Ada"), ("company", "ACME")])
+            .unwrap();
+        let expected = greeting()
+            .render_to_string()
+            .unwrap()
+            .replace("{{ name }}", "Ada")
+            .replace("{{company}}", "ACME");
+        assert_eq!(rendered, expected);
+
+        let mut bytes = Vec::new();
+        template
+            .render_into(
+                &HashMap::from([("name", "Ada"), ("company", "ACME")]),
+                &mut bytes,
+            )
+            .unwrap();
+        assert_eq!(bytes, expected.as_bytes());
+    }
+
+    #[test]
+    fn missing_value() {
+        let template = greeting().compile().unwrap();
+        let mut out = Vec::new();
+        let err = template
+            .render_into(&[("name", "Ada")], &mut out)
+            .unwrap_err();
+        assert!(matches!(err, TemplateError::MissingValue(name) if name == "company"));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn escaped_values() {
+        let template = CompiledTemplate::parse(
+            r#"<mj-button href="{{ url }}" title='{{name}}'>Hi {{ name }}, {{{ html }}}</mj-button>"#
+                .to_string(),
+        )
+        .unwrap();
+        assert_eq!(template.placeholders(), ["url", "name", "html"]);
+        let context = [
+            ("url", "https://example.com/?a=1&b=\"2\""),
+            ("name", "<Ada> 'A' \"L\""),
+            ("html", "<b>&amp;</b>"),
+        ];
+        let rendered = template.render_to_string(&context).unwrap();
+        assert_eq!(
+            rendered,
+            "<mj-button href=\"https://example.com/?a=1&amp;b=&quot;2&quot;\" \
+             title='&lt;Ada&gt; &#39;A&#39; &quot;L&quot;'>\
+             Hi &lt;Ada&gt; 'A' \"L\", <b>&amp;</b></mj-button>"
+        );
+        assert_eq!(template.rendered_len(&context).unwrap(), rendered.len());
+    }
+
+    #[test]
+    fn escaped_values_in_rendering() {
+        let mut mjml = greeting();
+        mjml.attributes.lang = Some("{{ lang }}".to_string());
+        let rendered = mjml
+            .compile()
+            .unwrap()
+            .render_to_string(&[
+                ("name", "<script>"),
+                ("company", "A&B"),
+                ("lang", "en\" dir=\"rtl"),
+            ])
+            .unwrap();
+        assert!(rendered.contains("Hello &lt;script&gt;, welcome to A&amp;B!"));
+        assert!(rendered.starts_with(r#"<mjml lang="en&quot; dir=&quot;rtl">"#));
+    }
+
+    #[test]
+    fn literal_placeholders() {
+        let template =
+            CompiledTemplate::parse(r#"\{{ name }} {{ name }} \{{{ raw }}}"#.to_string()).unwrap();
+        assert_eq!(template.placeholders(), ["name"]);
+        assert_eq!(
+            template.render_to_string(&[("name", "Ada")]).unwrap(),
+            "{{ name }} Ada {{{ raw }}}"
+        );
+    }
+
+    #[test]
+    fn invalid_placeholders() {
+        assert!(matches!(
+            CompiledTemplate::parse("a {{ b".to_string()),
+            Err(TemplateError::UnclosedPlaceholder { offset: 2 })
+        ));
+        assert!(matches!(
+            CompiledTemplate::parse("a {{ b c }}".to_string()),
+            Err(TemplateError::InvalidPlaceholder(_))
+        ));
+        assert!(matches!(
+            CompiledTemplate::parse("a {{{ b }}".to_string()),
+            Err(TemplateError::UnclosedPlaceholder { offset: 2 })
+        ));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn render_batch_keeps_order() {
+        let template = greeting().compile().unwrap();
+        let contexts = (0..100).map(|i| {
+            let mut context = HashMap::from([("name".to_string(), format!("user{}", i))]);
+            if i % 10 != 3 {
+                context.insert("company".to_string(), "ACME".to_string());
+            }
+            context
+        });
+
+        let results = crate::template::render_batch(&template, contexts);
+        assert_eq!(results.len(), 100);
+        for (i, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(rendered) => {
+                    assert_ne!(i % 10, 3);
+                    assert!(rendered.contains(&format!("Hello user{}, welcome to ACME!", i)));
+                }
+                Err(err) => {
+                    assert_eq!(i % 10, 3);
+                    assert!(matches!(err, TemplateError::MissingValue(name) if name == "company"));
+                }
+            }
+        }
+    }
+}
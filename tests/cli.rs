@@ -0,0 +1,226 @@
+#![cfg(feature = "cli")]
+
+use assert_cmd::Command;
+use std::path::PathBuf;
+
+const TEMPLATE: &str = r##"<mjml lang="en">
+  <mj-body>
+    <mj-section padding="20px">
+      <mj-column>
+        <mj-text color="#333333">Hello {{ name }}</mj-text>
+        <mj-button href="https://example.com" css-class="cta">Confirm</mj-button>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>
+"##;
+
+const INVALID: &str = "<mjml><mj-body><mj-unknown /></mj-body></mjml>";
+
+fn mjml_builder() -> Command {
+    Command::cargo_bin("mjml-builder").unwrap()
+}
+
+/// An empty directory of the temporary directory, unique to the test.
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mjml-builder-{}-{}", std::process::id(), test));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn stdout(command: &mut Command) -> String {
+    let output = command.assert().success().get_output().stdout.clone();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn validate() {
+    let dir = temp_dir("validate");
+    std::fs::write(dir.join("welcome.mjml"), TEMPLATE).unwrap();
+    mjml_builder().arg("validate").arg(&dir).assert().success();
+
+    std::fs::write(dir.join("invalid.mjml"), INVALID).unwrap();
+    let output = mjml_builder()
+        .arg("validate")
+        .arg(&dir)
+        .assert()
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8(output).unwrap();
+    assert!(stderr.contains("invalid.mjml"));
+    assert!(!stderr.contains("welcome.mjml"));
+    assert!(stderr.contains("1 file(s) are invalid"));
+}
+
+#[test]
+fn validate_missing_file() {
+    let dir = temp_dir("validate_missing_file");
+    mjml_builder()
+        .arg("validate")
+        .arg(dir.join("missing.mjml"))
+        .assert()
+        .failure();
+}
+
+#[test]
+fn fmt() {
+    let dir = temp_dir("fmt");
+    let path = dir.join("welcome.mjml");
+    std::fs::write(&path, TEMPLATE).unwrap();
+
+    mjml_builder()
+        .args(["fmt", "--check"])
+        .arg(&path)
+        .assert()
+        .failure();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), TEMPLATE);
+
+    mjml_builder().arg("fmt").arg(&path).assert().success();
+    let formatted = std::fs::read_to_string(&path).unwrap();
+    assert_ne!(formatted, TEMPLATE);
+    // attributes filled in by the model are not written
+    assert!(!formatted.contains("<mj-body width"));
+    assert!(formatted.contains(r#"padding="20px""#));
+
+    mjml_builder()
+        .args(["fmt", "--check"])
+        .arg(&path)
+        .assert()
+        .success();
+    mjml_builder().arg("fmt").arg(&path).assert().success();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), formatted);
+}
+
+#[test]
+fn fmt_comments() {
+    let dir = temp_dir("fmt_comments");
+    let path = dir.join("commented.mjml");
+    let source = TEMPLATE.replace("<mj-body>", "<mj-body>\n    <!-- greeting -->");
+    std::fs::write(&path, &source).unwrap();
+
+    mjml_builder().arg("fmt").arg(&path).assert().success();
+    let formatted = std::fs::read_to_string(&path).unwrap();
+    assert!(formatted.contains("\t\t<!-- greeting -->\n\t\t<mj-section padding=\"20px\">"));
+
+    mjml_builder()
+        .args(["fmt", "--strip-comments"])
+        .arg(&path)
+        .assert()
+        .success();
+    assert!(!std::fs::read_to_string(&path).unwrap().contains("<!--"));
+}
+
+#[test]
+fn fmt_stdin() {
+    let dir = temp_dir("fmt_stdin");
+    let formatted = stdout(
+        mjml_builder()
+            .current_dir(&dir)
+            .args(["fmt", "-"])
+            .write_stdin(TEMPLATE),
+    );
+    assert!(formatted.starts_with("<mjml lang=\"en\">\n\t<mj-body>\n"));
+    assert!(!dir.join("-").exists());
+
+    mjml_builder()
+        .args(["fmt", "--check", "-"])
+        .write_stdin(TEMPLATE)
+        .assert()
+        .failure();
+    let output = mjml_builder()
+        .args(["fmt", "--check", "-"])
+        .write_stdin(formatted)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(output.is_empty());
+}
+
+#[test]
+fn fmt_invalid() {
+    let dir = temp_dir("fmt_invalid");
+    let path = dir.join("invalid.mjml");
+    std::fs::write(&path, INVALID).unwrap();
+    mjml_builder().arg("fmt").arg(&path).assert().failure();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), INVALID);
+}
+
+#[test]
+fn convert() {
+    let dir = temp_dir("convert");
+    let path = dir.join("welcome.mjml");
+    std::fs::write(&path, TEMPLATE).unwrap();
+
+    let mjml_json = stdout(
+        mjml_builder()
+            .arg("convert")
+            .arg(&path)
+            .args(["--to", "mjml-json"]),
+    );
+    let value: serde_json::Value = serde_json::from_str(&mjml_json).unwrap();
+    assert_eq!(value["tagName"], "mjml");
+    assert_eq!(value["attributes"]["lang"], "en");
+
+    let json_path = dir.join("welcome.json");
+    mjml_builder()
+        .arg("convert")
+        .arg(&path)
+        .args(["--to", "json", "--output"])
+        .arg(&json_path)
+        .assert()
+        .success();
+    let yaml = stdout(
+        mjml_builder()
+            .arg("convert")
+            .arg(&json_path)
+            .args(["--to", "yaml"]),
+    );
+    let mjml = stdout(
+        mjml_builder()
+            .args(["convert", "-", "--from", "yaml", "--to", "mjml"])
+            .write_stdin(yaml),
+    );
+    let from_mjml_json = stdout(
+        mjml_builder()
+            .args(["convert", "-", "--to", "mjml"])
+            .write_stdin(mjml_json),
+    );
+    assert_eq!(mjml, from_mjml_json);
+    assert!(mjml.contains("Hello {{ name }}"));
+}
+
+#[test]
+fn convert_invalid() {
+    mjml_builder()
+        .args(["convert", "-", "--to", "json"])
+        .write_stdin(INVALID)
+        .assert()
+        .failure();
+    mjml_builder()
+        .args(["convert", "-", "--from", "json", "--to", "mjml"])
+        .write_stdin("{")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn text() {
+    let text = stdout(mjml_builder().args(["text", "-"]).write_stdin(TEMPLATE));
+    assert!(text.contains("Hello {{ name }}"));
+    assert!(text.contains("Confirm (https://example.com)"));
+    assert!(!text.contains('<'));
+}
+
+#[test]
+fn usage() {
+    mjml_builder().assert().failure();
+    mjml_builder()
+        .args(["convert", "-", "--to", "html"])
+        .assert()
+        .failure();
+}